@@ -0,0 +1,585 @@
+//! Assignment Heatmap
+//!
+//! Grid of assessors (columns) against applications (rows) showing which assessor
+//! is assigned to which application and the status of each assignment.
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+use std::cell::Cell;
+use std::collections::HashMap;
+
+use super::common::{get_canvas_context, track_context_loss, surface_context_loss, clear_canvas, readable_on, payload_hash, is_same_payload, render_image_data, draw_legend_swatch, now_ms, RenderTiming, ChartConfig, HitTestResult, SwatchShape};
+use super::network_graph::{NetworkEdge, NetworkNode, NodeType};
+use super::draw_target::DrawTarget;
+
+/// Width reserved for application labels on the left
+const LABEL_WIDTH: f64 = 100.0;
+
+/// Row or column header entry
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct AxisEntry {
+    id: String,
+    label: String,
+}
+
+/// Assessor × application assignment heatmap
+#[wasm_bindgen]
+pub struct AssignmentHeatmapChart {
+    canvas_id: String,
+    config: ChartConfig,
+    assessors: Vec<AxisEntry>,
+    applications: Vec<AxisEntry>,
+    // Row-major (application, assessor) status; None when unassigned
+    statuses: Vec<Option<String>>,
+    hovered_cell: Option<(usize, usize)>,
+    scroll_offset: f64,
+    visible_rows: usize,
+    status_colors: HashMap<String, String>,
+    // Fingerprint of the last loaded payload
+    data_hash: Option<u64>,
+    render_timing: RenderTiming,
+    context_lost: Cell<bool>,
+}
+
+#[wasm_bindgen]
+impl AssignmentHeatmapChart {
+    /// Create a new assignment heatmap chart
+    #[wasm_bindgen(constructor)]
+    pub fn new(canvas_id: &str, config_js: JsValue) -> Result<AssignmentHeatmapChart, JsValue> {
+        let config: ChartConfig = serde_wasm_bindgen::from_value(config_js)
+            .unwrap_or_else(|_| ChartConfig::default());
+        Ok(Self::with_config(canvas_id, config))
+    }
+
+    /// Create a chart from an already-parsed config
+    pub(crate) fn with_config(canvas_id: &str, config: ChartConfig) -> Self {
+Self {
+            canvas_id: canvas_id.to_string(),
+            config,
+            assessors: Vec::new(),
+            applications: Vec::new(),
+            statuses: Vec::new(),
+            hovered_cell: None,
+            scroll_offset: 0.0,
+            visible_rows: 20,
+            status_colors: HashMap::new(),
+            data_hash: None,
+            render_timing: RenderTiming::default(),
+            context_lost: Cell::new(false),
+        }
+    }
+
+    /// Set data from the same nodes and edges used by the network graph
+    pub fn set_data(&mut self, nodes_js: JsValue, edges_js: JsValue) -> Result<(), JsValue> {
+        self.set_data_with_options(nodes_js, edges_js, false)
+    }
+
+    /// Set data from the same nodes and edges used by the network graph; identical payloads are skipped unless `force` is set
+    pub fn set_data_with_options(&mut self, nodes_js: JsValue, edges_js: JsValue, force: bool) -> Result<(), JsValue> {
+        let hash = payload_hash(&[&nodes_js, &edges_js]);
+        if is_same_payload(hash, self.data_hash, force) {
+            return Ok(());
+        }
+
+        self.load_data(nodes_js, edges_js)?;
+        self.data_hash = hash;
+        Ok(())
+    }
+
+    fn load_data(&mut self, nodes_js: JsValue, edges_js: JsValue) -> Result<(), JsValue> {
+        let nodes: Vec<NetworkNode> = serde_wasm_bindgen::from_value(nodes_js)?;
+        let edges: Vec<NetworkEdge> = serde_wasm_bindgen::from_value(edges_js)?;
+        self.load_graph(&nodes, &edges);
+        Ok(())
+    }
+
+    /// Rebuild the matrix from parsed nodes and edges
+    fn load_graph(&mut self, nodes: &[NetworkNode], edges: &[NetworkEdge]) {
+        self.assessors.clear();
+        self.applications.clear();
+        let mut assessor_index = HashMap::new();
+        let mut application_index = HashMap::new();
+
+        for node in nodes {
+            let entry = AxisEntry { id: node.id.clone(), label: node.label.clone() };
+            match node.node_type {
+                NodeType::Assessor => {
+                    assessor_index.insert(node.id.clone(), self.assessors.len());
+                    self.assessors.push(entry);
+                }
+                NodeType::Application => {
+                    application_index.insert(node.id.clone(), self.applications.len());
+                    self.applications.push(entry);
+                }
+            }
+        }
+
+        let cols = self.assessors.len();
+        self.statuses = vec![None; self.applications.len() * cols];
+
+        // Edges may point either way between an assessor and an application
+        for edge in edges {
+            let pair = match (
+                assessor_index.get(&edge.source),
+                application_index.get(&edge.target),
+                assessor_index.get(&edge.target),
+                application_index.get(&edge.source),
+            ) {
+                (Some(&col), Some(&row), _, _) | (_, _, Some(&col), Some(&row)) => Some((row, col)),
+                _ => None,
+            };
+
+            if let Some((row, col)) = pair {
+                let status = edge.status.clone().unwrap_or_else(|| "pending".to_string());
+                self.statuses[row * cols + col] = Some(status);
+            }
+        }
+
+        self.hovered_cell = None;
+        self.scroll_offset = 0.0;
+    }
+
+    /// Replace the color theme (a `ColorTheme` object) and re-render
+    pub fn set_theme(&mut self, theme_js: JsValue) -> Result<(), JsValue> {
+        self.config.theme = serde_wasm_bindgen::from_value(theme_js)?;
+        self.render()
+    }
+
+    /// Whether the last render failed because the 2D context was lost
+    pub fn is_context_lost(&self) -> bool {
+        self.context_lost.get()
+    }
+
+    /// Re-acquire the canvas context after a loss and re-render
+    pub fn restore(&mut self) -> Result<(), JsValue> {
+        self.context_lost.set(false);
+        self.render()
+    }
+
+    /// Re-render after a state change, warning once if the context was lost
+    fn refresh(&self) {
+        surface_context_loss(&self.context_lost, &self.canvas_id, || self.render());
+    }
+
+    /// Render the heatmap
+    pub fn render(&self) -> Result<(), JsValue> {
+        if !self.config.has_drawable_size() {
+            return Ok(());
+        }
+
+        let (canvas, ctx) = get_canvas_context(&self.canvas_id)?;
+        let start = now_ms();
+        let result = track_context_loss(&self.context_lost, &ctx, || self.draw_frame(&canvas, &ctx));
+        self.render_timing.record(now_ms() - start);
+        result
+    }
+
+    /// Duration of the last render in milliseconds
+    pub fn last_render_ms(&self) -> f64 {
+        self.render_timing.last()
+    }
+
+    /// Average render duration over recent renders in milliseconds
+    pub fn avg_render_ms(&self) -> f64 {
+        self.render_timing.average()
+    }
+
+    /// Render offscreen and return raw RGBA bytes for pixel-level regression tests
+    pub fn render_to_image_data(&self, width: u32, height: u32) -> Result<Vec<u8>, JsValue> {
+        render_image_data(&self.config, width, height, |canvas, ctx| self.draw_frame(canvas, ctx))
+    }
+
+    fn draw_frame(&self, canvas: &HtmlCanvasElement, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
+        canvas.set_width(self.config.width as u32);
+        canvas.set_height(self.config.height as u32);
+
+        self.draw_to(ctx)
+    }
+
+    /// Draw the full chart onto any target; the canvas must already be sized
+    pub(crate) fn draw_to<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        clear_canvas(ctx, self.config.width, self.config.height, &self.config.theme.background);
+
+        if self.applications.is_empty() || self.assessors.is_empty() {
+            self.draw_empty_state(ctx)?;
+            return Ok(());
+        }
+
+        // Draw header
+        self.draw_header(ctx)?;
+
+        // Draw row labels
+        self.draw_row_labels(ctx)?;
+
+        // Draw column headers
+        self.draw_column_headers(ctx)?;
+
+        // Draw cells
+        self.draw_cells(ctx)?;
+
+        // Draw legend
+        if self.config.show_legend {
+            self.draw_legend(ctx)?;
+        }
+
+        Ok(())
+    }
+
+    /// Cell dimensions for the current layout
+    fn cell_size(&self) -> (f64, f64) {
+        let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
+        let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
+        let row_count = self.visible_rows.min(self.applications.len()).max(1);
+
+        (
+            (plot_width - LABEL_WIDTH) / self.assessors.len().max(1) as f64,
+            plot_height / row_count as f64,
+        )
+    }
+
+    fn start_row(&self) -> usize {
+        let (_, cell_height) = self.cell_size();
+        (self.scroll_offset / cell_height) as usize
+    }
+
+    /// Color for an assignment status; unassigned cells use the grid color
+    fn status_color(&self, status: Option<&str>) -> &str {
+        if let Some(color) = status.and_then(|s| self.status_colors.get(s)) {
+            return color;
+        }
+        match status {
+            Some("completed") => &self.config.theme.success,
+            Some("in_progress") => &self.config.theme.warning,
+            Some(_) => &self.config.theme.secondary,
+            None => &self.config.theme.grid,
+        }
+    }
+
+    fn status_at(&self, row: usize, col: usize) -> Option<&str> {
+        self.statuses
+            .get(row * self.assessors.len() + col)
+            .and_then(|s| s.as_deref())
+    }
+
+    /// Matrix cell (row, col) under a screen position, if any
+    fn cell_at(&self, x: f64, y: f64) -> Option<(usize, usize)> {
+        let (cell_width, cell_height) = self.cell_size();
+        let grid_x = x - self.config.padding.left - LABEL_WIDTH;
+        let grid_y = y - self.config.padding.top;
+        let row_count = self.visible_rows.min(self.applications.len());
+
+        if grid_x < 0.0 || grid_y < 0.0 || cell_width <= 0.0 || cell_height <= 0.0 {
+            return None;
+        }
+
+        let col = (grid_x / cell_width) as usize;
+        let visible_row = (grid_y / cell_height) as usize;
+        let row = self.start_row() + visible_row;
+
+        (col < self.assessors.len() && visible_row < row_count && row < self.applications.len())
+            .then_some((row, col))
+    }
+
+    fn draw_header<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        ctx.set_fill_style(&self.config.theme.text);
+        ctx.set_font(&self.config.title_font());
+        ctx.set_text_align("center");
+        ctx.fill_text(
+            "Assessor Assignments",
+            self.config.width / 2.0,
+            20.0,
+        )?;
+        Ok(())
+    }
+
+    fn draw_row_labels<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        let (_, cell_height) = self.cell_size();
+        let row_count = self.visible_rows.min(self.applications.len());
+        let start_row = self.start_row();
+
+        ctx.set_fill_style(&self.config.theme.text);
+        ctx.set_font(&self.config.font("", self.config.axis_size()));
+        ctx.set_text_align("right");
+        ctx.set_text_baseline("middle");
+
+        for (i, app) in self.applications.iter().enumerate().skip(start_row).take(row_count) {
+            let y = self.config.padding.top + (i - start_row) as f64 * cell_height + cell_height / 2.0;
+
+            let label = if app.label.chars().count() > 12 {
+                format!("{}...", app.label.chars().take(9).collect::<String>())
+            } else {
+                app.label.clone()
+            };
+
+            ctx.fill_text(&label, self.config.padding.left + LABEL_WIDTH - 10.0, y)?;
+        }
+
+        ctx.set_text_baseline("alphabetic");
+        Ok(())
+    }
+
+    fn draw_column_headers<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        let (cell_width, _) = self.cell_size();
+
+        ctx.set_fill_style(&self.config.theme.text);
+        ctx.set_font(&self.config.font("", self.config.axis_size()));
+        ctx.set_text_align("center");
+
+        // Fit assessor labels to the column width (roughly 7px per character)
+        let max_chars = ((cell_width / 7.0) as usize).max(2);
+
+        for (col, assessor) in self.assessors.iter().enumerate() {
+            let x = self.config.padding.left + LABEL_WIDTH + col as f64 * cell_width + cell_width / 2.0;
+            let label: String = assessor.label.chars().take(max_chars).collect();
+            ctx.fill_text(&label, x, self.config.padding.top - 10.0)?;
+        }
+
+        Ok(())
+    }
+
+    fn draw_cells<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        let (cell_width, cell_height) = self.cell_size();
+        let row_count = self.visible_rows.min(self.applications.len());
+        let start_row = self.start_row();
+        let end_row = (start_row + row_count).min(self.applications.len());
+
+        for row in start_row..end_row {
+            for col in 0..self.assessors.len() {
+                let x = self.config.padding.left + LABEL_WIDTH + col as f64 * cell_width;
+                let y = self.config.padding.top + (row - start_row) as f64 * cell_height;
+                let is_hovered = self.hovered_cell == Some((row, col));
+
+                ctx.set_fill_style(self.status_color(self.status_at(row, col)));
+                ctx.set_global_alpha(if is_hovered { 1.0 } else { 0.85 });
+                ctx.fill_rect(x + 1.0, y + 1.0, cell_width - 2.0, cell_height - 2.0);
+                ctx.set_global_alpha(1.0);
+
+                if is_hovered {
+                    ctx.set_stroke_style(&self.config.theme.primary);
+                    ctx.set_line_width(2.0);
+                    ctx.stroke_rect(x, y, cell_width, cell_height);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn draw_legend<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        let legend_y = self.config.height - 25.0;
+        let mut legend_x = self.config.padding.left;
+
+        ctx.set_font(&self.config.font("", self.config.legend_size()));
+        ctx.set_text_align("left");
+
+        let mut entries = vec![
+            ("Unassigned".to_string(), None),
+            ("Pending".to_string(), Some("pending")),
+            ("In progress".to_string(), Some("in_progress")),
+            ("Completed".to_string(), Some("completed")),
+        ];
+
+        // Custom statuses get their own entries after the built-in ones
+        let mut custom: Vec<&str> = self.status_colors.keys()
+            .map(String::as_str)
+            .filter(|s| !matches!(*s, "pending" | "in_progress" | "completed"))
+            .collect();
+        custom.sort_unstable();
+        entries.extend(custom.into_iter().map(|s| (s.replace('_', " "), Some(s))));
+
+        for (label, status) in entries {
+            draw_legend_swatch(ctx, SwatchShape::Square, self.status_color(status), legend_x, legend_y - 10.0, 12.0)?;
+            ctx.set_fill_style(&self.config.theme.text);
+            ctx.fill_text(&label, legend_x + 18.0, legend_y)?;
+            legend_x += 100.0;
+        }
+
+        Ok(())
+    }
+
+    fn draw_empty_state<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        ctx.set_fill_style(readable_on(&self.config.theme.secondary, &self.config.theme.background));
+        ctx.set_font(&self.config.font("", self.config.label_size()));
+        ctx.set_text_align("center");
+        ctx.fill_text(
+            "No assignment data available",
+            self.config.width / 2.0,
+            self.config.height / 2.0,
+        )?;
+        Ok(())
+    }
+
+    /// Map assignment statuses to colors (`{status: color}`); unmapped statuses use theme defaults
+    pub fn set_status_colors(&mut self, map_js: JsValue) -> Result<(), JsValue> {
+        self.status_colors = serde_wasm_bindgen::from_value(map_js)?;
+        self.render()
+    }
+
+    /// Handle scroll
+    pub fn on_scroll(&mut self, delta_y: f64) {
+        let (_, cell_height) = self.cell_size();
+        let row_count = self.visible_rows.min(self.applications.len());
+        let max_scroll = (self.applications.len() as f64 - row_count as f64) * cell_height;
+
+        self.scroll_offset = (self.scroll_offset + delta_y).clamp(0.0, max_scroll.max(0.0));
+        self.refresh();
+    }
+
+    /// Handle mouse move
+    pub fn on_mouse_move(&mut self, x: f64, y: f64) -> JsValue {
+        let old_hovered = self.hovered_cell;
+        self.hovered_cell = self.cell_at(x, y);
+
+        if old_hovered != self.hovered_cell {
+            self.refresh();
+        }
+
+        if let Some((row, col)) = self.hovered_cell {
+            let application = &self.applications[row];
+            let assessor = &self.assessors[col];
+            let status = self.status_at(row, col);
+
+            let result = HitTestResult::hit(
+                &format!("{}-{}", application.id, assessor.id),
+                "assignment_cell",
+                serde_json::json!({
+                    "applicationId": application.id,
+                    "application": application.label,
+                    "assessorId": assessor.id,
+                    "assessor": assessor.label,
+                    "assigned": status.is_some(),
+                    "status": status
+                }),
+            );
+            return serde_wasm_bindgen::to_value(&result).unwrap();
+        }
+
+        HitTestResult::miss_value()
+    }
+
+    /// Get statistics
+    pub fn get_stats(&self) -> JsValue {
+        let count = |status: &str| self.statuses.iter()
+            .filter(|s| s.as_deref() == Some(status))
+            .count();
+        let assigned = self.statuses.iter().filter(|s| s.is_some()).count();
+
+        let stats = serde_json::json!({
+            "assessorCount": self.assessors.len(),
+            "applicationCount": self.applications.len(),
+            "assignedCount": assigned,
+            "pendingCount": assigned - count("completed") - count("in_progress"),
+            "inProgressCount": count("in_progress"),
+            "completedCount": count("completed")
+        });
+        serde_wasm_bindgen::to_value(&stats).unwrap()
+    }
+
+    /// One-sentence summary for screen readers (e.g. a canvas `aria-label`)
+    pub fn describe(&self) -> String {
+        let count = |status: &str| self.statuses.iter()
+            .filter(|s| s.as_deref() == Some(status))
+            .count();
+        let assigned = self.statuses.iter().filter(|s| s.is_some()).count();
+
+        format!(
+            "Assignment matrix of {} assessors and {} applications; {} assignments, {} completed, {} in progress",
+            self.assessors.len(),
+            self.applications.len(),
+            assigned,
+            count("completed"),
+            count("in_progress")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, node_type: NodeType) -> NetworkNode {
+        NetworkNode { id: id.to_string(), label: id.to_string(), node_type, size: None, color: None, metadata: None }
+    }
+
+    fn edge(source: &str, target: &str, status: &str) -> NetworkEdge {
+        NetworkEdge {
+            source: source.to_string(),
+            target: target.to_string(),
+            weight: None,
+            color: None,
+            status: Some(status.to_string()),
+            label: None,
+        }
+    }
+
+    #[test]
+    fn describe_counts_assignments_by_status() {
+        let nodes = [
+            node("a1", NodeType::Assessor),
+            node("a2", NodeType::Assessor),
+            node("p1", NodeType::Application),
+            node("p2", NodeType::Application),
+            node("p3", NodeType::Application),
+        ];
+        // Edges run in both directions; the matrix normalises them
+        let edges = [
+            edge("a1", "p1", "completed"),
+            edge("p2", "a1", "in_progress"),
+            edge("a2", "p3", "completed"),
+            edge("a2", "p1", "pending"),
+        ];
+        let mut chart = AssignmentHeatmapChart::with_config("test", ChartConfig::default());
+        chart.load_graph(&nodes, &edges);
+        assert_eq!(
+            chart.describe(),
+            "Assignment matrix of 2 assessors and 3 applications; 4 assignments, 2 completed, 1 in progress"
+        );
+    }
+
+    fn loaded_chart() -> AssignmentHeatmapChart {
+        let nodes = [
+            node("a1", NodeType::Assessor),
+            node("a2", NodeType::Assessor),
+            node("p1", NodeType::Application),
+            node("p2", NodeType::Application),
+            node("p3", NodeType::Application),
+        ];
+        let edges = [edge("a1", "p1", "completed"), edge("p2", "a1", "in_progress"), edge("a2", "p3", "pending")];
+        let mut chart = AssignmentHeatmapChart::with_config("test", ChartConfig::default());
+        chart.load_graph(&nodes, &edges);
+        chart
+    }
+
+    #[test]
+    fn status_color_maps_statuses_to_theme_and_overrides() {
+        let mut chart = loaded_chart();
+        let theme = chart.config.theme.clone();
+
+        assert_eq!(chart.status_color(Some("completed")), theme.success);
+        assert_eq!(chart.status_color(Some("in_progress")), theme.warning);
+        assert_eq!(chart.status_color(Some("pending")), theme.secondary);
+        assert_eq!(chart.status_color(None), theme.grid);
+
+        chart.status_colors.insert("pending".to_string(), "#123456".to_string());
+        assert_eq!(chart.status_color(Some("pending")), "#123456");
+        assert_eq!(chart.status_color(Some("completed")), theme.success);
+    }
+
+    #[test]
+    fn hover_resolves_cell_under_cursor() {
+        let chart = loaded_chart();
+        // 600px of grid across 2 assessors and 300px down 3 applications
+        let (left, top) = (chart.config.padding.left + LABEL_WIDTH, chart.config.padding.top);
+
+        assert_eq!(chart.cell_at(left + 150.0, top + 150.0), Some((1, 0)));
+        assert_eq!(chart.status_at(1, 0), Some("in_progress"));
+        assert_eq!(chart.cell_at(left + 450.0, top + 250.0), Some((2, 1)));
+        assert_eq!(chart.status_at(2, 1), Some("pending"));
+        assert_eq!(chart.status_at(1, 1), None);
+
+        // Row labels and space below the last row are not cells
+        assert_eq!(chart.cell_at(left - 10.0, top + 50.0), None);
+        assert_eq!(chart.cell_at(left + 150.0, top + 310.0), None);
+    }
+}
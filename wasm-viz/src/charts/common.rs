@@ -3,6 +3,16 @@
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use super::draw_target::DrawTarget;
+
+/// Smallest canvas dimension (px) that is worth rendering
+pub const MIN_RENDER_SIZE: f64 = 1.0;
 
 /// Color theme for visualizations
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -12,7 +22,7 @@ pub struct ColorTheme {
     pub success: String,
     pub warning: String,
     pub danger: String,
-    pub background: String,
+    pub background: String, // "transparent" clears instead of filling
     pub text: String,
     pub grid: String,
     pub accent: Vec<String>,
@@ -68,6 +78,33 @@ impl Default for Padding {
     }
 }
 
+/// Per-role text styling; unset sizes follow `font_size` so existing configs render unchanged
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Typography {
+    /// Chart titles (default `font_size + 2`)
+    pub title_size: Option<f64>,
+    /// CSS weight for titles (default "bold")
+    pub title_weight: Option<String>,
+    /// Tick, row/column and in-plot value labels (default `font_size - 2`)
+    pub axis_size: Option<f64>,
+    /// Axis titles, center labels and empty states (default `font_size`)
+    pub label_size: Option<f64>,
+    /// Legend entries (default `font_size - 1`)
+    pub legend_size: Option<f64>,
+    /// Headline figures such as the progress ring's center value (default `font_size * 2.5`)
+    pub value_size: Option<f64>,
+}
+
+/// The `ChartConfig` settings a sparkline overrides
+#[derive(Clone, Copy, Debug)]
+pub struct SparklineBase {
+    padding: Padding,
+    show_grid: bool,
+    show_labels: bool,
+    show_legend: bool,
+}
+
 /// Common chart configuration
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ChartConfig {
@@ -81,6 +118,8 @@ pub struct ChartConfig {
     pub show_legend: bool,
     pub font_family: String,
     pub font_size: f64,
+    #[serde(default)]
+    pub typography: Typography,
 }
 
 impl Default for ChartConfig {
@@ -96,8 +135,81 @@ impl Default for ChartConfig {
             show_legend: true,
             font_family: "Inter, system-ui, sans-serif".to_string(),
             font_size: 12.0,
+            typography: Typography::default(),
+        }
+    }
+}
+
+impl ChartConfig {
+    /// Switch to the compact sparkline layout (no padding, grid, labels or
+    /// legend), returning the overridden settings for `exit_sparkline`
+    pub fn enter_sparkline(&mut self) -> SparklineBase {
+        let base = SparklineBase {
+            padding: self.padding,
+            show_grid: self.show_grid,
+            show_labels: self.show_labels,
+            show_legend: self.show_legend,
+        };
+        self.padding = Padding { top: 0.0, right: 0.0, bottom: 0.0, left: 0.0 };
+        self.show_grid = false;
+        self.show_labels = false;
+        self.show_legend = false;
+        base
+    }
+
+    /// Put back the settings saved by `enter_sparkline`, keeping every other
+    /// change made while the sparkline was shown
+    pub fn exit_sparkline(&mut self, base: SparklineBase) {
+        self.padding = base.padding;
+        self.show_grid = base.show_grid;
+        self.show_labels = base.show_labels;
+        self.show_legend = base.show_legend;
+    }
+
+    /// CSS font string; an empty weight is omitted
+    pub fn font(&self, weight: &str, size: f64) -> String {
+        if weight.is_empty() {
+            format!("{}px {}", size, self.font_family)
+        } else {
+            format!("{} {}px {}", weight, size, self.font_family)
         }
     }
+
+    pub fn title_size(&self) -> f64 {
+        self.typography.title_size.unwrap_or(self.font_size + 2.0)
+    }
+
+    pub fn title_weight(&self) -> &str {
+        self.typography.title_weight.as_deref().unwrap_or("bold")
+    }
+
+    pub fn title_font(&self) -> String {
+        self.font(self.title_weight(), self.title_size())
+    }
+
+    pub fn axis_size(&self) -> f64 {
+        self.typography.axis_size.unwrap_or(self.font_size - 2.0)
+    }
+
+    pub fn label_size(&self) -> f64 {
+        self.typography.label_size.unwrap_or(self.font_size)
+    }
+
+    pub fn legend_size(&self) -> f64 {
+        self.typography.legend_size.unwrap_or(self.font_size - 1.0)
+    }
+
+    pub fn value_size(&self) -> f64 {
+        self.typography.value_size.unwrap_or(self.font_size * 2.5)
+    }
+
+    /// Whether the configured size is large enough to lay out a plot area.
+    ///
+    /// Containers that have not been laid out yet report 0x0, which would turn
+    /// the plot-area math into NaN/Inf geometry.
+    pub fn has_drawable_size(&self) -> bool {
+        self.width >= MIN_RENDER_SIZE && self.height >= MIN_RENDER_SIZE
+    }
 }
 
 /// Get canvas context helper
@@ -117,15 +229,271 @@ pub fn get_canvas_context(canvas_id: &str) -> Result<(HtmlCanvasElement, CanvasR
     Ok((canvas, ctx))
 }
 
+/// Create a detached canvas for offscreen rendering and image export
+pub fn create_offscreen_canvas(width: u32, height: u32) -> Result<(HtmlCanvasElement, CanvasRenderingContext2d), JsValue> {
+    let window = web_sys::window().ok_or("No window")?;
+    let document = window.document().ok_or("No document")?;
+    let canvas = document
+        .create_element("canvas")?
+        .dyn_into::<HtmlCanvasElement>()?;
+
+    canvas.set_width(width);
+    canvas.set_height(height);
+
+    let ctx = canvas
+        .get_context("2d")?
+        .ok_or("Failed to get 2d context")?
+        .dyn_into::<CanvasRenderingContext2d>()?;
+
+    Ok((canvas, ctx))
+}
+
+/// Draw a frame offscreen and return its RGBA pixels scaled to `width` x `height`.
+///
+/// `draw` renders at the chart's configured size; the result is resampled so
+/// callers can request a fixed buffer size for pixel comparisons.
+pub fn render_image_data<F>(config: &ChartConfig, width: u32, height: u32, draw: F) -> Result<Vec<u8>, JsValue>
+where
+    F: FnOnce(&HtmlCanvasElement, &CanvasRenderingContext2d) -> Result<(), JsValue>,
+{
+    if width == 0 || height == 0 {
+        return Err(JsValue::from_str("Image size must be non-zero"));
+    }
+
+    let (frame, frame_ctx) = create_offscreen_canvas(config.width as u32, config.height as u32)?;
+    draw(&frame, &frame_ctx)?;
+
+    let (_, ctx) = create_offscreen_canvas(width, height)?;
+    ctx.draw_image_with_html_canvas_element_and_dw_and_dh(&frame, 0.0, 0.0, width as f64, height as f64)?;
+
+    let image = ctx.get_image_data(0.0, 0.0, width as f64, height as f64)?;
+    Ok(image.data().0)
+}
+
+/// Placement of one source canvas in a composite image
+#[derive(Clone, Debug, Deserialize)]
+struct CompositeCell {
+    x: f64,
+    y: f64,
+    // Defaults to the source canvas size
+    width: Option<f64>,
+    height: Option<f64>,
+}
+
+/// Whole-pixel size that fits every `(x, y, width, height)` placement, or
+/// `None` when the result would be empty
+fn composite_size(placements: &[(f64, f64, f64, f64)]) -> Option<(u32, u32)> {
+    let width = placements.iter().map(|p| p.0 + p.2).fold(0.0, f64::max);
+    let height = placements.iter().map(|p| p.1 + p.3).fold(0.0, f64::max);
+    if width < MIN_RENDER_SIZE || height < MIN_RENDER_SIZE {
+        return None;
+    }
+    Some((width.ceil() as u32, height.ceil() as u32))
+}
+
+/// Stitch several chart canvases into one PNG data URL.
+///
+/// `layout` is an array of `{x, y, width?, height?}` parallel to `canvas_ids`;
+/// the image is sized to fit every cell.
+#[wasm_bindgen]
+pub fn composite_canvases(canvas_ids: Vec<String>, layout: JsValue) -> Result<String, JsValue> {
+    let cells: Vec<CompositeCell> = serde_wasm_bindgen::from_value(layout)?;
+    if cells.len() != canvas_ids.len() {
+        return Err(JsValue::from_str("Layout must have one cell per canvas"));
+    }
+
+    let mut sources = Vec::with_capacity(canvas_ids.len());
+    for (id, cell) in canvas_ids.iter().zip(&cells) {
+        let (canvas, _) = get_canvas_context(id)?;
+        let width = cell.width.unwrap_or(canvas.width() as f64);
+        let height = cell.height.unwrap_or(canvas.height() as f64);
+        sources.push((canvas, cell.x, cell.y, width, height));
+    }
+
+    let placements: Vec<(f64, f64, f64, f64)> = sources.iter().map(|s| (s.1, s.2, s.3, s.4)).collect();
+    let Some((total_width, total_height)) = composite_size(&placements) else {
+        return Err(JsValue::from_str("Composite image would be empty"));
+    };
+
+    let (output, ctx) = create_offscreen_canvas(total_width, total_height)?;
+    for (canvas, x, y, width, height) in &sources {
+        ctx.draw_image_with_html_canvas_element_and_dw_and_dh(canvas, *x, *y, *width, *height)?;
+    }
+
+    output.to_data_url()
+}
+
+/// Check whether a 2D context has been lost (GPU reset, tab backgrounding).
+/// Browsers without `isContextLost` are treated as never losing context.
+pub fn is_context_lost(ctx: &CanvasRenderingContext2d) -> bool {
+    js_sys::Reflect::get(ctx, &JsValue::from_str("isContextLost"))
+        .ok()
+        .and_then(|f| f.dyn_into::<js_sys::Function>().ok())
+        .and_then(|f| f.call0(ctx).ok())
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Run a draw pass and record whether the context was lost.
+///
+/// Loss is detected up front and re-checked when a draw call throws, so the
+/// flag stays set even where callers discard the result. Other draw errors
+/// leave the flag clear.
+pub fn track_context_loss<T, F>(
+    lost: &Cell<bool>,
+    ctx: &T,
+    draw: F,
+) -> Result<(), JsValue>
+where
+    T: DrawTarget + ?Sized,
+    F: FnOnce() -> Result<(), JsValue>,
+{
+    if ctx.is_context_lost() {
+        lost.set(true);
+        return Err(JsValue::from_str("Canvas context lost"));
+    }
+
+    let result = draw();
+    lost.set(result.is_err() && ctx.is_context_lost());
+    result
+}
+
+/// Re-render after a state change where the caller has no error to return.
+/// The first render to find the context lost logs a warning so the host knows
+/// to call `restore()`; the loss itself is kept in `lost` by `track_context_loss`.
+pub fn surface_context_loss<F>(lost: &Cell<bool>, canvas_id: &str, render: F)
+where
+    F: FnOnce() -> Result<(), JsValue>,
+{
+    let was_lost = lost.get();
+    if render().is_err() && lost.get() && !was_lost {
+        web_sys::console::warn_1(&JsValue::from_str(&format!(
+            "Canvas '{}' lost its 2D context; call restore() once it is available",
+            canvas_id
+        )));
+    }
+}
+
+/// Current high-resolution time in milliseconds, falling back to `Date.now()`
+/// where the performance API is unavailable
+pub fn now_ms() -> f64 {
+    web_sys::window()
+        .and_then(|w| w.performance())
+        .map(|p| p.now())
+        .unwrap_or_else(js_sys::Date::now)
+}
+
+/// Cheap fingerprint of JS payloads for change detection.
+///
+/// Returns `None` when a value can't be serialized to JSON, in which case callers
+/// should treat the data as changed.
+pub fn payload_hash(values: &[&JsValue]) -> Option<u64> {
+    let json: Vec<String> = values.iter()
+        .map(|value| js_sys::JSON::stringify(value).ok()?.as_string())
+        .collect::<Option<_>>()?;
+    Some(hash_json(&json))
+}
+
+/// Fingerprint of already-serialized payloads, as used by `payload_hash`
+pub fn hash_json<S: AsRef<str>>(json: &[S]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for text in json {
+        text.as_ref().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Whether `set_data` can skip a payload: it matches the last loaded one and no reload is forced
+pub fn is_same_payload(hash: Option<u64>, last: Option<u64>, force: bool) -> bool {
+    !force && hash.is_some() && hash == last
+}
+
+/// Number of renders averaged by `RenderTiming::average`
+const RENDER_TIMING_WINDOW: usize = 30;
+
+/// Last and rolling-average render durations, updated from `&self` renders
+#[derive(Debug, Default)]
+pub struct RenderTiming {
+    last_ms: Cell<f64>,
+    samples: RefCell<VecDeque<f64>>,
+}
+
+impl RenderTiming {
+    pub fn record(&self, ms: f64) {
+        self.last_ms.set(ms);
+        let mut samples = self.samples.borrow_mut();
+        if samples.len() == RENDER_TIMING_WINDOW {
+            samples.pop_front();
+        }
+        samples.push_back(ms);
+    }
+
+    pub fn last(&self) -> f64 {
+        self.last_ms.get()
+    }
+
+    pub fn average(&self) -> f64 {
+        let samples = self.samples.borrow();
+        if samples.is_empty() {
+            0.0
+        } else {
+            samples.iter().sum::<f64>() / samples.len() as f64
+        }
+    }
+}
+
 /// Clear and prepare canvas for rendering
-pub fn clear_canvas(ctx: &CanvasRenderingContext2d, width: f64, height: f64, bg_color: &str) {
-    ctx.set_fill_style(&JsValue::from_str(bg_color));
-    ctx.fill_rect(0.0, 0.0, width, height);
+pub fn clear_canvas<T: DrawTarget + ?Sized>(ctx: &T, width: f64, height: f64, bg_color: &str) {
+    if is_transparent(bg_color) {
+        ctx.clear_rect(0.0, 0.0, width, height);
+    } else {
+        ctx.set_fill_style(bg_color);
+        ctx.fill_rect(0.0, 0.0, width, height);
+    }
+}
+
+/// Whether a background color means "leave the canvas transparent"
+pub fn is_transparent(color: &str) -> bool {
+    let c = color.trim();
+    c.is_empty() || c.eq_ignore_ascii_case("transparent") || c.eq_ignore_ascii_case("none")
+}
+
+/// A color the canvas accepts as a fill or stroke style. "none" and "" are
+/// accepted for a transparent background but are not CSS colors, so they map
+/// to "transparent".
+pub fn canvas_color(color: &str) -> &str {
+    if is_transparent(color) {
+        "transparent"
+    } else {
+        color
+    }
+}
+
+/// Text color that stays legible on `fill`: `preferred` when it contrasts
+/// enough, otherwise near-black or white depending on the fill's luminance.
+/// Transparent or unparseable fills keep `preferred`, since what shows
+/// through is unknown.
+pub fn readable_on<'a>(preferred: &'a str, fill: &str) -> &'a str {
+    let Some(fill_luminance) = parse_color(fill).map(luminance) else {
+        return preferred;
+    };
+    if parse_color(preferred).is_some_and(|c| (luminance(c) - fill_luminance).abs() >= MIN_TEXT_CONTRAST) {
+        return preferred;
+    }
+    if fill_luminance > 0.5 { "#111827" } else { "#FFFFFF" }
+}
+
+/// Minimum luminance difference between text and the fill behind it
+const MIN_TEXT_CONTRAST: f64 = 0.3;
+
+/// Perceived brightness in 0..=1
+fn luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    (0.299 * r as f64 + 0.587 * g as f64 + 0.114 * b as f64) / 255.0
 }
 
 /// Draw grid lines
-pub fn draw_grid(
-    ctx: &CanvasRenderingContext2d,
+pub fn draw_grid<T: DrawTarget + ?Sized>(
+    ctx: &T,
     config: &ChartConfig,
     x_count: u32,
     y_count: u32,
@@ -133,7 +501,7 @@ pub fn draw_grid(
     let plot_width = config.width - config.padding.left - config.padding.right;
     let plot_height = config.height - config.padding.top - config.padding.bottom;
 
-    ctx.set_stroke_style(&JsValue::from_str(&config.theme.grid));
+    ctx.set_stroke_style(&config.theme.grid);
     ctx.set_line_width(0.5);
 
     // Vertical grid lines
@@ -156,14 +524,15 @@ pub fn draw_grid(
 }
 
 /// Draw axis labels
-pub fn draw_axes(
-    ctx: &CanvasRenderingContext2d,
+pub fn draw_axes<T: DrawTarget + ?Sized>(
+    ctx: &T,
     config: &ChartConfig,
     x_label: &str,
     y_label: &str,
 ) {
-    ctx.set_fill_style(&JsValue::from_str(&config.theme.text));
-    ctx.set_font(&format!("{}px {}", config.font_size, config.font_family));
+    ctx.set_fill_style(&config.theme.text);
+    ctx.set_font(&config.font("", config.label_size()));
+    ctx.set_text_baseline("alphabetic");
 
     // X-axis label
     ctx.set_text_align("center");
@@ -181,6 +550,152 @@ pub fn draw_axes(
     ctx.restore();
 }
 
+/// Marker shape shared by legend swatches and the series they describe
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SwatchShape {
+    Square,
+    Circle,
+    Triangle,
+    Line,
+}
+
+impl SwatchShape {
+    /// Parse a point marker shape: "circle", "square" or "triangle"
+    pub fn parse_marker(shape: &str) -> Result<Self, JsValue> {
+        match shape {
+            "circle" => Ok(SwatchShape::Circle),
+            "square" => Ok(SwatchShape::Square),
+            "triangle" => Ok(SwatchShape::Triangle),
+            _ => Err(JsValue::from_str(&format!("Unknown marker shape: {}", shape))),
+        }
+    }
+}
+
+/// Draw a legend swatch filling the `size` box whose top-left corner is (`x`, `y`).
+/// Styles set for the swatch (fill, stroke, line width) are restored afterwards.
+pub fn draw_legend_swatch<T: DrawTarget + ?Sized>(ctx: &T, shape: SwatchShape, color: &str, x: f64, y: f64, size: f64) -> Result<(), JsValue> {
+    let half = size / 2.0;
+    ctx.save();
+    ctx.set_fill_style(color);
+
+    let result = match shape {
+        SwatchShape::Square => {
+            ctx.fill_rect(x, y, size, size);
+            Ok(())
+        }
+        SwatchShape::Circle => {
+            ctx.begin_path();
+            ctx.arc(x + half, y + half, half, 0.0, 2.0 * std::f64::consts::PI)
+                .map(|_| ctx.fill())
+        }
+        SwatchShape::Triangle => {
+            ctx.begin_path();
+            ctx.move_to(x + half, y);
+            ctx.line_to(x + size, y + size);
+            ctx.line_to(x, y + size);
+            ctx.close_path();
+            ctx.fill();
+            Ok(())
+        }
+        SwatchShape::Line => {
+            ctx.set_stroke_style(color);
+            ctx.set_line_width(2.0);
+            ctx.begin_path();
+            ctx.move_to(x, y + half);
+            ctx.line_to(x + size, y + half);
+            ctx.stroke();
+            Ok(())
+        }
+    };
+
+    ctx.restore();
+    result
+}
+
+/// Draw an x-axis tick label hanging below `y`, rotated counter-clockwise by `degrees`
+pub fn draw_tick_label<T: DrawTarget + ?Sized>(ctx: &T, text: &str, x: f64, y: f64, degrees: f64) -> Result<(), JsValue> {
+    if degrees == 0.0 {
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("top");
+        return ctx.fill_text(text, x, y);
+    }
+
+    ctx.save();
+    ctx.translate(x, y)?;
+    ctx.rotate(-degrees.to_radians())?;
+    ctx.set_text_align("right");
+    ctx.set_text_baseline("middle");
+    let result = ctx.fill_text(text, 0.0, 0.0);
+    ctx.restore();
+    result
+}
+
+/// Vertical extent of tick labels rotated by `degrees`, using the context's current font
+pub fn rotated_label_height<T: DrawTarget + ?Sized>(ctx: &T, labels: &[String], font_size: f64, degrees: f64) -> f64 {
+    let theta = degrees.to_radians().abs();
+    labels
+        .iter()
+        .map(|label| {
+            let width = ctx.measure_text_width(label).unwrap_or(0.0);
+            width * theta.sin() + font_size * theta.cos()
+        })
+        .fold(0.0, f64::max)
+}
+
+/// Word-wrap text to `max_width` using the context's current font.
+///
+/// Output is capped at `max_lines`; overflow (or a single word too long to fit)
+/// is ellipsized.
+pub fn wrap_text<T: DrawTarget + ?Sized>(ctx: &T, text: &str, max_width: f64, max_lines: usize) -> Vec<String> {
+    let measure = |s: &str| ctx.measure_text_width(s).unwrap_or(0.0);
+    let max_lines = max_lines.max(1);
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() { word.to_string() } else { format!("{} {}", current, word) };
+        if measure(&candidate) <= max_width || current.is_empty() {
+            current = candidate;
+        } else {
+            lines.push(std::mem::replace(&mut current, word.to_string()));
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    if lines.len() > max_lines {
+        let overflow = lines.split_off(max_lines - 1).join(" ");
+        lines.push(overflow);
+    }
+
+    lines.into_iter().map(|line| truncate_to_width(ctx, &line, max_width)).collect()
+}
+
+/// Shorten text to fit `max_width` px in the current font, ending in an ellipsis
+/// when cut. Works on whole chars, so multibyte text is never split.
+pub fn truncate_to_width<T: DrawTarget + ?Sized>(ctx: &T, text: &str, max_width: f64) -> String {
+    let measure = |s: &str| ctx.measure_text_width(s).unwrap_or(0.0);
+    if measure(text) <= max_width {
+        return text.to_string();
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    let candidate = |n: usize| format!("{}…", chars[..n].iter().collect::<String>().trim_end());
+
+    // Longest prefix that fits, by binary search over the char count
+    let (mut lo, mut hi) = (0, chars.len());
+    while lo < hi {
+        let mid = (lo + hi).div_ceil(2);
+        if measure(&candidate(mid)) <= max_width {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    candidate(lo)
+}
+
 /// Format number with appropriate precision
 pub fn format_number(n: f64, precision: usize) -> String {
     if n.abs() >= 1000.0 {
@@ -192,18 +707,173 @@ pub fn format_number(n: f64, precision: usize) -> String {
     }
 }
 
-/// Interpolate between two colors
-pub fn interpolate_color(color1: &str, color2: &str, t: f64) -> String {
-    let parse_hex = |c: &str| -> (u8, u8, u8) {
-        let c = c.trim_start_matches('#');
-        let r = u8::from_str_radix(&c[0..2], 16).unwrap_or(0);
-        let g = u8::from_str_radix(&c[2..4], 16).unwrap_or(0);
-        let b = u8::from_str_radix(&c[4..6], 16).unwrap_or(0);
-        (r, g, b)
+/// Format a number using a spreadsheet-style spec.
+///
+/// `0`, `0.0` and `0.00` fix the number of decimals, a `,` in the integer part
+/// (e.g. `#,##0`) adds thousands separators and a trailing `%` is appended as-is.
+pub fn format_value(spec: &str, value: f64) -> String {
+    let spec = spec.trim();
+    let (spec, percent) = match spec.strip_suffix('%') {
+        Some(rest) => (rest, true),
+        None => (spec, false),
     };
 
-    let (r1, g1, b1) = parse_hex(color1);
-    let (r2, g2, b2) = parse_hex(color2);
+    let (int_part, frac_part) = spec.split_once('.').unwrap_or((spec, ""));
+    let decimals = frac_part.chars().filter(|c| *c == '0' || *c == '#').count();
+    let mut text = format!("{:.*}", decimals, value);
+
+    if int_part.contains(',') {
+        let (sign, digits) = match text.strip_prefix('-') {
+            Some(rest) => ("-", rest.to_string()),
+            None => ("", text.clone()),
+        };
+        let (whole, fraction) = match digits.split_once('.') {
+            Some((w, f)) => (w.to_string(), format!(".{}", f)),
+            None => (digits, String::new()),
+        };
+
+        let mut grouped = String::with_capacity(whole.len() + whole.len() / 3);
+        for (i, c) in whole.chars().enumerate() {
+            if i > 0 && (whole.len() - i) % 3 == 0 {
+                grouped.push(',');
+            }
+            grouped.push(c);
+        }
+        text = format!("{}{}{}", sign, grouped, fraction);
+    }
+
+    if percent {
+        text.push('%');
+    }
+    text
+}
+
+/// Format a timestamp (ms since epoch, local time) using a date pattern.
+///
+/// Supported tokens: `YYYY`, `MM`, `DD`, `HH`, `mm`, `ss`.
+pub fn format_date(pattern: &str, timestamp: f64) -> String {
+    let date = js_sys::Date::new(&JsValue::from_f64(timestamp));
+
+    pattern
+        .replace("YYYY", &date.get_full_year().to_string())
+        .replace("MM", &format!("{:02}", date.get_month() + 1))
+        .replace("DD", &format!("{:02}", date.get_date()))
+        .replace("HH", &format!("{:02}", date.get_hours()))
+        .replace("mm", &format!("{:02}", date.get_minutes()))
+        .replace("ss", &format!("{:02}", date.get_seconds()))
+}
+
+/// When bar charts draw a value label above each bar
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ValueLabelMode {
+    /// Only on bars at least this tall (px)
+    Auto(f64),
+    Always,
+    Never,
+}
+
+/// Bar height below which "auto" value labels are hidden by default
+pub const DEFAULT_VALUE_LABEL_MIN_HEIGHT: f64 = 20.0;
+
+impl ValueLabelMode {
+    /// Parse "auto", "always" or "never"; `min_height` only applies to "auto"
+    pub fn parse(mode: &str, min_height: Option<f64>) -> Result<Self, JsValue> {
+        match mode {
+            "auto" => Ok(ValueLabelMode::Auto(min_height.unwrap_or(DEFAULT_VALUE_LABEL_MIN_HEIGHT))),
+            "always" => Ok(ValueLabelMode::Always),
+            "never" => Ok(ValueLabelMode::Never),
+            _ => Err(JsValue::from_str(&format!("Unknown value label mode: {}", mode))),
+        }
+    }
+
+    /// Whether a bar of `height` px gets a label
+    pub fn shows(&self, height: f64) -> bool {
+        match self {
+            ValueLabelMode::Auto(min_height) => height > *min_height,
+            ValueLabelMode::Always => true,
+            ValueLabelMode::Never => false,
+        }
+    }
+}
+
+/// Per-kind display formats used when building tooltip and overlay text
+#[derive(Clone, Debug, Default)]
+pub struct ValueFormatters {
+    formats: HashMap<String, String>,
+}
+
+impl ValueFormatters {
+    /// Set the format spec for a value kind (e.g. `"score"`, `"count"`, `"timestamp"`)
+    pub fn set(&mut self, kind: &str, format: &str) {
+        if format.is_empty() {
+            self.formats.remove(kind);
+        } else {
+            self.formats.insert(kind.to_string(), format.to_string());
+        }
+    }
+
+    /// Format a number, falling back to `default_spec` when no format is set for `kind`
+    pub fn number(&self, kind: &str, value: f64, default_spec: &str) -> String {
+        let spec = self.formats.get(kind).map(String::as_str).unwrap_or(default_spec);
+        format_value(spec, value)
+    }
+
+    /// Format a timestamp, falling back to `default_pattern` when no format is set for `kind`
+    pub fn date(&self, kind: &str, timestamp: f64, default_pattern: &str) -> String {
+        let pattern = self.formats.get(kind).map(String::as_str).unwrap_or(default_pattern);
+        format_date(pattern, timestamp)
+    }
+}
+
+/// Parse a CSS color into RGB components.
+///
+/// Accepts `#rgb`, `#rrggbb` (with or without `#`, trailing alpha ignored) and
+/// `rgb()`/`rgba()` strings. Returns `None` for anything else, such as named colors.
+pub fn parse_color(color: &str) -> Option<(u8, u8, u8)> {
+    let c = color.trim();
+
+    if let Some(args) = c
+        .strip_prefix("rgba(")
+        .or_else(|| c.strip_prefix("rgb("))
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        let mut parts = args.split(',').map(|p| p.trim().parse::<f64>().ok());
+        let r = parts.next()??;
+        let g = parts.next()??;
+        let b = parts.next()??;
+        return Some((
+            r.clamp(0.0, 255.0) as u8,
+            g.clamp(0.0, 255.0) as u8,
+            b.clamp(0.0, 255.0) as u8,
+        ));
+    }
+
+    let hex = c.trim_start_matches('#');
+    if !hex.is_ascii() || !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    match hex.len() {
+        3 | 4 => {
+            let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|v| v * 17);
+            Some((digit(0)?, digit(1)?, digit(2)?))
+        }
+        6 | 8 => {
+            let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+            Some((pair(0)?, pair(2)?, pair(4)?))
+        }
+        _ => None,
+    }
+}
+
+/// Interpolate between two colors
+///
+/// Unparseable colors fall back to black rather than panicking, since themes
+/// are user-supplied.
+pub fn interpolate_color(color1: &str, color2: &str, t: f64) -> String {
+    let (r1, g1, b1) = parse_color(color1).unwrap_or((0, 0, 0));
+    let (r2, g2, b2) = parse_color(color2).unwrap_or((0, 0, 0));
+    let t = t.clamp(0.0, 1.0);
 
     let r = (r1 as f64 + (r2 as f64 - r1 as f64) * t) as u8;
     let g = (g1 as f64 + (g2 as f64 - g1 as f64) * t) as u8;
@@ -226,7 +896,8 @@ pub struct TooltipData {
 pub struct HitTestResult {
     pub hit: bool,
     pub element_id: Option<String>,
-    pub element_type: String,
+    // Borrowed for misses so building one doesn't allocate
+    pub element_type: Cow<'static, str>,
     pub data: Option<serde_json::Value>,
 }
 
@@ -235,17 +906,285 @@ impl HitTestResult {
         Self {
             hit: false,
             element_id: None,
-            element_type: "none".to_string(),
+            element_type: Cow::Borrowed("none"),
             data: None,
         }
     }
 
+    /// Shared JS object for a miss, built once per thread so pointer sweeps
+    /// over empty space don't allocate. Every caller gets the same object, so
+    /// it is frozen: a handler writing to one miss can't change the next.
+    pub fn miss_value() -> JsValue {
+        thread_local! {
+            static MISS: JsValue = {
+                let miss = serde_wasm_bindgen::to_value(&HitTestResult::miss()).unwrap();
+                js_sys::Object::freeze(miss.unchecked_ref());
+                miss
+            };
+        }
+        MISS.with(|miss| miss.clone())
+    }
+
     pub fn hit(id: &str, element_type: &str, data: serde_json::Value) -> Self {
         Self {
             hit: true,
             element_id: Some(id.to_string()),
-            element_type: element_type.to_string(),
+            element_type: Cow::Owned(element_type.to_string()),
             data: Some(data),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::charts::RecordingTarget;
+
+    #[test]
+    fn miss_has_the_shared_sentinel_shape() {
+        let miss = serde_json::to_value(HitTestResult::miss()).unwrap();
+        assert_eq!(miss, serde_json::json!({
+            "hit": false,
+            "element_id": null,
+            "element_type": "none",
+            "data": null
+        }));
+    }
+
+    #[test]
+    fn only_a_lost_context_flags_loss_after_a_failed_draw() {
+        let lost = Cell::new(false);
+        let target = RecordingTarget::new();
+
+        // An ordinary draw error leaves the flag clear
+        assert!(track_context_loss(&lost, &target, || Err(JsValue::NULL)).is_err());
+        assert!(!lost.get());
+
+        // A context that goes away mid-draw is flagged
+        let result = track_context_loss(&lost, &target, || {
+            target.lose_context();
+            Err(JsValue::NULL)
+        });
+        assert!(result.is_err());
+        assert!(lost.get());
+
+        // A later successful draw on a working context clears it
+        let restored = RecordingTarget::new();
+        assert!(track_context_loss(&lost, &restored, || Ok(())).is_ok());
+        assert!(!lost.get());
+    }
+
+    #[test]
+    fn parse_color_accepts_hex_shorthand_and_rgb() {
+        assert_eq!(parse_color("#fff"), Some((255, 255, 255)));
+        assert_eq!(parse_color("#ffffff"), Some((255, 255, 255)));
+        assert_eq!(parse_color("fff"), Some((255, 255, 255)));
+        assert_eq!(parse_color("#1a2B3c"), Some((0x1a, 0x2b, 0x3c)));
+        assert_eq!(parse_color("rgb(10, 20, 30)"), Some((10, 20, 30)));
+        assert_eq!(parse_color("rgba(10,20,30,0.5)"), Some((10, 20, 30)));
+    }
+
+    #[test]
+    fn parse_color_rejects_garbage_without_panicking() {
+        for input in ["", "#", "not a color", "#ggg", "#12345", "rgb(1,2)", "#é€", "red"] {
+            assert_eq!(parse_color(input), None, "{:?}", input);
+        }
+        assert_eq!(interpolate_color("garbage", "#ffffff", 0.0), "#000000");
+    }
+
+    #[test]
+    fn format_value_groups_thousands_and_fixes_decimals() {
+        assert_eq!(format_value("#,##0", 1234.0), "1,234");
+        assert_eq!(format_value("#,##0", -1234567.0), "-1,234,567");
+        assert_eq!(format_value("#,##0.00", 1234.5), "1,234.50");
+        assert_eq!(format_value("0.0", 7.25), "7.2");
+        assert_eq!(format_value("0", 99.6), "100");
+        assert_eq!(format_value("0.0%", 42.0), "42.0%");
+    }
+
+    #[test]
+    fn value_formatters_fall_back_to_the_default_spec() {
+        let mut formatters = ValueFormatters::default();
+        assert_eq!(formatters.number("count", 1234.0, "0"), "1234");
+        formatters.set("count", "#,##0");
+        assert_eq!(formatters.number("count", 1234.0, "0"), "1,234");
+        formatters.set("count", "");
+        assert_eq!(formatters.number("count", 1234.0, "0"), "1234");
+    }
+
+    #[test]
+    fn identical_payload_skips_reload_and_changed_payload_reloads() {
+        let loaded = Some(hash_json(&[r#"[{"id":"a","score":70}]"#, "10"]));
+        let same = Some(hash_json(&[r#"[{"id":"a","score":70}]"#, "10"]));
+        let changed = Some(hash_json(&[r#"[{"id":"a","score":71}]"#, "10"]));
+        let rebinned = Some(hash_json(&[r#"[{"id":"a","score":70}]"#, "20"]));
+
+        assert!(is_same_payload(same, loaded, false));
+        assert!(!is_same_payload(same, loaded, true));
+        assert!(!is_same_payload(changed, loaded, false));
+        assert!(!is_same_payload(rebinned, loaded, false));
+        // Unhashable payloads and cleared hashes always reload
+        assert!(!is_same_payload(None, None, false));
+        assert!(!is_same_payload(same, None, false));
+    }
+
+    #[test]
+    fn truncate_to_width_keeps_multibyte_labels_whole() {
+        let target = RecordingTarget::new();
+        let label = "Zoé-Château-Application";
+        for max_width in [5.0, 20.0, 40.0, 60.0, 90.0] {
+            let text = truncate_to_width(&target, label, max_width);
+            let kept = text.trim_end_matches('…');
+            assert!(label.starts_with(kept), "{:?} is not a prefix", kept);
+            assert!(target.measure_text_width(&text).unwrap() <= max_width || kept.is_empty());
+        }
+    }
+
+    #[test]
+    fn typography_defaults_follow_font_size() {
+        let config = ChartConfig::default();
+        assert_eq!(config.title_size(), 14.0);
+        assert_eq!(config.title_weight(), "bold");
+        assert_eq!(config.axis_size(), 10.0);
+        assert_eq!(config.label_size(), 12.0);
+        assert_eq!(config.legend_size(), 11.0);
+        assert_eq!(config.value_size(), 30.0);
+        assert_eq!(config.title_font(), "bold 14px Inter, system-ui, sans-serif");
+    }
+
+    #[test]
+    fn typography_round_trips_through_json() {
+        let typography = Typography {
+            title_size: Some(20.0),
+            title_weight: Some("600".to_string()),
+            axis_size: Some(9.0),
+            label_size: None,
+            legend_size: Some(13.0),
+            value_size: None,
+        };
+        let json = serde_json::to_string(&typography).unwrap();
+        let back: Typography = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&back).unwrap(), json);
+
+        // Omitted roles stay unset and fall back to `font_size`
+        let partial: Typography = serde_json::from_str(r#"{"axis_size": 9}"#).unwrap();
+        assert_eq!(partial.axis_size, Some(9.0));
+        assert!(partial.legend_size.is_none());
+
+        // Configs from before typography existed still load
+        let mut legacy = serde_json::to_value(ChartConfig::default()).unwrap();
+        legacy.as_object_mut().unwrap().remove("typography");
+        let config: ChartConfig = serde_json::from_value(legacy).unwrap();
+        assert_eq!(config.title_size(), 14.0);
+        assert_eq!(config.legend_size(), 11.0);
+    }
+
+    #[test]
+    fn transparent_background_clears_instead_of_filling() {
+        for background in ["transparent", "none", " "] {
+            let target = RecordingTarget::new();
+            clear_canvas(&target, 800.0, 400.0, background);
+            assert_eq!(target.count("clear_rect"), 1, "{:?}", background);
+            assert_eq!(target.count("fill_rect"), 0, "{:?}", background);
+        }
+
+        let target = RecordingTarget::new();
+        clear_canvas(&target, 800.0, 400.0, "#FFFFFF");
+        assert_eq!(target.count("clear_rect"), 0);
+        assert_eq!(target.count("fill_rect"), 1);
+    }
+
+    #[test]
+    fn canvas_color_maps_transparent_spellings_to_a_css_color() {
+        assert_eq!(canvas_color("none"), "transparent");
+        assert_eq!(canvas_color(""), "transparent");
+        assert_eq!(canvas_color("#111827"), "#111827");
+    }
+
+    #[test]
+    fn readable_on_keeps_contrasting_text_and_flips_by_luminance() {
+        let theme = ColorTheme::default();
+        assert_eq!(readable_on(&theme.secondary, &theme.background), theme.secondary);
+        assert_eq!(readable_on(&theme.text, "#000000"), "#FFFFFF");
+        assert_eq!(readable_on("#FFFFFF", "#F3F4F6"), "#111827");
+        // Nothing is known about what shows through a transparent canvas
+        assert_eq!(readable_on(&theme.text, "transparent"), theme.text);
+    }
+
+    #[test]
+    fn legend_swatches_draw_their_shape_inside_save_and_restore() {
+        let target = RecordingTarget::new();
+        draw_legend_swatch(&target, SwatchShape::Square, "#3B82F6", 10.0, 20.0, 12.0).unwrap();
+        let ops: Vec<&str> = target.calls().iter().map(|c| c.op).collect();
+        assert_eq!(ops, vec!["save", "set_fill_style", "fill_rect", "restore"]);
+        assert_eq!(target.calls()[2].args, vec![10.0, 20.0, 12.0, 12.0]);
+
+        let target = RecordingTarget::new();
+        draw_legend_swatch(&target, SwatchShape::Circle, "#3B82F6", 10.0, 20.0, 12.0).unwrap();
+        assert_eq!(target.calls()[3].args[..3], [16.0, 26.0, 6.0]);
+        assert_eq!(target.count("fill"), 1);
+
+        let target = RecordingTarget::new();
+        draw_legend_swatch(&target, SwatchShape::Triangle, "#F59E0B", 10.0, 20.0, 12.0).unwrap();
+        let ops: Vec<&str> = target.calls().iter().map(|c| c.op).collect();
+        assert_eq!(ops, vec!["save", "set_fill_style", "begin_path", "move_to", "line_to", "line_to", "close_path", "fill", "restore"]);
+        assert_eq!(target.calls()[3].args, vec![16.0, 20.0]);
+
+        // The line swatch's width would otherwise leak into the caller's next stroke
+        let target = RecordingTarget::new();
+        draw_legend_swatch(&target, SwatchShape::Line, "#10B981", 10.0, 20.0, 16.0).unwrap();
+        let ops: Vec<&str> = target.calls().iter().map(|c| c.op).collect();
+        assert_eq!(ops.first(), Some(&"save"));
+        assert_eq!(ops.last(), Some(&"restore"));
+        assert_eq!(target.count("set_line_width"), 1);
+        assert_eq!(target.count("stroke"), 1);
+    }
+
+    #[test]
+    fn zero_size_config_renders_without_panic() {
+        use crate::charts::{
+            AssignmentHeatmapChart, NetworkGraphChart, ProgressTrackerChart, ScoreDistributionChart, TimelineChart,
+            VarianceHeatmapChart,
+        };
+
+        for (width, height) in [(0.0, 0.0), (800.0, 0.0), (0.0, 400.0)] {
+            let config = ChartConfig { width, height, ..ChartConfig::default() };
+            assert!(!config.has_drawable_size());
+
+            // Returns before looking up the canvas, so no DOM is needed here
+            assert!(ScoreDistributionChart::with_config("test", config.clone()).render().is_ok());
+            assert!(ProgressTrackerChart::with_config("test", config.clone()).render().is_ok());
+            assert!(VarianceHeatmapChart::with_config("test", config.clone()).render().is_ok());
+            assert!(TimelineChart::with_config("test", config.clone()).render().is_ok());
+            assert!(NetworkGraphChart::with_config("test", config.clone()).render().is_ok());
+            assert!(AssignmentHeatmapChart::with_config("test", config).render().is_ok());
+        }
+        assert!(ChartConfig::default().has_drawable_size());
+    }
+
+    #[test]
+    fn render_timing_tracks_last_and_a_rolling_average() {
+        let timing = RenderTiming::default();
+        assert_eq!((timing.last(), timing.average()), (0.0, 0.0));
+
+        timing.record(4.0);
+        timing.record(8.0);
+        assert_eq!((timing.last(), timing.average()), (8.0, 6.0));
+
+        // Older samples fall out of the window
+        for _ in 0..RENDER_TIMING_WINDOW {
+            timing.record(2.0);
+        }
+        assert_eq!((timing.last(), timing.average()), (2.0, 2.0));
+    }
+
+    #[test]
+    fn composite_size_fits_every_cell() {
+        // Two charts side by side, a third below spanning part of the width
+        let placements = [(0.0, 0.0, 400.0, 300.0), (400.0, 0.0, 400.0, 250.0), (100.0, 300.0, 500.5, 120.2)];
+        assert_eq!(composite_size(&placements), Some((800, 421)));
+
+        assert_eq!(composite_size(&[]), None);
+        assert_eq!(composite_size(&[(0.0, 0.0, 300.0, 0.0)]), None);
+    }
+}
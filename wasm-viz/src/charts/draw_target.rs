@@ -0,0 +1,398 @@
+//! Drawing surface abstraction
+//!
+//! Chart draw code written against `DrawTarget` runs on a real canvas in the
+//! browser and on `RecordingTarget` natively, so draw order and call counts can
+//! be checked without a DOM.
+
+use wasm_bindgen::prelude::*;
+use web_sys::CanvasRenderingContext2d;
+use std::cell::{Cell, RefCell};
+
+/// The subset of the 2D canvas API used by chart draw code
+pub trait DrawTarget {
+    fn save(&self);
+    fn restore(&self);
+    fn translate(&self, x: f64, y: f64) -> Result<(), JsValue>;
+    fn rotate(&self, angle: f64) -> Result<(), JsValue>;
+    fn scale(&self, x: f64, y: f64) -> Result<(), JsValue>;
+
+    fn set_fill_style(&self, color: &str);
+    fn set_stroke_style(&self, color: &str);
+    fn set_line_width(&self, width: f64);
+    fn set_line_dash(&self, segments: &[f64]) -> Result<(), JsValue>;
+    fn set_global_alpha(&self, alpha: f64);
+    fn set_global_composite_operation(&self, operation: &str) -> Result<(), JsValue>;
+    fn set_line_cap(&self, cap: &str);
+
+    fn set_font(&self, font: &str);
+    fn set_text_align(&self, align: &str);
+    fn set_text_baseline(&self, baseline: &str);
+    fn fill_text(&self, text: &str, x: f64, y: f64) -> Result<(), JsValue>;
+    fn measure_text_width(&self, text: &str) -> Result<f64, JsValue>;
+
+    fn fill_rect(&self, x: f64, y: f64, width: f64, height: f64);
+    fn stroke_rect(&self, x: f64, y: f64, width: f64, height: f64);
+    fn clear_rect(&self, x: f64, y: f64, width: f64, height: f64);
+    fn rect(&self, x: f64, y: f64, width: f64, height: f64);
+    fn clip(&self);
+
+    fn begin_path(&self);
+    fn close_path(&self);
+    fn move_to(&self, x: f64, y: f64);
+    fn line_to(&self, x: f64, y: f64);
+    fn quadratic_curve_to(&self, cpx: f64, cpy: f64, x: f64, y: f64);
+    fn arc(&self, x: f64, y: f64, radius: f64, start: f64, end: f64) -> Result<(), JsValue>;
+    fn arc_with_anticlockwise(&self, x: f64, y: f64, radius: f64, start: f64, end: f64, anticlockwise: bool) -> Result<(), JsValue>;
+    fn fill(&self);
+    fn stroke(&self);
+
+    /// Whether the surface has lost its backing context; plain targets never do
+    fn is_context_lost(&self) -> bool {
+        false
+    }
+}
+
+impl DrawTarget for CanvasRenderingContext2d {
+    fn save(&self) {
+        CanvasRenderingContext2d::save(self)
+    }
+
+    fn restore(&self) {
+        CanvasRenderingContext2d::restore(self)
+    }
+
+    fn translate(&self, x: f64, y: f64) -> Result<(), JsValue> {
+        CanvasRenderingContext2d::translate(self, x, y)
+    }
+
+    fn rotate(&self, angle: f64) -> Result<(), JsValue> {
+        CanvasRenderingContext2d::rotate(self, angle)
+    }
+
+    fn scale(&self, x: f64, y: f64) -> Result<(), JsValue> {
+        CanvasRenderingContext2d::scale(self, x, y)
+    }
+
+    fn set_fill_style(&self, color: &str) {
+        CanvasRenderingContext2d::set_fill_style_str(self, color)
+    }
+
+    fn set_stroke_style(&self, color: &str) {
+        CanvasRenderingContext2d::set_stroke_style_str(self, color)
+    }
+
+    fn set_line_width(&self, width: f64) {
+        CanvasRenderingContext2d::set_line_width(self, width)
+    }
+
+    fn set_line_dash(&self, segments: &[f64]) -> Result<(), JsValue> {
+        let array: js_sys::Array = segments.iter().map(|&s| JsValue::from(s)).collect();
+        CanvasRenderingContext2d::set_line_dash(self, &array)
+    }
+
+    fn set_global_alpha(&self, alpha: f64) {
+        CanvasRenderingContext2d::set_global_alpha(self, alpha)
+    }
+
+    fn set_global_composite_operation(&self, operation: &str) -> Result<(), JsValue> {
+        CanvasRenderingContext2d::set_global_composite_operation(self, operation)
+    }
+
+    fn set_line_cap(&self, cap: &str) {
+        CanvasRenderingContext2d::set_line_cap(self, cap)
+    }
+
+    fn set_font(&self, font: &str) {
+        CanvasRenderingContext2d::set_font(self, font)
+    }
+
+    fn set_text_align(&self, align: &str) {
+        CanvasRenderingContext2d::set_text_align(self, align)
+    }
+
+    fn set_text_baseline(&self, baseline: &str) {
+        CanvasRenderingContext2d::set_text_baseline(self, baseline)
+    }
+
+    fn fill_text(&self, text: &str, x: f64, y: f64) -> Result<(), JsValue> {
+        CanvasRenderingContext2d::fill_text(self, text, x, y)
+    }
+
+    fn measure_text_width(&self, text: &str) -> Result<f64, JsValue> {
+        CanvasRenderingContext2d::measure_text(self, text).map(|m| m.width())
+    }
+
+    fn fill_rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        CanvasRenderingContext2d::fill_rect(self, x, y, width, height)
+    }
+
+    fn stroke_rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        CanvasRenderingContext2d::stroke_rect(self, x, y, width, height)
+    }
+
+    fn clear_rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        CanvasRenderingContext2d::clear_rect(self, x, y, width, height)
+    }
+
+    fn rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        CanvasRenderingContext2d::rect(self, x, y, width, height)
+    }
+
+    fn clip(&self) {
+        CanvasRenderingContext2d::clip(self)
+    }
+
+    fn begin_path(&self) {
+        CanvasRenderingContext2d::begin_path(self)
+    }
+
+    fn close_path(&self) {
+        CanvasRenderingContext2d::close_path(self)
+    }
+
+    fn move_to(&self, x: f64, y: f64) {
+        CanvasRenderingContext2d::move_to(self, x, y)
+    }
+
+    fn line_to(&self, x: f64, y: f64) {
+        CanvasRenderingContext2d::line_to(self, x, y)
+    }
+
+    fn quadratic_curve_to(&self, cpx: f64, cpy: f64, x: f64, y: f64) {
+        CanvasRenderingContext2d::quadratic_curve_to(self, cpx, cpy, x, y)
+    }
+
+    fn arc(&self, x: f64, y: f64, radius: f64, start: f64, end: f64) -> Result<(), JsValue> {
+        CanvasRenderingContext2d::arc(self, x, y, radius, start, end)
+    }
+
+    fn arc_with_anticlockwise(&self, x: f64, y: f64, radius: f64, start: f64, end: f64, anticlockwise: bool) -> Result<(), JsValue> {
+        CanvasRenderingContext2d::arc_with_anticlockwise(self, x, y, radius, start, end, anticlockwise)
+    }
+
+    fn fill(&self) {
+        CanvasRenderingContext2d::fill(self)
+    }
+
+    fn stroke(&self) {
+        CanvasRenderingContext2d::stroke(self)
+    }
+
+    fn is_context_lost(&self) -> bool {
+        super::common::is_context_lost(self)
+    }
+}
+
+/// One recorded draw call: operation name, numeric arguments and any text/style argument
+#[derive(Clone, Debug, PartialEq)]
+pub struct DrawCall {
+    pub op: &'static str,
+    pub args: Vec<f64>,
+    pub text: Option<String>,
+}
+
+/// Headless target that records every call instead of drawing.
+///
+/// Text is measured as 0.6 × the font's px size per character, enough for
+/// layout code that only compares widths.
+#[derive(Debug)]
+pub struct RecordingTarget {
+    calls: RefCell<Vec<DrawCall>>,
+    font_px: Cell<f64>,
+    lost: Cell<bool>,
+}
+
+impl Default for RecordingTarget {
+    fn default() -> Self {
+        Self {
+            calls: RefCell::new(Vec::new()),
+            font_px: Cell::new(10.0),
+            lost: Cell::new(false),
+        }
+    }
+}
+
+impl RecordingTarget {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All calls so far, in order
+    pub fn calls(&self) -> Vec<DrawCall> {
+        self.calls.borrow().clone()
+    }
+
+    /// Number of calls to `op`
+    pub fn count(&self, op: &str) -> usize {
+        self.calls.borrow().iter().filter(|c| c.op == op).count()
+    }
+
+    /// Text passed to every `fill_text`, in order
+    pub fn texts(&self) -> Vec<String> {
+        self.calls.borrow().iter()
+            .filter(|c| c.op == "fill_text")
+            .filter_map(|c| c.text.clone())
+            .collect()
+    }
+
+    pub fn clear(&self) {
+        self.calls.borrow_mut().clear();
+    }
+
+    /// Simulate losing the backing context, as on a GPU reset
+    pub fn lose_context(&self) {
+        self.lost.set(true);
+    }
+
+    fn record(&self, op: &'static str, args: &[f64], text: Option<&str>) {
+        self.calls.borrow_mut().push(DrawCall {
+            op,
+            args: args.to_vec(),
+            text: text.map(str::to_string),
+        });
+    }
+}
+
+impl DrawTarget for RecordingTarget {
+    fn save(&self) {
+        self.record("save", &[], None);
+    }
+
+    fn restore(&self) {
+        self.record("restore", &[], None);
+    }
+
+    fn translate(&self, x: f64, y: f64) -> Result<(), JsValue> {
+        self.record("translate", &[x, y], None);
+        Ok(())
+    }
+
+    fn rotate(&self, angle: f64) -> Result<(), JsValue> {
+        self.record("rotate", &[angle], None);
+        Ok(())
+    }
+
+    fn scale(&self, x: f64, y: f64) -> Result<(), JsValue> {
+        self.record("scale", &[x, y], None);
+        Ok(())
+    }
+
+    fn set_fill_style(&self, color: &str) {
+        self.record("set_fill_style", &[], Some(color));
+    }
+
+    fn set_stroke_style(&self, color: &str) {
+        self.record("set_stroke_style", &[], Some(color));
+    }
+
+    fn set_line_width(&self, width: f64) {
+        self.record("set_line_width", &[width], None);
+    }
+
+    fn set_line_dash(&self, segments: &[f64]) -> Result<(), JsValue> {
+        self.record("set_line_dash", segments, None);
+        Ok(())
+    }
+
+    fn set_global_alpha(&self, alpha: f64) {
+        self.record("set_global_alpha", &[alpha], None);
+    }
+
+    fn set_global_composite_operation(&self, operation: &str) -> Result<(), JsValue> {
+        self.record("set_global_composite_operation", &[], Some(operation));
+        Ok(())
+    }
+
+    fn set_line_cap(&self, cap: &str) {
+        self.record("set_line_cap", &[], Some(cap));
+    }
+
+    fn set_font(&self, font: &str) {
+        // "bold 12px Inter" -> 12
+        if let Some(px) = font.split_whitespace()
+            .find_map(|part| part.strip_suffix("px").and_then(|n| n.parse::<f64>().ok()))
+        {
+            self.font_px.set(px);
+        }
+        self.record("set_font", &[], Some(font));
+    }
+
+    fn set_text_align(&self, align: &str) {
+        self.record("set_text_align", &[], Some(align));
+    }
+
+    fn set_text_baseline(&self, baseline: &str) {
+        self.record("set_text_baseline", &[], Some(baseline));
+    }
+
+    fn fill_text(&self, text: &str, x: f64, y: f64) -> Result<(), JsValue> {
+        self.record("fill_text", &[x, y], Some(text));
+        Ok(())
+    }
+
+    fn measure_text_width(&self, text: &str) -> Result<f64, JsValue> {
+        Ok(text.chars().count() as f64 * self.font_px.get() * 0.6)
+    }
+
+    fn fill_rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        self.record("fill_rect", &[x, y, width, height], None);
+    }
+
+    fn stroke_rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        self.record("stroke_rect", &[x, y, width, height], None);
+    }
+
+    fn clear_rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        self.record("clear_rect", &[x, y, width, height], None);
+    }
+
+    fn rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        self.record("rect", &[x, y, width, height], None);
+    }
+
+    fn clip(&self) {
+        self.record("clip", &[], None);
+    }
+
+    fn begin_path(&self) {
+        self.record("begin_path", &[], None);
+    }
+
+    fn close_path(&self) {
+        self.record("close_path", &[], None);
+    }
+
+    fn move_to(&self, x: f64, y: f64) {
+        self.record("move_to", &[x, y], None);
+    }
+
+    fn line_to(&self, x: f64, y: f64) {
+        self.record("line_to", &[x, y], None);
+    }
+
+    fn quadratic_curve_to(&self, cpx: f64, cpy: f64, x: f64, y: f64) {
+        self.record("quadratic_curve_to", &[cpx, cpy, x, y], None);
+    }
+
+    fn arc(&self, x: f64, y: f64, radius: f64, start: f64, end: f64) -> Result<(), JsValue> {
+        self.record("arc", &[x, y, radius, start, end], None);
+        Ok(())
+    }
+
+    fn arc_with_anticlockwise(&self, x: f64, y: f64, radius: f64, start: f64, end: f64, anticlockwise: bool) -> Result<(), JsValue> {
+        self.record("arc", &[x, y, radius, start, end, if anticlockwise { 1.0 } else { 0.0 }], None);
+        Ok(())
+    }
+
+    fn fill(&self) {
+        self.record("fill", &[], None);
+    }
+
+    fn stroke(&self) {
+        self.record("stroke", &[], None);
+    }
+
+    fn is_context_lost(&self) -> bool {
+        self.lost.get()
+    }
+}
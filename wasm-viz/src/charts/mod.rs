@@ -7,11 +7,15 @@ mod progress_tracker;
 mod variance_heatmap;
 mod timeline;
 mod network_graph;
+mod assignment_heatmap;
 mod common;
+mod draw_target;
 
 pub use score_distribution::*;
 pub use progress_tracker::*;
 pub use variance_heatmap::*;
 pub use timeline::*;
 pub use network_graph::*;
+pub use assignment_heatmap::*;
 pub use common::*;
+pub use draw_target::*;
@@ -5,10 +5,46 @@
 
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
-use web_sys::CanvasRenderingContext2d;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
 use std::f64::consts::PI;
 
-use super::common::{get_canvas_context, clear_canvas, ChartConfig, HitTestResult};
+use super::common::{get_canvas_context, track_context_loss, surface_context_loss, clear_canvas, canvas_color, readable_on, now_ms, interpolate_color, wrap_text, payload_hash, is_same_payload, render_image_data, draw_legend_swatch, truncate_to_width, RenderTiming, SwatchShape, ChartConfig, ColorTheme, HitTestResult};
+use super::draw_target::DrawTarget;
+
+/// Adaptive quality levels, each dropping one more detail than the previous
+const QUALITY_NO_LABELS: u8 = 1;
+const QUALITY_NO_ARROWS: u8 = 2;
+const QUALITY_STRAIGHT_EDGES: u8 = 3;
+const QUALITY_THIN_EDGES: u8 = 4;
+
+/// Screen-space distance (px) within which the cursor hits an edge
+const EDGE_HIT_TOLERANCE: f64 = 4.0;
+
+/// Screen-space width (px) beyond which node labels are cut with an ellipsis
+const NODE_LABEL_MAX_WIDTH: f64 = 90.0;
+
+/// Inset (px) from the canvas edge for grid and random initial layouts
+const INITIAL_LAYOUT_MARGIN: f64 = 40.0;
+
+/// Pan speed (px/frame) below which inertia stops
+const INERTIA_MIN_SPEED: f64 = 0.1;
+
+/// Screen-space extent (px) of the graph that pan bounds keep visible
+const PAN_MIN_VISIBLE: f64 = 60.0;
+
+/// Zoom level above which edge labels are drawn
+const EDGE_LABEL_MIN_ZOOM: f64 = 1.2;
+
+/// Label propagation passes before giving up on convergence
+const COMMUNITY_MAX_ITERATIONS: usize = 20;
+
+/// Zoom change per unit of wheel delta at sensitivity 1.0
+const ZOOM_STEP: f64 = 0.001;
+
+/// Maximum legend label width before wrapping
+const LEGEND_LABEL_WIDTH: f64 = 120.0;
 
 /// Node types in the network
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -19,6 +55,16 @@ pub enum NodeType {
     Application,
 }
 
+impl NodeType {
+    /// Shape this node type is drawn with, reused by the legend
+    fn shape(&self) -> SwatchShape {
+        match self {
+            NodeType::Assessor => SwatchShape::Square,
+            NodeType::Application => SwatchShape::Circle,
+        }
+    }
+}
+
 /// Network node
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NetworkNode {
@@ -38,6 +84,22 @@ pub struct NetworkEdge {
     pub weight: Option<f64>,
     pub color: Option<String>,
     pub status: Option<String>, // "pending", "in_progress", "completed"
+    pub label: Option<String>,
+}
+
+/// Conflict of interest between an assessor and an application
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AssignmentConflict {
+    pub assessor_id: String,
+    pub application_id: String,
+}
+
+/// One active touch in canvas coordinates
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TouchPoint {
+    pub id: f64,
+    pub x: f64,
+    pub y: f64,
 }
 
 /// Internal node with physics state
@@ -54,6 +116,71 @@ struct PhysicsNode {
     color: String,
     fixed: bool,
     metadata: Option<serde_json::Value>,
+    // Size and color before any metadata encoding is applied;
+    // no color means the theme color for the node type
+    base_size: f64,
+    base_color: Option<String>,
+}
+
+/// Metadata-driven size and color mapping for one node type
+#[derive(Clone, Debug)]
+struct NodeEncoding {
+    size_key: Option<String>,
+    color_key: Option<String>,
+    size_range: (f64, f64),
+    color_range: (String, String),
+}
+
+/// Per-node-type physics overrides
+#[derive(Clone, Copy, Debug)]
+struct TypePhysics {
+    repulsion_multiplier: f64,
+    mass: f64,
+}
+
+impl Default for TypePhysics {
+    fn default() -> Self {
+        Self {
+            repulsion_multiplier: 1.0,
+            mass: 1.0,
+        }
+    }
+}
+
+/// How repulsion falls off with distance.
+///
+/// - `InverseSquare`: strong locally, but distant nodes barely interact, so
+///   disconnected components can drift apart.
+/// - `Inverse`: longer reach keeps components together at the cost of a
+///   looser, more spread-out layout.
+/// - `Capped`: inverse-square up to `repulsion_range`, zero beyond it; far
+///   pairs are skipped entirely, which is cheapest on large graphs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum RepulsionModel {
+    InverseSquare,
+    Inverse,
+    Capped,
+}
+
+/// Where `set_data` places nodes before the simulation starts.
+///
+/// - `Bipartite`: assessors on an inner ring, applications on an outer ring.
+/// - `Grid`: a near-square lattice filling the canvas, in data order.
+/// - `Random`: scattered uniformly over the canvas.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum InitialLayout {
+    Bipartite,
+    Grid,
+    Random,
+}
+
+/// In-flight color transition for an edge whose status changed
+#[derive(Clone, Debug)]
+struct EdgeTransition {
+    edge_index: usize,
+    from_color: String,
+    to_color: String,
+    elapsed_ms: f64,
 }
 
 /// Network graph with force-directed layout
@@ -63,20 +190,93 @@ pub struct NetworkGraphChart {
     config: ChartConfig,
     nodes: Vec<PhysicsNode>,
     edges: Vec<NetworkEdge>,
+    // Edges dropped on load because an endpoint id matched no node
+    dropped_edges: usize,
     // View state
     zoom: f64,
+    // Multiplier on the per-unit wheel zoom step
+    zoom_sensitivity: f64,
+    invert_zoom: bool,
     pan_x: f64,
     pan_y: f64,
+    // Keep part of the node extent on screen when panning and zooming
+    pan_bounds: bool,
+    // Touches from the last touch event, the baseline for the next move
+    touches: Vec<TouchPoint>,
+    // Pan glide after release: last pan delta, decayed by `inertia_friction` per frame
+    inertia: bool,
+    inertia_friction: f64,
+    pan_velocity: (f64, f64),
     // Interaction state
     dragging_node: Option<usize>,
     hovered_node: Option<usize>,
     selected_nodes: Vec<usize>,
+    // Remap selection and hover by id on reload instead of clearing them
+    preserve_selection: bool,
+    max_selection: Option<usize>,
+    // Highlighted shortest path (node and edge indices)
+    path_nodes: Vec<usize>,
+    path_edges: Vec<usize>,
+    hover_grow: f64,
+    hover_effect: bool,
+    // Draw edges as quadratic curves; straight lines when off
+    curved_edges: bool,
+    // Animation state
+    edge_transitions: Vec<EdgeTransition>,
+    transition_ms: f64,
+    reduced_motion: bool,
+    deterministic: bool,
     // Physics settings
     simulation_running: bool,
+    auto_stop: bool,
+    // Stop the simulation when fit_to_content runs so gravity can't undo the fit
+    freeze_on_fit: bool,
+    // Set while the simulation is stopped by a fit; only then does a drag resume it
+    frozen_by_fit: bool,
     repulsion_strength: f64,
+    repulsion_model: RepulsionModel,
+    initial_layout: InitialLayout,
+    // Distance beyond which the capped model applies no repulsion
+    repulsion_range: f64,
     attraction_strength: f64,
     damping: f64,
     center_gravity: f64,
+    // Hard minimum separation between node shapes
+    collision_enabled: bool,
+    collision_padding: f64,
+    // Gravity target; None falls back to the canvas center
+    gravity_center: (Option<f64>, Option<f64>),
+    // Set when a step produced a non-finite node and it was reset; cleared by set_data/reset_view
+    diverged: bool,
+    assessor_physics: TypePhysics,
+    application_physics: TypePhysics,
+    // Adaptive render quality (0 = full detail)
+    quality_level: u8,
+    last_frame_ms: f64,
+    status_colors: HashMap<String, String>,
+    show_progress_ring: bool,
+    // Outline around every node; color None falls back to the theme background
+    node_stroke: bool,
+    node_stroke_color: Option<String>,
+    node_stroke_width: f64,
+    // Draw hovered/selected nodes and their edges last
+    raise_active: bool,
+    show_edge_labels: bool,
+    // Color nodes by detected community instead of type/encoding
+    community_coloring: bool,
+    // Drawn as an overlay; not part of the physics
+    conflicts: Vec<AssignmentConflict>,
+    // Hide node labels that would overlap a higher-priority label
+    label_collision: bool,
+    assessor_encoding: Option<NodeEncoding>,
+    application_encoding: Option<NodeEncoding>,
+    // Scale base node size by edge count over `degree_size_range` (px)
+    size_by_degree: bool,
+    degree_size_range: (f64, f64),
+    // Fingerprint of the last loaded payload
+    data_hash: Option<u64>,
+    render_timing: RenderTiming,
+    context_lost: Cell<bool>,
 }
 
 #[wasm_bindgen]
@@ -86,107 +286,837 @@ impl NetworkGraphChart {
     pub fn new(canvas_id: &str, config_js: JsValue) -> Result<NetworkGraphChart, JsValue> {
         let config: ChartConfig = serde_wasm_bindgen::from_value(config_js)
             .unwrap_or_else(|_| ChartConfig::default());
+        Ok(Self::with_config(canvas_id, config))
+    }
 
-        Ok(Self {
+    /// Create a chart from an already-parsed config
+    pub(crate) fn with_config(canvas_id: &str, config: ChartConfig) -> Self {
+        Self {
             canvas_id: canvas_id.to_string(),
             config,
             nodes: Vec::new(),
             edges: Vec::new(),
+            dropped_edges: 0,
             zoom: 1.0,
+            zoom_sensitivity: 1.0,
+            invert_zoom: false,
             pan_x: 0.0,
             pan_y: 0.0,
+            pan_bounds: true,
+            touches: Vec::new(),
+            inertia: true,
+            inertia_friction: 0.92,
+            pan_velocity: (0.0, 0.0),
             dragging_node: None,
             hovered_node: None,
             selected_nodes: Vec::new(),
+            preserve_selection: true,
+            max_selection: None,
+            path_nodes: Vec::new(),
+            path_edges: Vec::new(),
+            hover_grow: 1.2,
+            hover_effect: true,
+            curved_edges: true,
+            edge_transitions: Vec::new(),
+            transition_ms: 600.0,
+            reduced_motion: false,
+            deterministic: false,
             simulation_running: true,
+            freeze_on_fit: false,
+            frozen_by_fit: false,
+            auto_stop: true,
             repulsion_strength: 500.0,
+            repulsion_model: RepulsionModel::InverseSquare,
+            initial_layout: InitialLayout::Bipartite,
+            repulsion_range: 300.0,
             attraction_strength: 0.05,
             damping: 0.9,
             center_gravity: 0.02,
-        })
+            collision_enabled: false,
+            collision_padding: 0.0,
+            gravity_center: (None, None),
+            diverged: false,
+            assessor_physics: TypePhysics::default(),
+            application_physics: TypePhysics::default(),
+            quality_level: 0,
+            last_frame_ms: 0.0,
+            status_colors: HashMap::new(),
+            show_progress_ring: false,
+            node_stroke: false,
+            node_stroke_color: None,
+            node_stroke_width: 1.5,
+            raise_active: true,
+            show_edge_labels: false,
+            community_coloring: false,
+            conflicts: Vec::new(),
+            label_collision: false,
+            assessor_encoding: None,
+            application_encoding: None,
+            size_by_degree: false,
+            degree_size_range: (8.0, 32.0),
+            data_hash: None,
+            render_timing: RenderTiming::default(),
+            context_lost: Cell::new(false),
+        }
     }
 
     /// Set graph data
     pub fn set_data(&mut self, nodes_js: JsValue, edges_js: JsValue) -> Result<(), JsValue> {
+        self.set_data_with_options(nodes_js, edges_js, false)
+    }
+
+    /// Set graph data; identical payloads are skipped unless `force` is set
+    pub fn set_data_with_options(&mut self, nodes_js: JsValue, edges_js: JsValue, force: bool) -> Result<(), JsValue> {
+        let hash = payload_hash(&[&nodes_js, &edges_js]);
+        if is_same_payload(hash, self.data_hash, force) {
+            return Ok(());
+        }
+
+        self.load_data(nodes_js, edges_js)?;
+        self.data_hash = hash;
+        Ok(())
+    }
+
+    fn load_data(&mut self, nodes_js: JsValue, edges_js: JsValue) -> Result<(), JsValue> {
         let nodes: Vec<NetworkNode> = serde_wasm_bindgen::from_value(nodes_js)?;
         let edges: Vec<NetworkEdge> = serde_wasm_bindgen::from_value(edges_js)?;
+        self.load_graph(nodes, edges);
+        Ok(())
+    }
 
-        // Initialize physics nodes with random positions in a circle
-        let center_x = self.config.width / 2.0;
-        let center_y = self.config.height / 2.0;
-        let radius = (self.config.width.min(self.config.height) / 3.0).max(100.0);
+    /// Replace the graph with parsed nodes and edges and restart the layout
+    fn load_graph(&mut self, nodes: Vec<NetworkNode>, edges: Vec<NetworkEdge>) {
+        let selected_ids: Vec<String> = self.selected_nodes.iter()
+            .filter_map(|&i| self.nodes.get(i))
+            .map(|n| n.id.clone())
+            .collect();
+        let hovered_id = self.hovered_node.and_then(|i| self.nodes.get(i)).map(|n| n.id.clone());
 
         self.nodes = nodes.iter().enumerate().map(|(i, node)| {
-            let angle = (i as f64 / nodes.len() as f64) * 2.0 * PI;
+            let (x, y) = self.initial_position(i, nodes.len(), &node.node_type);
 
-            // Assessors in inner ring, applications in outer ring
-            let r = match node.node_type {
-                NodeType::Assessor => radius * 0.4,
-                NodeType::Application => radius * 0.9,
-            };
+            let size = node.size.unwrap_or(match node.node_type {
+                NodeType::Assessor => 20.0,
+                NodeType::Application => 12.0,
+            });
 
             PhysicsNode {
                 id: node.id.clone(),
                 label: node.label.clone(),
                 node_type: node.node_type.clone(),
-                x: center_x + r * angle.cos() + (rand_float() - 0.5) * 50.0,
-                y: center_y + r * angle.sin() + (rand_float() - 0.5) * 50.0,
+                x,
+                y,
                 vx: 0.0,
                 vy: 0.0,
-                size: node.size.unwrap_or(match node.node_type {
-                    NodeType::Assessor => 20.0,
-                    NodeType::Application => 12.0,
-                }),
-                color: node.color.clone().unwrap_or_else(|| match node.node_type {
-                    NodeType::Assessor => self.config.theme.primary.clone(),
-                    NodeType::Application => self.config.theme.secondary.clone(),
-                }),
+                size,
+                color: String::new(),
                 fixed: false,
                 metadata: node.metadata.clone(),
+                base_size: size,
+                base_color: node.color.clone(),
             }
         }).collect();
 
-        self.edges = edges;
+        let ids: HashSet<&str> = self.nodes.iter().map(|n| n.id.as_str()).collect();
+        let edge_count = edges.len();
+        self.edges = edges.into_iter()
+            .filter(|e| ids.contains(e.source.as_str()) && ids.contains(e.target.as_str()))
+            .collect();
+        self.dropped_edges = edge_count - self.edges.len();
+        self.diverged = false;
+        self.apply_encodings();
+        self.remap_selection(&selected_ids, hovered_id.as_deref());
+        self.edge_transitions.clear();
+        self.path_nodes.clear();
+        self.path_edges.clear();
         self.simulation_running = true;
+    }
+
+    /// Starting position of node `i` of `count` under the initial layout
+    fn initial_position(&self, i: usize, count: usize, node_type: &NodeType) -> (f64, f64) {
+        let (width, height) = (self.config.width, self.config.height);
+
+        match self.initial_layout {
+            InitialLayout::Bipartite => {
+                let center_x = width / 2.0;
+                let center_y = height / 2.0;
+                let radius = (width.min(height) / 3.0).max(100.0);
+                let angle = (i as f64 / count as f64) * 2.0 * PI;
+
+                // Assessors in inner ring, applications in outer ring
+                let r = match node_type {
+                    NodeType::Assessor => radius * 0.4,
+                    NodeType::Application => radius * 0.9,
+                };
+
+                (
+                    center_x + r * angle.cos() + (rand_float() - 0.5) * 50.0,
+                    center_y + r * angle.sin() + (rand_float() - 0.5) * 50.0,
+                )
+            }
+            InitialLayout::Grid => {
+                let cols = (count as f64).sqrt().ceil().max(1.0) as usize;
+                let rows = count.div_ceil(cols).max(1);
+                let cell_w = (width - INITIAL_LAYOUT_MARGIN * 2.0) / cols as f64;
+                let cell_h = (height - INITIAL_LAYOUT_MARGIN * 2.0) / rows as f64;
+                (
+                    INITIAL_LAYOUT_MARGIN + ((i % cols) as f64 + 0.5) * cell_w,
+                    INITIAL_LAYOUT_MARGIN + ((i / cols) as f64 + 0.5) * cell_h,
+                )
+            }
+            InitialLayout::Random => (
+                INITIAL_LAYOUT_MARGIN + rand_float() * (width - INITIAL_LAYOUT_MARGIN * 2.0),
+                INITIAL_LAYOUT_MARGIN + rand_float() * (height - INITIAL_LAYOUT_MARGIN * 2.0),
+            ),
+        }
+    }
 
+    /// Initial node placement for the next `set_data`: "bipartite" (default),
+    /// "grid" or "random"
+    pub fn set_initial_layout(&mut self, strategy: &str) -> Result<(), JsValue> {
+        self.initial_layout = match strategy {
+            "bipartite" => InitialLayout::Bipartite,
+            "grid" => InitialLayout::Grid,
+            "random" => InitialLayout::Random,
+            _ => return Err(JsValue::from_str(&format!("Unknown initial layout: {}", strategy))),
+        };
         Ok(())
     }
 
-    /// Configure physics simulation
+    /// Keep selected and hovered nodes across `set_data` when their ids still exist
+    /// (default); when off, a reload clears them
+    pub fn set_preserve_selection(&mut self, enabled: bool) {
+        self.preserve_selection = enabled;
+    }
+
+    /// Point selection and hover at the reloaded nodes with the same ids, dropping removed ones
+    fn remap_selection(&mut self, selected_ids: &[String], hovered_id: Option<&str>) {
+        if !self.preserve_selection {
+            self.selected_nodes.clear();
+            self.hovered_node = None;
+            return;
+        }
+
+        let index: HashMap<&str, usize> = self.nodes.iter()
+            .enumerate()
+            .map(|(i, n)| (n.id.as_str(), i))
+            .collect();
+        self.selected_nodes = selected_ids.iter()
+            .filter_map(|id| index.get(id.as_str()).copied())
+            .collect();
+        self.hovered_node = hovered_id.and_then(|id| index.get(id).copied());
+    }
+
+    /// Configure physics simulation. Values are clamped to ranges the integrator
+    /// stays stable in; non-finite values are ignored.
     pub fn set_physics(&mut self, repulsion: f64, attraction: f64, damping: f64) {
-        self.repulsion_strength = repulsion;
-        self.attraction_strength = attraction;
-        self.damping = damping;
+        let sane = |value: f64, current: f64, min: f64, max: f64| {
+            if value.is_finite() { value.clamp(min, max) } else { current }
+        };
+        self.repulsion_strength = sane(repulsion, self.repulsion_strength, 0.0, 100_000.0);
+        self.attraction_strength = sane(attraction, self.attraction_strength, 0.0, 1.0);
+        self.damping = sane(damping, self.damping, 0.0, 0.99);
+    }
+
+    /// Set the repulsion falloff: "inverse_square" (default), "inverse" (1/dist)
+    /// or "capped" (inverse-square, zero beyond the repulsion range)
+    pub fn set_repulsion_model(&mut self, model: &str) -> Result<(), JsValue> {
+        self.repulsion_model = match model {
+            "inverse_square" => RepulsionModel::InverseSquare,
+            "inverse" => RepulsionModel::Inverse,
+            "capped" => RepulsionModel::Capped,
+            _ => return Err(JsValue::from_str(&format!("Unknown repulsion model: {}", model))),
+        };
+        self.simulation_running = true;
+        Ok(())
+    }
+
+    /// Distance (px) beyond which the "capped" repulsion model applies no force
+    pub fn set_repulsion_range(&mut self, range: f64) {
+        self.repulsion_range = range.max(1.0);
+        self.simulation_running = true;
+    }
+
+    /// Push overlapping nodes apart after each simulation step
+    pub fn set_collision(&mut self, enabled: bool) {
+        self.collision_enabled = enabled;
+        self.simulation_running = true;
+    }
+
+    /// Extra gap (px) kept between node shapes when collision is enabled
+    pub fn set_collision_padding(&mut self, px: f64) {
+        self.collision_padding = px.max(0.0);
+        self.simulation_running = true;
+    }
+
+    /// Separate pairs closer than their combined size plus padding, using a
+    /// uniform grid so only nodes in neighboring cells are compared
+    fn resolve_collisions(&mut self) {
+        let max_size = self.nodes.iter().map(|n| n.size).fold(0.0, f64::max);
+        let cell = (max_size * 2.0 + self.collision_padding).max(1.0);
+        let cell_of = |x: f64, y: f64| ((x / cell).floor() as i64, (y / cell).floor() as i64);
+
+        let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            grid.entry(cell_of(node.x, node.y)).or_default().push(i);
+        }
+
+        for i in 0..self.nodes.len() {
+            let (cx, cy) = cell_of(self.nodes[i].x, self.nodes[i].y);
+            for gx in (cx - 1)..=(cx + 1) {
+                for gy in (cy - 1)..=(cy + 1) {
+                    let Some(bucket) = grid.get(&(gx, gy)) else { continue };
+                    for &j in bucket.iter().filter(|&&j| j > i) {
+                        self.separate(i, j);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Move a colliding pair apart along the line between them; pinned nodes stay put
+    fn separate(&mut self, i: usize, j: usize) {
+        let min_dist = self.nodes[i].size + self.nodes[j].size + self.collision_padding;
+        let dx = self.nodes[j].x - self.nodes[i].x;
+        let dy = self.nodes[j].y - self.nodes[i].y;
+        let dist = (dx * dx + dy * dy).sqrt();
+        if dist >= min_dist {
+            return;
+        }
+
+        let pinned = |idx: usize| self.nodes[idx].fixed || self.dragging_node == Some(idx);
+        let (share_i, share_j) = match (pinned(i), pinned(j)) {
+            (true, true) => return,
+            (true, false) => (0.0, 1.0),
+            (false, true) => (1.0, 0.0),
+            (false, false) => (0.5, 0.5),
+        };
+
+        // Coincident nodes split horizontally
+        let (ux, uy) = if dist > 0.0 { (dx / dist, dy / dist) } else { (1.0, 0.0) };
+        let overlap = min_dist - dist;
+        self.nodes[i].x -= ux * overlap * share_i;
+        self.nodes[i].y -= uy * overlap * share_i;
+        self.nodes[j].x += ux * overlap * share_j;
+        self.nodes[j].y += uy * overlap * share_j;
+    }
+
+    /// Point nodes are pulled toward (None = canvas center on that axis)
+    pub fn set_gravity_center(&mut self, x: Option<f64>, y: Option<f64>) {
+        self.gravity_center = (x, y);
+    }
+
+    /// Configure repulsion and mass for one node type ("assessor" or "application").
+    /// Heavier nodes move less under the same force; 1.0/1.0 is the default.
+    pub fn set_type_physics(&mut self, node_type: &str, repulsion_multiplier: f64, mass: f64) -> Result<(), JsValue> {
+        let physics = TypePhysics {
+            repulsion_multiplier: repulsion_multiplier.max(0.0),
+            mass: mass.max(0.01),
+        };
+        match node_type {
+            "assessor" => self.assessor_physics = physics,
+            "application" => self.application_physics = physics,
+            _ => return Err(JsValue::from_str(&format!("Unknown node type: {}", node_type))),
+        }
+        Ok(())
+    }
+
+    fn type_physics(&self, node_type: &NodeType) -> TypePhysics {
+        match node_type {
+            NodeType::Assessor => self.assessor_physics,
+            NodeType::Application => self.application_physics,
+        }
+    }
+
+    /// Strength of the pull toward the gravity center; 0 disables gravity for free layouts
+    pub fn set_center_gravity(&mut self, strength: f64) {
+        self.center_gravity = strength.max(0.0);
+    }
+
+    /// Map edge statuses to colors (`{status: color}`); unmapped statuses use theme defaults
+    pub fn set_status_colors(&mut self, map_js: JsValue) -> Result<(), JsValue> {
+        self.status_colors = serde_wasm_bindgen::from_value(map_js)?;
+        self.render()
+    }
+
+    /// Draw a ring around each node showing the completed share of its assignments
+    pub fn set_node_progress_ring(&mut self, enabled: bool) {
+        self.show_progress_ring = enabled;
+        self.refresh();
+    }
+
+    /// Outline every node so it stays visible against a same-colored background.
+    /// `color` defaults to the theme background; selection outlines draw on top.
+    pub fn set_node_stroke(&mut self, enabled: bool, color: Option<String>, width: f64) {
+        self.node_stroke = enabled;
+        self.node_stroke_color = color;
+        self.node_stroke_width = width.max(0.0);
+        self.refresh();
+    }
+
+    /// Set conflicts of interest (`[{assessor_id, application_id}]`), drawn as red
+    /// dashed links with a warning glyph whether or not an assignment exists.
+    /// Errors only when the conflicts can't be parsed.
+    pub fn set_conflicts(&mut self, conflicts_js: JsValue) -> Result<(), JsValue> {
+        self.conflicts = serde_wasm_bindgen::from_value(conflicts_js)?;
+        self.refresh();
+        Ok(())
+    }
+
+    /// Draw edge labels at their midpoints once zoomed in past the clutter threshold
+    pub fn set_show_edge_labels(&mut self, enabled: bool) {
+        self.show_edge_labels = enabled;
+        self.refresh();
+    }
+
+    /// Hide node labels that would overlap others; hovered, selected and larger nodes win
+    pub fn set_label_collision(&mut self, enabled: bool) {
+        self.label_collision = enabled;
+        self.refresh();
+    }
+
+    /// Alias for `set_label_collision`
+    pub fn set_smart_labels(&mut self, enabled: bool) {
+        self.set_label_collision(enabled);
+    }
+
+    /// Draw the hovered and selected nodes (and their edges) above the rest
+    pub fn set_hover_to_front(&mut self, enabled: bool) {
+        self.raise_active = enabled;
+        self.refresh();
+    }
+
+    /// Encode two numeric metadata keys on one node type: size over `size_range`
+    /// (`[min, max]` px) and color over `color_range` (`[from, to]`).
+    /// Pass `None` for a key to keep the default size or color.
+    pub fn set_node_encoding(
+        &mut self,
+        node_type: &str,
+        size_key: Option<String>,
+        color_key: Option<String>,
+        size_range: Vec<f64>,
+        color_range: Vec<String>,
+    ) -> Result<(), JsValue> {
+        let encoding = if size_key.is_none() && color_key.is_none() {
+            None
+        } else {
+            Some(NodeEncoding {
+                size_key,
+                color_key,
+                size_range: (
+                    size_range.first().copied().unwrap_or(8.0),
+                    size_range.get(1).copied().unwrap_or(24.0),
+                ),
+                color_range: (
+                    color_range.first().cloned().unwrap_or_else(|| self.config.theme.grid.clone()),
+                    color_range.get(1).cloned().unwrap_or_else(|| self.config.theme.primary.clone()),
+                ),
+            })
+        };
+
+        match node_type {
+            "assessor" => self.assessor_encoding = encoding,
+            "application" => self.application_encoding = encoding,
+            _ => return Err(JsValue::from_str(&format!("Unknown node type: {}", node_type))),
+        }
+
+        self.apply_encodings();
+        self.refresh();
+        Ok(())
+    }
+
+    /// Size nodes by how many edges touch them; metadata size encodings still take precedence
+    pub fn set_size_by_degree(&mut self, enabled: bool) {
+        self.size_by_degree = enabled;
+        self.apply_encodings();
+        self.refresh();
+    }
+
+    /// Size range (px) for degree sizing: the least-connected node gets `min`, the most `max`
+    pub fn set_degree_size_range(&mut self, min: f64, max: f64) {
+        let min = min.max(1.0);
+        self.degree_size_range = (min, max.max(min));
+        self.apply_encodings();
+        self.refresh();
+    }
+
+    /// Edge count per node, in node order
+    fn degrees(&self) -> Vec<usize> {
+        let index: HashMap<&str, usize> = self.nodes.iter()
+            .enumerate()
+            .map(|(i, n)| (n.id.as_str(), i))
+            .collect();
+        let mut degrees = vec![0; self.nodes.len()];
+        for edge in &self.edges {
+            for id in [&edge.source, &edge.target] {
+                if let Some(&i) = index.get(id.as_str()) {
+                    degrees[i] += 1;
+                }
+            }
+        }
+        degrees
+    }
+
+    /// Color nodes by community (label propagation over assignments), using `theme.accent`
+    pub fn color_by_community(&mut self, enabled: bool) {
+        self.community_coloring = enabled;
+        self.apply_encodings();
+        self.refresh();
+    }
+
+    /// Map of node id to community index from label propagation
+    pub fn get_communities(&self) -> JsValue {
+        let communities: HashMap<&str, usize> = self.nodes.iter()
+            .zip(self.detect_communities())
+            .map(|(n, c)| (n.id.as_str(), c))
+            .collect();
+        serde_wasm_bindgen::to_value(&communities).unwrap()
+    }
+
+    /// Label propagation: each node repeatedly takes the label with the most votes
+    /// among its neighbors (ties to the smallest) until stable. Each vote is weighted
+    /// by 1 + the neighbors the pair shares, so a lone bridge between two dense
+    /// groups can't outvote either group. Communities are renumbered 0.. in order
+    /// of first appearance.
+    fn detect_communities(&self) -> Vec<usize> {
+        let adjacency = self.adjacency();
+        let neighbor_sets: Vec<HashSet<usize>> = adjacency.iter()
+            .map(|neighbors| neighbors.iter().map(|&(n, _)| n).collect())
+            .collect();
+        let mut labels: Vec<usize> = (0..self.nodes.len()).collect();
+
+        for _ in 0..COMMUNITY_MAX_ITERATIONS {
+            let mut changed = false;
+            for i in 0..labels.len() {
+                let mut counts: HashMap<usize, usize> = HashMap::new();
+                for &(neighbor, _) in &adjacency[i] {
+                    let shared = neighbor_sets[i].intersection(&neighbor_sets[neighbor]).count();
+                    *counts.entry(labels[neighbor]).or_insert(0) += 1 + shared;
+                }
+                let best = counts.into_iter()
+                    .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+                    .map(|(label, _)| label);
+                if let Some(label) = best {
+                    if label != labels[i] {
+                        labels[i] = label;
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut renumber: HashMap<usize, usize> = HashMap::new();
+        labels.iter()
+            .map(|label| {
+                let next = renumber.len();
+                *renumber.entry(*label).or_insert(next)
+            })
+            .collect()
+    }
+
+    /// Recompute node size/color from base values and the active encodings
+    fn apply_encodings(&mut self) {
+        let degree_sizes: Option<Vec<f64>> = self.size_by_degree.then(|| {
+            let degrees = self.degrees();
+            let min = degrees.iter().copied().min().unwrap_or(0) as f64;
+            let max = degrees.iter().copied().max().unwrap_or(0) as f64;
+            let (lo, hi) = self.degree_size_range;
+            degrees.iter()
+                .map(|&d| {
+                    let t = if max > min { (d as f64 - min) / (max - min) } else { 0.5 };
+                    lo + t * (hi - lo)
+                })
+                .collect()
+        });
+
+        for node_type in [NodeType::Assessor, NodeType::Application] {
+            let encoding = match node_type {
+                NodeType::Assessor => self.assessor_encoding.clone(),
+                NodeType::Application => self.application_encoding.clone(),
+            };
+
+            let size_extent = encoding.as_ref()
+                .and_then(|e| e.size_key.as_deref())
+                .and_then(|key| self.metadata_extent(&node_type, key));
+            let color_extent = encoding.as_ref()
+                .and_then(|e| e.color_key.as_deref())
+                .and_then(|key| self.metadata_extent(&node_type, key));
+            let theme_color = match node_type {
+                NodeType::Assessor => self.config.theme.primary.clone(),
+                NodeType::Application => self.config.theme.secondary.clone(),
+            };
+
+            for (i, node) in self.nodes.iter_mut().enumerate().filter(|(_, n)| n.node_type == node_type) {
+                node.size = degree_sizes.as_ref().map_or(node.base_size, |sizes| sizes[i]);
+                node.color = node.base_color.clone().unwrap_or_else(|| theme_color.clone());
+
+                let Some(encoding) = &encoding else { continue };
+
+                if let (Some(key), Some((min, max))) = (&encoding.size_key, size_extent) {
+                    if let Some(v) = metadata_number(node, key) {
+                        let t = if max > min { (v - min) / (max - min) } else { 0.5 };
+                        node.size = encoding.size_range.0 + t * (encoding.size_range.1 - encoding.size_range.0);
+                    }
+                }
+
+                if let (Some(key), Some((min, max))) = (&encoding.color_key, color_extent) {
+                    if let Some(v) = metadata_number(node, key) {
+                        let t = if max > min { (v - min) / (max - min) } else { 0.5 };
+                        node.color = interpolate_color(&encoding.color_range.0, &encoding.color_range.1, t);
+                    }
+                }
+            }
+        }
+
+        if self.community_coloring && !self.config.theme.accent.is_empty() {
+            let communities = self.detect_communities();
+            let accent = &self.config.theme.accent;
+            for (node, community) in self.nodes.iter_mut().zip(communities) {
+                node.color = accent[community % accent.len()].clone();
+            }
+        }
+    }
+
+    /// Min and max of a numeric metadata key across nodes of one type
+    fn metadata_extent(&self, node_type: &NodeType, key: &str) -> Option<(f64, f64)> {
+        let values: Vec<f64> = self.nodes.iter()
+            .filter(|n| n.node_type == *node_type)
+            .filter_map(|n| metadata_number(n, key))
+            .collect();
+
+        if values.is_empty() {
+            return None;
+        }
+        Some((
+            values.iter().cloned().fold(f64::INFINITY, f64::min),
+            values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+        ))
+    }
+
+    fn draw_encoding_legend<T: DrawTarget + ?Sized>(&self, ctx: &T, x: f64, mut y: f64) -> Result<(), JsValue> {
+        let entries = [
+            ("Assessor", &self.assessor_encoding),
+            ("Application", &self.application_encoding),
+        ];
+
+        for (type_label, encoding) in entries {
+            let Some(encoding) = encoding else { continue };
+
+            if let Some(key) = &encoding.size_key {
+                ctx.set_fill_style(&self.config.theme.text);
+                ctx.fill_text(&format!("{} size: {}", type_label, key), x, y)?;
+
+                // Size ramp: min, mid and max, capped to fit the legend row
+                ctx.set_fill_style(&self.config.theme.secondary);
+                let (min, max) = encoding.size_range;
+                let mut cx = x;
+                for size in [min, (min + max) / 2.0, max] {
+                    let r = size.clamp(2.0, 10.0);
+                    ctx.begin_path();
+                    ctx.arc(cx + r, y + 16.0, r, 0.0, 2.0 * PI)?;
+                    ctx.fill();
+                    cx += r * 2.0 + 6.0;
+                }
+                y += 36.0;
+            }
+
+            if let Some(key) = &encoding.color_key {
+                ctx.set_fill_style(&self.config.theme.text);
+                ctx.fill_text(&format!("{} color: {}", type_label, key), x, y)?;
+
+                // Color ramp
+                let steps = 20;
+                let ramp_width = 100.0;
+                for i in 0..steps {
+                    let t = i as f64 / (steps - 1) as f64;
+                    ctx.set_fill_style(&interpolate_color(&encoding.color_range.0, &encoding.color_range.1, t));
+                    ctx.fill_rect(x + i as f64 * ramp_width / steps as f64, y + 8.0, ramp_width / steps as f64 + 0.5, 10.0);
+                }
+                y += 32.0;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Draw edges as curves (default) or straight lines
+    pub fn set_curved_edges(&mut self, enabled: bool) {
+        self.curved_edges = enabled;
+        self.refresh();
+    }
+
+    /// Enable or disable the hover grow on nodes; disabled, the hovered node keeps its base size
+    pub fn set_hover_effect(&mut self, enabled: bool) {
+        self.hover_effect = enabled;
+        self.refresh();
+    }
+
+    /// Scale applied to the hovered node (default 1.2); 1.0 keeps base size
+    pub fn set_hover_grow(&mut self, grow: f64) {
+        self.hover_grow = grow.max(0.1);
+        self.refresh();
+    }
+
+    /// Set how long edge status color transitions take (0 snaps immediately)
+    pub fn set_transition_duration(&mut self, ms: f64) {
+        self.transition_ms = ms.max(0.0);
+        if self.transition_ms == 0.0 {
+            self.edge_transitions.clear();
+        }
+    }
+
+    /// Disable animated transitions for users who prefer reduced motion
+    pub fn set_reduced_motion(&mut self, enabled: bool) {
+        self.reduced_motion = enabled;
+        if enabled {
+            self.edge_transitions.clear();
+        }
+    }
+
+    /// Make output reproducible: reseed the layout RNG and skip animated transitions.
+    /// Call before `set_data` so initial positions are identical between runs.
+    pub fn set_deterministic(&mut self, enabled: bool) {
+        self.deterministic = enabled;
+        if enabled {
+            seed_rand(DETERMINISTIC_SEED);
+            self.edge_transitions.clear();
+        }
+    }
+
+    /// Change an edge's status, tweening its color from the old status color to the new one.
+    /// Returns false if no edge connects `source` to `target`.
+    pub fn update_edge_status(&mut self, source: &str, target: &str, status: &str) -> bool {
+        let Some(idx) = self.edges.iter().position(|e| e.source == source && e.target == target) else {
+            return false;
+        };
+
+        let from_color = self.current_edge_color(idx);
+        self.edges[idx].status = Some(status.to_string());
+        // The loaded payload no longer matches, so reloading it must not be skipped
+        self.data_hash = None;
+        let to_color = self.edge_color(&self.edges[idx]);
+
+        self.edge_transitions.retain(|t| t.edge_index != idx);
+        if !self.reduced_motion && !self.deterministic && self.transition_ms > 0.0 && from_color != to_color {
+            self.edge_transitions.push(EdgeTransition {
+                edge_index: idx,
+                from_color,
+                to_color,
+                elapsed_ms: 0.0,
+            });
+        }
+
+        self.refresh();
+        true
+    }
+
+    /// Advance edge transitions (call from requestAnimationFrame)
+    pub fn animate(&mut self, delta_ms: f64) -> bool {
+        if self.edge_transitions.is_empty() {
+            return false;
+        }
+
+        for transition in &mut self.edge_transitions {
+            transition.elapsed_ms += delta_ms;
+        }
+        let duration = self.transition_ms;
+        self.edge_transitions.retain(|t| t.elapsed_ms < duration);
+
+        self.refresh();
+        !self.edge_transitions.is_empty()
     }
 
     /// Toggle simulation
     pub fn toggle_simulation(&mut self) -> bool {
         self.simulation_running = !self.simulation_running;
+        self.frozen_by_fit = false;
         self.simulation_running
     }
 
+    /// Whether the simulation stops itself once the layout settles.
+    /// Disable for graphs that receive continuous data updates.
+    pub fn set_auto_stop(&mut self, enabled: bool) {
+        self.auto_stop = enabled;
+    }
+
+    /// Restart the simulation with a small random velocity kick to re-layout after edits
+    pub fn restart_simulation(&mut self) {
+        for node in &mut self.nodes {
+            if node.fixed {
+                continue;
+            }
+            node.vx += (rand_float() - 0.5) * 4.0;
+            node.vy += (rand_float() - 0.5) * 4.0;
+        }
+        self.simulation_running = true;
+    }
+
+    /// Run as many simulation steps as fit in `max_ms` (at least one), stopping
+    /// early once the layout settles. Timing uses the performance API, like
+    /// `benchmark_canvas`. Returns whether any step ran; a budget that is not a
+    /// positive finite number runs nothing.
+    pub fn step_simulation_budgeted(&mut self, max_ms: f64) -> bool {
+        if !(max_ms.is_finite() && max_ms > 0.0) {
+            return false;
+        }
+
+        let start = now_ms();
+        let mut stepped = false;
+        let mut last_step_ms = 0.0;
+
+        while self.simulation_running {
+            let step_start = now_ms();
+            if !self.step_simulation() {
+                break;
+            }
+            stepped = true;
+            let now = now_ms();
+            last_step_ms = f64::max(last_step_ms, now - step_start);
+            // Don't start a step that would likely overrun the budget
+            if now - start + last_step_ms > max_ms {
+                break;
+            }
+        }
+
+        stepped
+    }
+
     /// Step physics simulation
     pub fn step_simulation(&mut self) -> bool {
         if !self.simulation_running || self.nodes.is_empty() {
             return false;
         }
 
-        let center_x = self.config.width / 2.0;
-        let center_y = self.config.height / 2.0;
+        let center_x = self.gravity_center.0.unwrap_or(self.config.width / 2.0);
+        let center_y = self.gravity_center.1.unwrap_or(self.config.height / 2.0);
 
         // Calculate forces
         let n = self.nodes.len();
         let mut forces: Vec<(f64, f64)> = vec![(0.0, 0.0); n];
+        let physics: Vec<TypePhysics> = self.nodes.iter().map(|n| self.type_physics(&n.node_type)).collect();
 
         // Repulsion between all nodes
+        let range_sq = self.repulsion_range * self.repulsion_range;
         for i in 0..n {
             for j in (i + 1)..n {
                 let dx = self.nodes[j].x - self.nodes[i].x;
                 let dy = self.nodes[j].y - self.nodes[i].y;
                 let dist_sq = dx * dx + dy * dy;
+                if self.repulsion_model == RepulsionModel::Capped && dist_sq > range_sq {
+                    continue;
+                }
                 let dist = dist_sq.sqrt().max(1.0);
 
-                let force = self.repulsion_strength / dist_sq;
+                let multiplier = (physics[i].repulsion_multiplier + physics[j].repulsion_multiplier) / 2.0;
+                let falloff = match self.repulsion_model {
+                    RepulsionModel::Inverse => dist,
+                    // Clamped distance, so coincident nodes don't divide by zero
+                    RepulsionModel::InverseSquare | RepulsionModel::Capped => dist * dist,
+                };
+                let force = self.repulsion_strength * multiplier / falloff;
                 let fx = (dx / dist) * force;
                 let fy = (dy / dist) * force;
 
@@ -203,6 +1133,10 @@ impl NetworkGraphChart {
             let target_idx = self.nodes.iter().position(|n| n.id == edge.target);
 
             if let (Some(s), Some(t)) = (source_idx, target_idx) {
+                // A self-loop has no length to contract
+                if s == t {
+                    continue;
+                }
                 let dx = self.nodes[t].x - self.nodes[s].x;
                 let dy = self.nodes[t].y - self.nodes[s].y;
                 let dist = (dx * dx + dy * dy).sqrt().max(1.0);
@@ -235,8 +1169,16 @@ impl NetworkGraphChart {
                 continue;
             }
 
-            self.nodes[i].vx = (self.nodes[i].vx + forces[i].0) * self.damping;
-            self.nodes[i].vy = (self.nodes[i].vy + forces[i].1) * self.damping;
+            let mass = physics[i].mass;
+            self.nodes[i].vx = (self.nodes[i].vx + forces[i].0 / mass) * self.damping;
+            self.nodes[i].vy = (self.nodes[i].vy + forces[i].1 / mass) * self.damping;
+
+            // One NaN would spread to every node through the pairwise forces
+            if !self.nodes[i].vx.is_finite() || !self.nodes[i].vy.is_finite() {
+                self.nodes[i].vx = 0.0;
+                self.nodes[i].vy = 0.0;
+                self.diverged = true;
+            }
 
             // Limit velocity
             let speed = (self.nodes[i].vx * self.nodes[i].vx + self.nodes[i].vy * self.nodes[i].vy).sqrt();
@@ -248,88 +1190,275 @@ impl NetworkGraphChart {
             self.nodes[i].x += self.nodes[i].vx;
             self.nodes[i].y += self.nodes[i].vy;
 
+            if !self.nodes[i].x.is_finite() || !self.nodes[i].y.is_finite() {
+                self.nodes[i].x = center_x + (rand_float() - 0.5) * 50.0;
+                self.nodes[i].y = center_y + (rand_float() - 0.5) * 50.0;
+                self.diverged = true;
+            }
+
             total_movement += speed;
         }
 
+        if self.collision_enabled {
+            self.resolve_collisions();
+        }
+
         // Stop simulation when movement is minimal
-        if total_movement < 0.5 {
+        if self.auto_stop && total_movement < 0.5 {
             self.simulation_running = false;
+            self.frozen_by_fit = false;
         }
 
         true
     }
 
-    /// Render the graph
-    pub fn render(&self) -> Result<(), JsValue> {
-        let (canvas, ctx) = get_canvas_context(&self.canvas_id)?;
+    /// Whether the simulation has had to reset a node with a non-finite velocity
+    /// or position since the last `set_data` or `reset_view`
+    pub fn has_diverged(&self) -> bool {
+        self.diverged
+    }
 
-        canvas.set_width(self.config.width as u32);
-        canvas.set_height(self.config.height as u32);
+    /// Replace the color theme (a `ColorTheme` object) and re-render; nodes
+    /// without an explicit color pick up the new theme colors
+    pub fn set_theme(&mut self, theme_js: JsValue) -> Result<(), JsValue> {
+        self.apply_theme(serde_wasm_bindgen::from_value(theme_js)?);
+        self.render()
+    }
 
-        clear_canvas(&ctx, self.config.width, self.config.height, &self.config.theme.background);
+    /// Swap in a theme and recompute the node colors derived from it
+    fn apply_theme(&mut self, theme: ColorTheme) {
+        self.config.theme = theme;
+        self.edge_transitions.clear();
+        self.apply_encodings();
+    }
 
-        if self.nodes.is_empty() {
-            self.draw_empty_state(&ctx)?;
-            return Ok(());
-        }
+    /// Whether the last render failed because the 2D context was lost
+    pub fn is_context_lost(&self) -> bool {
+        self.context_lost.get()
+    }
 
-        // Apply zoom and pan transform
-        ctx.save();
-        ctx.translate(self.pan_x, self.pan_y)?;
+    /// Re-acquire the canvas context after a loss and re-render
+    pub fn restore(&mut self) -> Result<(), JsValue> {
+        self.context_lost.set(false);
+        self.render()
+    }
+
+    /// Re-render after a state change, warning once if the context was lost
+    fn refresh(&self) {
+        surface_context_loss(&self.context_lost, &self.canvas_id, || self.render());
+    }
+
+    /// Render the graph
+    pub fn render(&self) -> Result<(), JsValue> {
+        if !self.config.has_drawable_size() {
+            return Ok(());
+        }
+
+        let (canvas, ctx) = get_canvas_context(&self.canvas_id)?;
+        let start = now_ms();
+        let result = track_context_loss(&self.context_lost, &ctx, || self.draw_frame(&canvas, &ctx));
+        self.render_timing.record(now_ms() - start);
+        result
+    }
+
+    /// Duration of the last render in milliseconds
+    pub fn last_render_ms(&self) -> f64 {
+        self.render_timing.last()
+    }
+
+    /// Average render duration over recent renders in milliseconds
+    pub fn avg_render_ms(&self) -> f64 {
+        self.render_timing.average()
+    }
+
+    /// Render offscreen and return raw RGBA bytes for pixel-level regression tests
+    pub fn render_to_image_data(&self, width: u32, height: u32) -> Result<Vec<u8>, JsValue> {
+        render_image_data(&self.config, width, height, |canvas, ctx| self.draw_frame(canvas, ctx))
+    }
+
+    /// Render while adapting detail to a frame budget (call from requestAnimationFrame).
+    ///
+    /// Frames over budget drop one level of detail: labels, then arrowheads, then
+    /// curves, then edge thickness. Frames well under budget restore one level, so
+    /// detail comes back gradually and a single fast frame can't cause flicker.
+    pub fn render_with_budget(&mut self, budget_ms: f64) -> Result<(), JsValue> {
+        let start = now_ms();
+        let result = self.render();
+        self.last_frame_ms = now_ms() - start;
+        self.adapt_quality(budget_ms);
+        result
+    }
+
+    /// Step the quality level for the last frame's duration against `budget_ms`
+    fn adapt_quality(&mut self, budget_ms: f64) {
+        if self.last_frame_ms > budget_ms {
+            self.quality_level = (self.quality_level + 1).min(QUALITY_THIN_EDGES);
+        } else if self.last_frame_ms < budget_ms * 0.5 {
+            self.quality_level = self.quality_level.saturating_sub(1);
+        }
+    }
+
+    fn draw_frame(&self, canvas: &HtmlCanvasElement, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
+        canvas.set_width(self.config.width as u32);
+        canvas.set_height(self.config.height as u32);
+
+        self.draw_to(ctx)
+    }
+
+    /// Draw the full chart onto any target; the canvas must already be sized
+    pub(crate) fn draw_to<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        clear_canvas(ctx, self.config.width, self.config.height, &self.config.theme.background);
+
+        if self.nodes.is_empty() {
+            self.draw_empty_state(ctx)?;
+            return Ok(());
+        }
+
+        // Apply zoom and pan transform
+        ctx.save();
+        ctx.translate(self.pan_x, self.pan_y)?;
         ctx.scale(self.zoom, self.zoom)?;
 
         // Draw edges first (behind nodes)
-        self.draw_edges(&ctx)?;
+        self.draw_edges(ctx)?;
+        if !self.conflicts.is_empty() {
+            self.draw_conflicts(ctx)?;
+        }
+        if self.show_edge_labels && self.zoom >= EDGE_LABEL_MIN_ZOOM && self.quality_level < QUALITY_NO_LABELS {
+            self.draw_edge_labels(ctx)?;
+        }
 
         // Draw nodes
-        self.draw_nodes(&ctx)?;
+        self.draw_nodes(ctx)?;
 
         ctx.restore();
 
         // Draw UI overlay
-        self.draw_overlay(&ctx)?;
+        self.draw_overlay(ctx)?;
 
         Ok(())
     }
 
-    fn draw_edges(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
-        for edge in &self.edges {
+    /// Resting color for an edge based on its status
+    fn edge_color(&self, edge: &NetworkEdge) -> String {
+        edge.color.clone().unwrap_or_else(|| {
+            if let Some(color) = edge.status.as_ref().and_then(|s| self.status_colors.get(s)) {
+                return color.clone();
+            }
+            match edge.status.as_deref() {
+                Some("completed") => self.config.theme.success.clone(),
+                Some("in_progress") => self.config.theme.warning.clone(),
+                _ => self.config.theme.grid.clone(),
+            }
+        })
+    }
+
+    /// Edge color including any in-flight status transition
+    fn current_edge_color(&self, idx: usize) -> String {
+        match self.edge_transitions.iter().find(|t| t.edge_index == idx) {
+            Some(t) => interpolate_color(&t.from_color, &t.to_color, t.elapsed_ms / self.transition_ms),
+            None => self.edge_color(&self.edges[idx]),
+        }
+    }
+
+    /// Whether a node is hovered or selected
+    fn is_active_node(&self, idx: usize) -> bool {
+        self.hovered_node == Some(idx) || self.selected_nodes.contains(&idx)
+    }
+
+    /// Indices with inactive items first so active ones paint on top
+    fn draw_order(&self, len: usize, is_active: impl Fn(usize) -> bool) -> Vec<usize> {
+        if !self.raise_active {
+            return (0..len).collect();
+        }
+        let (active, mut order): (Vec<usize>, Vec<usize>) = (0..len).partition(|&i| is_active(i));
+        order.extend(active);
+        order
+    }
+
+    /// Control-point offset as a fraction of edge length; 0 draws straight edges
+    fn edge_curve(&self) -> f64 {
+        if !self.curved_edges || self.quality_level >= QUALITY_STRAIGHT_EDGES {
+            0.0
+        } else {
+            0.1
+        }
+    }
+
+    fn draw_edges<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        let active_ids: Vec<&str> = self.nodes.iter()
+            .enumerate()
+            .filter(|(i, _)| self.is_active_node(*i))
+            .map(|(_, n)| n.id.as_str())
+            .collect();
+        let order = self.draw_order(self.edges.len(), |i| {
+            let edge = &self.edges[i];
+            active_ids.contains(&edge.source.as_str()) || active_ids.contains(&edge.target.as_str())
+        });
+
+        for i in order {
+            let edge = &self.edges[i];
             let source = self.nodes.iter().find(|n| n.id == edge.source);
             let target = self.nodes.iter().find(|n| n.id == edge.target);
 
             if let (Some(s), Some(t)) = (source, target) {
-                // Determine color based on status
-                let color = edge.color.clone().unwrap_or_else(|| {
-                    match edge.status.as_deref() {
-                        Some("completed") => self.config.theme.success.clone(),
-                        Some("in_progress") => self.config.theme.warning.clone(),
-                        _ => self.config.theme.grid.clone(),
-                    }
-                });
+                // Determine color based on status (path highlight wins)
+                let on_path = self.path_edges.contains(&i);
+                let color = if on_path {
+                    self.config.theme.primary.clone()
+                } else {
+                    self.current_edge_color(i)
+                };
 
-                ctx.set_stroke_style(&JsValue::from_str(&color));
-                ctx.set_line_width(edge.weight.unwrap_or(1.0).max(0.5));
+                ctx.set_stroke_style(&color);
+                if on_path {
+                    ctx.set_line_width(3.0);
+                } else if self.quality_level >= QUALITY_THIN_EDGES {
+                    ctx.set_line_width(0.5);
+                } else {
+                    ctx.set_line_width(edge.weight.unwrap_or(1.0).max(0.5));
+                }
+
+                if edge.source == edge.target {
+                    let (cx, cy, r) = self_loop_circle(s);
+                    ctx.begin_path();
+                    ctx.arc(cx, cy, r, 0.0, 2.0 * PI)?;
+                    ctx.stroke();
+                    continue;
+                }
 
-                // Draw curved edge
+                // Draw curved edge (straight when disabled or degraded for frame budget)
                 let mid_x = (s.x + t.x) / 2.0;
                 let mid_y = (s.y + t.y) / 2.0;
                 let dx = t.x - s.x;
                 let dy = t.y - s.y;
-                let perpx = -dy * 0.1;
-                let perpy = dx * 0.1;
+                let curve = self.edge_curve();
+                let perpx = -dy * curve;
+                let perpy = dx * curve;
 
                 ctx.begin_path();
                 ctx.move_to(s.x, s.y);
-                ctx.quadratic_curve_to(mid_x + perpx, mid_y + perpy, t.x, t.y);
+                if curve > 0.0 {
+                    ctx.quadratic_curve_to(mid_x + perpx, mid_y + perpy, t.x, t.y);
+                } else {
+                    ctx.line_to(t.x, t.y);
+                }
                 ctx.stroke();
 
-                // Draw arrow at target
-                let angle = (t.y - (mid_y + perpy)).atan2(t.x - (mid_x + perpx));
+                if self.quality_level >= QUALITY_NO_ARROWS {
+                    continue;
+                }
+
+                // Draw arrow where the curve meets the target's outline, aligned
+                // with the curve's tangent there rather than the chord
+                let (p0, c, p2) = ((s.x, s.y), (mid_x + perpx, mid_y + perpy), (t.x, t.y));
+                let tip_t = curve_exit_t(p0, c, p2, t);
+                let (arrow_x, arrow_y) = quad_point(p0, c, p2, tip_t);
+                let (tx, ty) = quad_tangent(p0, c, p2, tip_t);
+                let angle = ty.atan2(tx);
                 let arrow_size = 6.0;
-                let arrow_x = t.x - t.size * angle.cos();
-                let arrow_y = t.y - t.size * angle.sin();
 
-                ctx.set_fill_style(&JsValue::from_str(&color));
+                ctx.set_fill_style(&color);
                 ctx.begin_path();
                 ctx.move_to(arrow_x, arrow_y);
                 ctx.line_to(
@@ -348,94 +1477,308 @@ impl NetworkGraphChart {
         Ok(())
     }
 
-    fn draw_nodes(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
-        for (i, node) in self.nodes.iter().enumerate() {
+    fn draw_conflicts<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        let color = self.config.theme.danger.as_str();
+        let glyph_radius = 7.0 / self.zoom;
+
+        for conflict in &self.conflicts {
+            let assessor = self.nodes.iter().find(|n| n.id == conflict.assessor_id);
+            let application = self.nodes.iter().find(|n| n.id == conflict.application_id);
+
+            if let (Some(a), Some(b)) = (assessor, application) {
+                ctx.set_stroke_style(color);
+                ctx.set_line_width(2.0);
+                ctx.set_line_dash(&[6.0, 4.0])?;
+                ctx.begin_path();
+                ctx.move_to(a.x, a.y);
+                ctx.line_to(b.x, b.y);
+                ctx.stroke();
+                ctx.set_line_dash(&[])?;
+
+                // Warning glyph at the midpoint
+                let (mx, my) = ((a.x + b.x) / 2.0, (a.y + b.y) / 2.0);
+                ctx.set_fill_style(color);
+                ctx.begin_path();
+                ctx.arc(mx, my, glyph_radius, 0.0, 2.0 * PI)?;
+                ctx.fill();
+                ctx.set_fill_style("#FFFFFF");
+                ctx.set_font(&self.config.font("bold", glyph_radius * 1.6));
+                ctx.set_text_align("center");
+                ctx.set_text_baseline("middle");
+                ctx.fill_text("!", mx, my)?;
+            }
+        }
+
+        ctx.set_text_baseline("alphabetic");
+        Ok(())
+    }
+
+    /// Labels on a background plate at each labeled edge's curve midpoint
+    fn draw_edge_labels<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        let font_size = self.config.axis_size() / self.zoom;
+        ctx.set_font(&self.config.font("", font_size));
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+
+        let curve = self.edge_curve();
+        let pad = 2.0 / self.zoom;
+
+        for edge in &self.edges {
+            let Some(label) = edge.label.as_deref().filter(|l| !l.is_empty()) else {
+                continue;
+            };
+            let source = self.nodes.iter().find(|n| n.id == edge.source);
+            let target = self.nodes.iter().find(|n| n.id == edge.target);
+
+            if let (Some(s), Some(t)) = (source, target) {
+                // Point at t = 0.5 on the quadratic curve drawn by draw_edges,
+                // or the top of a self-loop
+                let (x, y) = if edge.source == edge.target {
+                    let (cx, cy, r) = self_loop_circle(s);
+                    (cx, cy - r)
+                } else {
+                    (
+                        (s.x + t.x) / 2.0 - (t.y - s.y) * curve / 2.0,
+                        (s.y + t.y) / 2.0 + (t.x - s.x) * curve / 2.0,
+                    )
+                };
+                let width = ctx.measure_text_width(label)?;
+
+                ctx.set_fill_style(canvas_color(&self.config.theme.background));
+                ctx.fill_rect(x - width / 2.0 - pad, y - font_size / 2.0 - pad, width + pad * 2.0, font_size + pad * 2.0);
+                ctx.set_fill_style(readable_on(&self.config.theme.text, &self.config.theme.background));
+                ctx.fill_text(label, x, y)?;
+            }
+        }
+
+        ctx.set_text_baseline("alphabetic");
+        Ok(())
+    }
+
+    /// (completed, total) incident edges per node
+    fn node_progress(&self) -> Vec<(u32, u32)> {
+        let index: HashMap<&str, usize> = self.nodes.iter()
+            .enumerate()
+            .map(|(i, n)| (n.id.as_str(), i))
+            .collect();
+        let mut progress = vec![(0, 0); self.nodes.len()];
+
+        for edge in &self.edges {
+            let completed = edge.status.as_deref() == Some("completed");
+            for id in [edge.source.as_str(), edge.target.as_str()] {
+                if let Some(&i) = index.get(id) {
+                    progress[i].1 += 1;
+                    if completed {
+                        progress[i].0 += 1;
+                    }
+                }
+            }
+        }
+
+        progress
+    }
+
+    fn draw_progress_ring<T: DrawTarget + ?Sized>(&self, ctx: &T, x: f64, y: f64, radius: f64, completed: u32, total: u32) -> Result<(), JsValue> {
+        if total == 0 {
+            return Ok(());
+        }
+
+        let start = -PI / 2.0;
+        let fraction = completed as f64 / total as f64;
+
+        ctx.set_line_width(2.0);
+        ctx.set_stroke_style(&self.config.theme.grid);
+        ctx.begin_path();
+        ctx.arc(x, y, radius, 0.0, 2.0 * PI)?;
+        ctx.stroke();
+
+        if completed > 0 {
+            ctx.set_stroke_style(&self.config.theme.success);
+            ctx.begin_path();
+            ctx.arc(x, y, radius, start, start + fraction * 2.0 * PI)?;
+            ctx.stroke();
+        }
+
+        Ok(())
+    }
+
+    /// Node label cut to `NODE_LABEL_MAX_WIDTH` on screen; the label font must be set
+    fn node_label<T: DrawTarget + ?Sized>(&self, ctx: &T, node: &PhysicsNode) -> String {
+        truncate_to_width(ctx, &node.label, NODE_LABEL_MAX_WIDTH / self.zoom)
+    }
+
+    /// Which node labels to draw: active nodes first, then larger nodes, each
+    /// kept only if its box clears every label already placed
+    fn resolve_label_collisions<T: DrawTarget + ?Sized>(&self, ctx: &T, show_label: bool, font_size: f64) -> Vec<bool> {
+        let mut visible = vec![false; self.nodes.len()];
+        let mut candidates: Vec<usize> = (0..self.nodes.len())
+            .filter(|&i| show_label || self.hovered_node == Some(i))
+            .collect();
+        candidates.sort_by(|&a, &b| {
+            let rank = |i: usize| (self.hovered_node != Some(i), !self.selected_nodes.contains(&i));
+            rank(a).cmp(&rank(b))
+                .then(self.nodes[b].size.partial_cmp(&self.nodes[a].size).unwrap_or(std::cmp::Ordering::Equal))
+        });
+
+        let mut placed: Vec<(f64, f64, f64, f64)> = Vec::new();
+        for i in candidates {
+            let node = &self.nodes[i];
+            let width = ctx.measure_text_width(&self.node_label(ctx, node)).unwrap_or(0.0);
+            let (x, y) = (node.x - width / 2.0, node.y + node.size + 5.0);
+            let overlaps = placed.iter().any(|&(px, py, pw, ph)| {
+                x < px + pw && px < x + width && y < py + ph && py < y + font_size
+            });
+
+            if !overlaps || self.is_active_node(i) {
+                visible[i] = true;
+                placed.push((x, y, width, font_size));
+            }
+        }
+
+        visible
+    }
+
+    fn draw_nodes<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        let progress = if self.show_progress_ring { self.node_progress() } else { Vec::new() };
+
+        // Selected before hovered so the node under the cursor ends up topmost
+        let mut order = self.draw_order(self.nodes.len(), |i| self.is_active_node(i));
+        if self.raise_active {
+            if let Some(h) = self.hovered_node {
+                if let Some(pos) = order.iter().position(|&i| i == h) {
+                    order.remove(pos);
+                    order.push(h);
+                }
+            }
+        }
+
+        let label_font_size = self.config.axis_size() / self.zoom;
+        ctx.set_font(&self.config.font("", label_font_size));
+        let show_label = self.zoom > 0.7 && self.quality_level < QUALITY_NO_LABELS;
+        let visible_labels = if self.label_collision {
+            Some(self.resolve_label_collisions(ctx, show_label, label_font_size))
+        } else {
+            None
+        };
+
+        let stroke_color = canvas_color(self.node_stroke_color.as_deref().unwrap_or(&self.config.theme.background));
+        let draw_stroke = self.node_stroke && self.node_stroke_width > 0.0;
+
+        for i in order {
+            let node = &self.nodes[i];
             let is_hovered = self.hovered_node == Some(i);
             let is_selected = self.selected_nodes.contains(&i);
+            let on_path = self.path_nodes.contains(&i);
+            let hover_scale = if is_hovered && self.hover_effect { self.hover_grow } else { 1.0 };
+
+            if let Some(&(completed, total)) = progress.get(i) {
+                // Outside the selection outline so the two never overlap
+                let extent = match node.node_type {
+                    NodeType::Assessor => node.size * hover_scale * std::f64::consts::SQRT_2,
+                    NodeType::Application => node.size * hover_scale,
+                };
+                self.draw_progress_ring(ctx, node.x, node.y, extent + 7.0, completed, total)?;
+            }
 
             // Node shape based on type
             match node.node_type {
                 NodeType::Assessor => {
                     // Draw square for assessors
-                    let size = node.size * if is_hovered { 1.2 } else { 1.0 };
+                    let size = node.size * hover_scale;
+
+                    ctx.set_fill_style(&node.color);
+                    ctx.fill_rect(node.x - size, node.y - size, size * 2.0, size * 2.0);
+
+                    if draw_stroke {
+                        ctx.set_stroke_style(stroke_color);
+                        ctx.set_line_width(self.node_stroke_width);
+                        ctx.stroke_rect(node.x - size, node.y - size, size * 2.0, size * 2.0);
+                    }
 
-                    if is_selected {
-                        ctx.set_stroke_style(&JsValue::from_str(&self.config.theme.warning));
+                    if is_selected || on_path {
+                        let outline = if is_selected { &self.config.theme.warning } else { &self.config.theme.primary };
+                        ctx.set_stroke_style(outline);
                         ctx.set_line_width(3.0);
                         ctx.stroke_rect(node.x - size - 2.0, node.y - size - 2.0, size * 2.0 + 4.0, size * 2.0 + 4.0);
                     }
-
-                    ctx.set_fill_style(&JsValue::from_str(&node.color));
-                    ctx.fill_rect(node.x - size, node.y - size, size * 2.0, size * 2.0);
                 }
                 NodeType::Application => {
                     // Draw circle for applications
-                    let radius = node.size * if is_hovered { 1.2 } else { 1.0 };
+                    let radius = node.size * hover_scale;
+
+                    ctx.set_fill_style(&node.color);
+                    ctx.begin_path();
+                    ctx.arc(node.x, node.y, radius, 0.0, 2.0 * PI)?;
+                    ctx.fill();
+
+                    if draw_stroke {
+                        ctx.set_stroke_style(stroke_color);
+                        ctx.set_line_width(self.node_stroke_width);
+                        ctx.stroke();
+                    }
 
-                    if is_selected {
-                        ctx.set_stroke_style(&JsValue::from_str(&self.config.theme.warning));
+                    if is_selected || on_path {
+                        let outline = if is_selected { &self.config.theme.warning } else { &self.config.theme.primary };
+                        ctx.set_stroke_style(outline);
                         ctx.set_line_width(3.0);
                         ctx.begin_path();
                         ctx.arc(node.x, node.y, radius + 4.0, 0.0, 2.0 * PI)?;
                         ctx.stroke();
                     }
-
-                    ctx.set_fill_style(&JsValue::from_str(&node.color));
-                    ctx.begin_path();
-                    ctx.arc(node.x, node.y, radius, 0.0, 2.0 * PI)?;
-                    ctx.fill();
                 }
             }
 
             // Draw label if zoomed in enough or hovered
-            if self.zoom > 0.7 || is_hovered {
-                ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
-                ctx.set_font(&format!("{}px {}",
-                    (self.config.font_size - 2.0) / self.zoom,
-                    self.config.font_family
-                ));
+            let visible = visible_labels.as_ref().map_or(show_label || is_hovered, |v| v[i]);
+            if visible {
+                ctx.set_fill_style(&self.config.theme.text);
+                ctx.set_font(&self.config.font("", label_font_size));
                 ctx.set_text_align("center");
+                ctx.set_text_baseline("top");
 
-                let label = if node.label.len() > 15 {
-                    format!("{}...", &node.label[..12])
-                } else {
-                    node.label.clone()
-                };
-
-                ctx.fill_text(&label, node.x, node.y + node.size + 15.0)?;
+                ctx.fill_text(&self.node_label(ctx, node), node.x, node.y + node.size + 5.0)?;
             }
         }
 
+        ctx.set_text_baseline("alphabetic");
         Ok(())
     }
 
-    fn draw_overlay(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
+    fn draw_overlay<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
         // Legend
         if self.config.show_legend {
             let legend_x = 20.0;
             let legend_y = 20.0;
 
-            ctx.set_font(&format!("{}px {}", self.config.font_size - 1.0, self.config.font_family));
+            ctx.set_font(&self.config.font("", self.config.legend_size()));
             ctx.set_text_align("left");
 
+            let line_height = self.config.legend_size() + 3.0;
+
             // Assessor legend
-            ctx.set_fill_style(&JsValue::from_str(&self.config.theme.primary));
-            ctx.fill_rect(legend_x, legend_y - 8.0, 12.0, 12.0);
-            ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
-            ctx.fill_text("Assessor", legend_x + 18.0, legend_y)?;
+            draw_legend_swatch(ctx, NodeType::Assessor.shape(), &self.config.theme.primary, legend_x, legend_y - 8.0, 12.0)?;
+            ctx.set_fill_style(&self.config.theme.text);
+            let lines = wrap_text(ctx, "Assessor", LEGEND_LABEL_WIDTH, 2);
+            for (i, line) in lines.iter().enumerate() {
+                ctx.fill_text(line, legend_x + 18.0, legend_y + i as f64 * line_height)?;
+            }
+            let app_y = legend_y + (lines.len().max(1) - 1) as f64 * line_height;
 
             // Application legend
-            ctx.set_fill_style(&JsValue::from_str(&self.config.theme.secondary));
-            ctx.begin_path();
-            ctx.arc(legend_x + 6.0, legend_y + 18.0, 6.0, 0.0, 2.0 * PI)?;
-            ctx.fill();
-            ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
-            ctx.fill_text("Application", legend_x + 18.0, legend_y + 22.0)?;
+            draw_legend_swatch(ctx, NodeType::Application.shape(), &self.config.theme.secondary, legend_x, app_y + 12.0, 12.0)?;
+            ctx.set_fill_style(&self.config.theme.text);
+            let app_lines = wrap_text(ctx, "Application", LEGEND_LABEL_WIDTH, 2);
+            for (i, line) in app_lines.iter().enumerate() {
+                ctx.fill_text(line, legend_x + 18.0, app_y + 22.0 + i as f64 * line_height)?;
+            }
+
+            let encoding_y = app_y + 22.0 + app_lines.len() as f64 * line_height + 12.0;
+            self.draw_encoding_legend(ctx, legend_x, encoding_y)?;
         }
 
         // Zoom indicator
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.secondary));
-        ctx.set_font(&format!("{}px {}", self.config.font_size - 2.0, self.config.font_family));
+        ctx.set_fill_style(&self.config.theme.secondary);
+        ctx.set_font(&self.config.font("", self.config.axis_size()));
         ctx.set_text_align("right");
         ctx.fill_text(
             &format!("Zoom: {:.0}%", self.zoom * 100.0),
@@ -455,9 +1798,9 @@ impl NetworkGraphChart {
         Ok(())
     }
 
-    fn draw_empty_state(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.secondary));
-        ctx.set_font(&format!("{}px {}", self.config.font_size, self.config.font_family));
+    fn draw_empty_state<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        ctx.set_fill_style(readable_on(&self.config.theme.secondary, &self.config.theme.background));
+        ctx.set_font(&self.config.font("", self.config.label_size()));
         ctx.set_text_align("center");
         ctx.fill_text(
             "No assignment data available",
@@ -467,43 +1810,189 @@ impl NetworkGraphChart {
         Ok(())
     }
 
+    /// Scale wheel zoom speed (1.0 = default); useful to normalize line- vs pixel-mode deltas
+    pub fn set_zoom_sensitivity(&mut self, factor: f64) {
+        self.zoom_sensitivity = factor.max(0.0);
+    }
+
+    /// Flip the wheel direction so scrolling down zooms in
+    pub fn set_invert_zoom(&mut self, inverted: bool) {
+        self.invert_zoom = inverted;
+    }
+
     /// Handle zoom
     pub fn on_zoom(&mut self, delta: f64, center_x: f64, center_y: f64) {
+        self.zoom_by(delta, center_x, center_y);
+        self.refresh();
+    }
+
+    /// Apply a wheel delta, keeping the point under the cursor fixed
+    fn zoom_by(&mut self, delta: f64, center_x: f64, center_y: f64) {
         let old_zoom = self.zoom;
-        self.zoom = (self.zoom * (1.0 - delta * 0.001)).clamp(0.3, 3.0);
+        let direction = if self.invert_zoom { -1.0 } else { 1.0 };
+        self.zoom = (self.zoom * (1.0 - direction * delta * ZOOM_STEP * self.zoom_sensitivity)).clamp(0.3, 3.0);
 
         // Adjust pan to zoom toward cursor
         let zoom_change = self.zoom / old_zoom;
         self.pan_x = center_x - (center_x - self.pan_x) * zoom_change;
         self.pan_y = center_y - (center_y - self.pan_y) * zoom_change;
-
-        self.render().ok();
+        self.clamp_pan();
     }
 
     /// Handle pan
     pub fn on_pan(&mut self, dx: f64, dy: f64) {
         self.pan_x += dx;
         self.pan_y += dy;
-        self.render().ok();
+        self.pan_velocity = (dx, dy);
+        self.clamp_pan();
+        self.refresh();
+    }
+
+    /// Glide after a pan: `friction` is the share of velocity kept each frame (0–0.99)
+    pub fn set_inertia(&mut self, enabled: bool, friction: f64) {
+        self.inertia = enabled;
+        if friction.is_finite() {
+            self.inertia_friction = friction.clamp(0.0, 0.99);
+        }
+        if !enabled {
+            self.pan_velocity = (0.0, 0.0);
+        }
+    }
+
+    /// Advance pan inertia by one frame (call from requestAnimationFrame after the
+    /// pan ends). Returns whether more frames are needed.
+    pub fn step_inertia(&mut self) -> bool {
+        let (vx, vy) = self.pan_velocity;
+        if !self.inertia || (vx * vx + vy * vy).sqrt() < INERTIA_MIN_SPEED {
+            self.pan_velocity = (0.0, 0.0);
+            return false;
+        }
+
+        self.pan_x += vx;
+        self.pan_y += vy;
+        self.pan_velocity = (vx * self.inertia_friction, vy * self.inertia_friction);
+        self.clamp_pan();
+        self.refresh();
+        true
+    }
+
+    /// Start a touch gesture from an array of `{id, x, y}` touches
+    pub fn on_touch_start(&mut self, touches_js: JsValue) -> Result<(), JsValue> {
+        self.touches = serde_wasm_bindgen::from_value(touches_js)?;
+        self.pan_velocity = (0.0, 0.0);
+        Ok(())
+    }
+
+    /// One finger pans; two fingers pinch-zoom around their midpoint and pan with it.
+    /// A change in finger count only resets the baseline.
+    pub fn on_touch_move(&mut self, touches_js: JsValue) -> Result<(), JsValue> {
+        let touches: Vec<TouchPoint> = serde_wasm_bindgen::from_value(touches_js)?;
+        let same_fingers = touches.len() == self.touches.len()
+            && touches.iter().zip(&self.touches).all(|(a, b)| a.id == b.id);
+
+        if same_fingers {
+            match (self.touches.as_slice(), touches.as_slice()) {
+                ([prev], [next]) => self.on_pan(next.x - prev.x, next.y - prev.y),
+                ([p0, p1], [n0, n1]) => {
+                    self.pan_velocity = (0.0, 0.0);
+                    let (scale, (prev_x, prev_y), (mid_x, mid_y)) = pinch_delta((p0, p1), (n0, n1));
+                    let old_zoom = self.zoom;
+                    self.zoom = (self.zoom * scale).clamp(0.3, 3.0);
+
+                    // The graph point under the old midpoint follows the fingers
+                    let zoom_change = self.zoom / old_zoom;
+                    self.pan_x = mid_x - (prev_x - self.pan_x) * zoom_change;
+                    self.pan_y = mid_y - (prev_y - self.pan_y) * zoom_change;
+                    self.clamp_pan();
+                    self.refresh();
+                }
+                _ => {}
+            }
+        }
+
+        self.touches = touches;
+        Ok(())
+    }
+
+    /// End the touch gesture; remaining fingers re-baseline on their next move
+    pub fn on_touch_end(&mut self) {
+        self.touches.clear();
+    }
+
+    /// Stop pan and zoom from moving the graph entirely off screen (default on);
+    /// disable for free panning
+    pub fn set_pan_bounds_enabled(&mut self, enabled: bool) {
+        self.pan_bounds = enabled;
+        if enabled {
+            self.clamp_pan();
+            self.refresh();
+        }
+    }
+
+    /// Keep at least `PAN_MIN_VISIBLE` px of the node extent inside the canvas on each axis
+    fn clamp_pan(&mut self) {
+        if !self.pan_bounds || self.nodes.is_empty() {
+            return;
+        }
+
+        let min_x = self.nodes.iter().map(|n| n.x).fold(f64::INFINITY, f64::min);
+        let max_x = self.nodes.iter().map(|n| n.x).fold(f64::NEG_INFINITY, f64::max);
+        let min_y = self.nodes.iter().map(|n| n.y).fold(f64::INFINITY, f64::min);
+        let max_y = self.nodes.iter().map(|n| n.y).fold(f64::NEG_INFINITY, f64::max);
+
+        let bound = |pan: f64, min: f64, max: f64, extent: f64| {
+            let lower = PAN_MIN_VISIBLE - max * self.zoom;
+            let upper = extent - PAN_MIN_VISIBLE - min * self.zoom;
+            if lower <= upper { pan.clamp(lower, upper) } else { pan }
+        };
+        self.pan_x = bound(self.pan_x, min_x, max_x, self.config.width);
+        self.pan_y = bound(self.pan_y, min_y, max_y, self.config.height);
+    }
+
+    /// Screen (canvas) coordinates to graph space, undoing pan and zoom
+    fn to_graph(&self, x: f64, y: f64) -> (f64, f64) {
+        ((x - self.pan_x) / self.zoom, (y - self.pan_y) / self.zoom)
+    }
+
+    /// Convert canvas coordinates to graph-space `{x, y}` under the current pan/zoom
+    pub fn screen_to_data(&self, x: f64, y: f64) -> JsValue {
+        let (gx, gy) = self.to_graph(x, y);
+        serde_wasm_bindgen::to_value(&serde_json::json!({ "x": gx, "y": gy })).unwrap()
+    }
+
+    /// Graph space to screen (canvas) coordinates, applying pan and zoom
+    fn to_screen(&self, gx: f64, gy: f64) -> (f64, f64) {
+        (gx * self.zoom + self.pan_x, gy * self.zoom + self.pan_y)
+    }
+
+    /// Convert graph-space coordinates to canvas `{x, y}`; the inverse of `screen_to_data`
+    pub fn data_to_screen(&self, x: f64, y: f64) -> JsValue {
+        let (sx, sy) = self.to_screen(x, y);
+        serde_wasm_bindgen::to_value(&serde_json::json!({ "x": sx, "y": sy })).unwrap()
+    }
+
+    /// First node whose hit radius (1.5× its size) contains a graph-space point
+    fn node_at(&self, tx: f64, ty: f64) -> Option<usize> {
+        self.nodes.iter().position(|node| {
+            let dx = tx - node.x;
+            let dy = ty - node.y;
+            (dx * dx + dy * dy).sqrt() < node.size * 1.5
+        })
     }
 
     /// Handle mouse down
     pub fn on_mouse_down(&mut self, x: f64, y: f64) -> bool {
+        // A new press stops any glide
+        self.pan_velocity = (0.0, 0.0);
+
         // Transform coordinates
-        let tx = (x - self.pan_x) / self.zoom;
-        let ty = (y - self.pan_y) / self.zoom;
+        let (tx, ty) = self.to_graph(x, y);
 
         // Check if clicking on a node
-        for (i, node) in self.nodes.iter().enumerate() {
-            let dx = tx - node.x;
-            let dy = ty - node.y;
-            let dist = (dx * dx + dy * dy).sqrt();
-
-            if dist < node.size * 1.5 {
-                self.dragging_node = Some(i);
-                self.nodes[i].fixed = true;
-                return true;
-            }
+        if let Some(i) = self.node_at(tx, ty) {
+            self.dragging_node = Some(i);
+            self.nodes[i].fixed = true;
+            return true;
         }
 
         false
@@ -513,6 +2002,10 @@ impl NetworkGraphChart {
     pub fn on_mouse_up(&mut self) {
         if let Some(idx) = self.dragging_node {
             self.nodes[idx].fixed = false;
+            if self.frozen_by_fit {
+                self.frozen_by_fit = false;
+                self.simulation_running = true;
+            }
         }
         self.dragging_node = None;
     }
@@ -520,101 +2013,457 @@ impl NetworkGraphChart {
     /// Handle mouse move
     pub fn on_mouse_move(&mut self, x: f64, y: f64) -> JsValue {
         // Transform coordinates
-        let tx = (x - self.pan_x) / self.zoom;
-        let ty = (y - self.pan_y) / self.zoom;
+        let (tx, ty) = self.to_graph(x, y);
 
         // Handle dragging
         if let Some(idx) = self.dragging_node {
             self.nodes[idx].x = tx;
             self.nodes[idx].y = ty;
-            self.render().ok();
-            return serde_wasm_bindgen::to_value(&HitTestResult::miss()).unwrap();
+            self.refresh();
+            return HitTestResult::miss_value();
         }
 
         // Check hover
         let old_hovered = self.hovered_node;
 
-        for (i, node) in self.nodes.iter().enumerate() {
-            let dx = tx - node.x;
-            let dy = ty - node.y;
-            let dist = (dx * dx + dy * dy).sqrt();
-
-            if dist < node.size * 1.5 {
-                self.hovered_node = Some(i);
-
-                if old_hovered != self.hovered_node {
-                    self.render().ok();
-                }
+        if let Some(i) = self.node_at(tx, ty) {
+            self.hovered_node = Some(i);
 
-                let result = HitTestResult::hit(
-                    &node.id,
-                    match node.node_type {
-                        NodeType::Assessor => "assessor",
-                        NodeType::Application => "application",
-                    },
-                    serde_json::json!({
-                        "id": node.id,
-                        "label": node.label,
-                        "type": match node.node_type {
-                            NodeType::Assessor => "assessor",
-                            NodeType::Application => "application",
-                        },
-                        "metadata": node.metadata,
-                        "connections": self.edges.iter()
-                            .filter(|e| e.source == node.id || e.target == node.id)
-                            .count()
-                    }),
-                );
-                return serde_wasm_bindgen::to_value(&result).unwrap();
+            if old_hovered != self.hovered_node {
+                self.refresh();
             }
+
+            let result = self.node_hit(i, None);
+            return serde_wasm_bindgen::to_value(&result).unwrap();
         }
 
         self.hovered_node = None;
         if old_hovered.is_some() {
-            self.render().ok();
+            self.refresh();
         }
 
-        serde_wasm_bindgen::to_value(&HitTestResult::miss()).unwrap()
+        HitTestResult::miss_value()
     }
 
-    /// Handle click for selection
-    pub fn on_click(&mut self, x: f64, y: f64, multi_select: bool) -> JsValue {
-        let tx = (x - self.pan_x) / self.zoom;
-        let ty = (y - self.pan_y) / self.zoom;
-
-        for (i, node) in self.nodes.iter().enumerate() {
-            let dx = tx - node.x;
-            let dy = ty - node.y;
-            let dist = (dx * dx + dy * dy).sqrt();
-
-            if dist < node.size * 1.5 {
-                if multi_select {
-                    if let Some(pos) = self.selected_nodes.iter().position(|&idx| idx == i) {
-                        self.selected_nodes.remove(pos);
-                    } else {
-                        self.selected_nodes.push(i);
-                    }
+    /// All nodes and edges under the cursor, nearest first. Does not change hover state.
+    pub fn hit_test_all(&self, x: f64, y: f64) -> JsValue {
+        let results: Vec<HitTestResult> = self.hits_at(x, y).into_iter()
+            .map(|(dist, i, is_node)| {
+                if is_node {
+                    self.node_hit(i, Some(dist))
                 } else {
-                    self.selected_nodes = vec![i];
+                    let edge = &self.edges[i];
+                    HitTestResult::hit(
+                        &format!("{}->{}", edge.source, edge.target),
+                        "edge",
+                        serde_json::json!({
+                            "source": edge.source,
+                            "target": edge.target,
+                            "status": edge.status,
+                            "weight": edge.weight,
+                            "label": edge.label,
+                            "distance": dist
+                        }),
+                    )
                 }
+            })
+            .collect();
+
+        serde_wasm_bindgen::to_value(&results).unwrap()
+    }
 
-                self.render().ok();
+    /// `(distance, index, is_node)` for every node and edge under the cursor,
+    /// nearest first. Edges are measured against the curve as drawn.
+    fn hits_at(&self, x: f64, y: f64) -> Vec<(f64, usize, bool)> {
+        let (tx, ty) = self.to_graph(x, y);
+        let edge_tolerance = EDGE_HIT_TOLERANCE / self.zoom;
+        let curve = self.edge_curve();
 
-                return serde_wasm_bindgen::to_value(&serde_json::json!({
-                    "selected": self.selected_nodes.iter().map(|&idx| &self.nodes[idx].id).collect::<Vec<_>>()
-                })).unwrap();
+        let mut hits: Vec<(f64, usize, bool)> = Vec::new();
+
+        for (i, node) in self.nodes.iter().enumerate() {
+            let dist = ((tx - node.x).powi(2) + (ty - node.y).powi(2)).sqrt();
+            if dist < node.size * 1.5 {
+                hits.push((dist, i, true));
             }
         }
 
-        // Click on empty space clears selection
+        let index: HashMap<&str, usize> = self.nodes.iter()
+            .enumerate()
+            .map(|(i, n)| (n.id.as_str(), i))
+            .collect();
+        for (i, edge) in self.edges.iter().enumerate() {
+            if let (Some(&s), Some(&t)) = (index.get(edge.source.as_str()), index.get(edge.target.as_str())) {
+                let (s, t) = (&self.nodes[s], &self.nodes[t]);
+                let dist = if edge.source == edge.target {
+                    let (cx, cy, r) = self_loop_circle(s);
+                    (((tx - cx).powi(2) + (ty - cy).powi(2)).sqrt() - r).abs()
+                } else {
+                    let (dx, dy) = (t.x - s.x, t.y - s.y);
+                    let c = ((s.x + t.x) / 2.0 - dy * curve, (s.y + t.y) / 2.0 + dx * curve);
+                    point_curve_distance(tx, ty, (s.x, s.y), c, (t.x, t.y))
+                };
+                if dist < edge_tolerance {
+                    hits.push((dist, i, false));
+                }
+            }
+        }
+
+        hits.sort_by(|a, b| a.0.total_cmp(&b.0));
+        hits
+    }
+
+    fn node_hit(&self, idx: usize, distance: Option<f64>) -> HitTestResult {
+        let node = &self.nodes[idx];
+        let node_type = match node.node_type {
+            NodeType::Assessor => "assessor",
+            NodeType::Application => "application",
+        };
+
+        let mut data = serde_json::json!({
+            "id": node.id,
+            "label": node.label,
+            "type": node_type,
+            "metadata": node.metadata,
+            "connections": self.edges.iter()
+                .filter(|e| e.source == node.id || e.target == node.id)
+                .count(),
+            "conflicts": self.conflicts.iter()
+                .filter_map(|c| {
+                    if c.assessor_id == node.id {
+                        Some(&c.application_id)
+                    } else if c.application_id == node.id {
+                        Some(&c.assessor_id)
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        });
+        if let Some(distance) = distance {
+            data["distance"] = serde_json::json!(distance);
+        }
+
+        HitTestResult::hit(&node.id, node_type, data)
+    }
+
+    /// Handle click for selection
+    pub fn on_click(&mut self, x: f64, y: f64, multi_select: bool) -> JsValue {
+        let (tx, ty) = self.to_graph(x, y);
+
+        if let Some(i) = self.node_at(tx, ty) {
+            if multi_select {
+                if let Some(pos) = self.selected_nodes.iter().position(|&idx| idx == i) {
+                    self.selected_nodes.remove(pos);
+                } else {
+                    self.selected_nodes.push(i);
+                }
+            } else {
+                self.selected_nodes = vec![i];
+            }
+
+            self.enforce_selection_limit();
+            self.refresh();
+
+            return self.selection_value();
+        }
+
+        // Click on empty space clears selection
         if !multi_select {
             self.selected_nodes.clear();
-            self.render().ok();
+            self.refresh();
         }
 
         serde_wasm_bindgen::to_value(&serde_json::json!({ "selected": [] })).unwrap()
     }
 
+    /// Cap the number of selected nodes (None = unlimited); oldest selections are dropped first
+    pub fn set_max_selection(&mut self, n: Option<usize>) -> JsValue {
+        self.max_selection = n;
+        self.enforce_selection_limit();
+        self.refresh();
+        self.selection_value()
+    }
+
+    /// Select all nodes, or only those of a type ("assessor" / "application")
+    pub fn select_all(&mut self, node_type: Option<String>) -> Result<JsValue, JsValue> {
+        let filter = match node_type.as_deref() {
+            None => None,
+            Some("assessor") => Some(NodeType::Assessor),
+            Some("application") => Some(NodeType::Application),
+            Some(other) => return Err(JsValue::from_str(&format!("Unknown node type: {}", other))),
+        };
+
+        self.select_all_of(filter);
+        self.refresh();
+        Ok(self.selection_value())
+    }
+
+    /// Select every node matching `filter` (all nodes when `None`), capped at the
+    /// selection limit with the earliest dropped first
+    fn select_all_of(&mut self, filter: Option<NodeType>) {
+        self.selected_nodes = self.nodes.iter()
+            .enumerate()
+            .filter(|(_, n)| filter.as_ref().is_none_or(|t| n.node_type == *t))
+            .map(|(i, _)| i)
+            .collect();
+
+        self.enforce_selection_limit();
+    }
+
+    /// Highlight the shortest path between two nodes (edges treated as undirected).
+    /// Returns the ordered node ids, or a miss when the nodes are not connected.
+    pub fn highlight_path(&mut self, from_id: &str, to_id: &str) -> JsValue {
+        self.path_nodes.clear();
+        self.path_edges.clear();
+
+        let from = self.nodes.iter().position(|n| n.id == from_id);
+        let to = self.nodes.iter().position(|n| n.id == to_id);
+
+        let path = match (from, to) {
+            (Some(from), Some(to)) => self.shortest_path(from, to),
+            _ => None,
+        };
+
+        let result = match path {
+            Some((nodes, edges)) => {
+                self.path_nodes = nodes;
+                self.path_edges = edges;
+                let ids: Vec<&str> = self.path_nodes.iter().map(|&i| self.nodes[i].id.as_str()).collect();
+                HitTestResult::hit(
+                    &format!("{}-{}", from_id, to_id),
+                    "path",
+                    serde_json::json!({
+                        "nodes": ids,
+                        "length": self.path_edges.len()
+                    }),
+                )
+            }
+            None => HitTestResult::miss(),
+        };
+
+        self.refresh();
+        serde_wasm_bindgen::to_value(&result).unwrap()
+    }
+
+    /// Remove any path highlight
+    pub fn clear_path_highlight(&mut self) {
+        self.path_nodes.clear();
+        self.path_edges.clear();
+        self.refresh();
+    }
+
+    /// Connected components, largest first, as `{ components: [{ index, nodes, size }], isolated }`.
+    /// Nodes with no edges are listed in `isolated` and also appear as size-1 components.
+    pub fn get_components(&self) -> JsValue {
+        let adjacency = self.adjacency();
+        let components: Vec<serde_json::Value> = self.components().iter()
+            .enumerate()
+            .map(|(index, members)| serde_json::json!({
+                "index": index,
+                "nodes": members.iter().map(|&i| self.nodes[i].id.as_str()).collect::<Vec<_>>(),
+                "size": members.len()
+            }))
+            .collect();
+        let isolated: Vec<&str> = self.nodes.iter()
+            .zip(&adjacency)
+            .filter(|(_, neighbors)| neighbors.is_empty())
+            .map(|(n, _)| n.id.as_str())
+            .collect();
+
+        serde_wasm_bindgen::to_value(&serde_json::json!({
+            "components": components,
+            "isolated": isolated
+        })).unwrap()
+    }
+
+    /// Highlight one component from `get_components` using the path styling.
+    /// Cleared by `clear_path_highlight`; an out-of-range index returns a miss.
+    pub fn highlight_component(&mut self, index: usize) -> JsValue {
+        self.path_nodes.clear();
+        self.path_edges.clear();
+
+        let result = match self.components().into_iter().nth(index) {
+            Some(members) => {
+                let member_set: HashSet<usize> = members.iter().copied().collect();
+                self.path_edges = self.adjacency().iter()
+                    .enumerate()
+                    .filter(|(i, _)| member_set.contains(i))
+                    .flat_map(|(_, neighbors)| neighbors.iter().map(|&(_, e)| e))
+                    .collect::<HashSet<usize>>()
+                    .into_iter()
+                    .collect();
+                self.path_nodes = members;
+                let ids: Vec<&str> = self.path_nodes.iter().map(|&i| self.nodes[i].id.as_str()).collect();
+                HitTestResult::hit(
+                    &format!("component-{}", index),
+                    "component",
+                    serde_json::json!({
+                        "nodes": ids,
+                        "edgeCount": self.path_edges.len()
+                    }),
+                )
+            }
+            None => HitTestResult::miss(),
+        };
+
+        self.refresh();
+        serde_wasm_bindgen::to_value(&result).unwrap()
+    }
+
+    /// Node indices per connected component (BFS, edges undirected), largest first
+    fn components(&self) -> Vec<Vec<usize>> {
+        let adjacency = self.adjacency();
+        let mut visited = vec![false; self.nodes.len()];
+        let mut components = Vec::new();
+
+        for start in 0..self.nodes.len() {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            let mut members = vec![start];
+            let mut queue = std::collections::VecDeque::from([start]);
+            while let Some(current) = queue.pop_front() {
+                for &(neighbor, _) in &adjacency[current] {
+                    if !visited[neighbor] {
+                        visited[neighbor] = true;
+                        members.push(neighbor);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+            components.push(members);
+        }
+
+        // Stable, so equal-sized components keep node order
+        components.sort_by_key(|c| std::cmp::Reverse(c.len()));
+        components
+    }
+
+    /// Neighbor lists as (node index, edge index), ignoring edges with missing endpoints
+    fn adjacency(&self) -> Vec<Vec<(usize, usize)>> {
+        let index: HashMap<&str, usize> = self.nodes.iter()
+            .enumerate()
+            .map(|(i, n)| (n.id.as_str(), i))
+            .collect();
+        let mut adjacency = vec![Vec::new(); self.nodes.len()];
+
+        for (e, edge) in self.edges.iter().enumerate() {
+            if let (Some(&s), Some(&t)) = (index.get(edge.source.as_str()), index.get(edge.target.as_str())) {
+                adjacency[s].push((t, e));
+                adjacency[t].push((s, e));
+            }
+        }
+
+        adjacency
+    }
+
+    /// BFS shortest path as (node indices, edge indices), from first to last
+    fn shortest_path(&self, from: usize, to: usize) -> Option<(Vec<usize>, Vec<usize>)> {
+        let adjacency = self.adjacency();
+        let mut previous: Vec<Option<(usize, usize)>> = vec![None; self.nodes.len()];
+        let mut visited = vec![false; self.nodes.len()];
+        let mut queue = std::collections::VecDeque::new();
+
+        visited[from] = true;
+        queue.push_back(from);
+
+        while let Some(current) = queue.pop_front() {
+            if current == to {
+                break;
+            }
+            for &(next, edge) in &adjacency[current] {
+                if !visited[next] {
+                    visited[next] = true;
+                    previous[next] = Some((current, edge));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if !visited[to] {
+            return None;
+        }
+
+        let mut nodes = vec![to];
+        let mut edges = Vec::new();
+        let mut current = to;
+        while let Some((prev, edge)) = previous[current] {
+            nodes.push(prev);
+            edges.push(edge);
+            current = prev;
+        }
+        nodes.reverse();
+        edges.reverse();
+
+        Some((nodes, edges))
+    }
+
+    fn enforce_selection_limit(&mut self) {
+        if let Some(max) = self.max_selection {
+            let excess = self.selected_nodes.len().saturating_sub(max);
+            self.selected_nodes.drain(..excess);
+        }
+    }
+
+    fn selection_value(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&serde_json::json!({
+            "selected": self.selected_nodes.iter().map(|&idx| &self.nodes[idx].id).collect::<Vec<_>>()
+        })).unwrap()
+    }
+
+    /// Assessor workload: assigned applications per assessor, mean and stddev,
+    /// and the assessors more than one stddev above (`overloaded`) or below (`underloaded`)
+    pub fn get_load_balance(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.load_balance()).unwrap()
+    }
+
+    /// Report behind `get_load_balance`
+    fn load_balance(&self) -> serde_json::Value {
+        let index: HashMap<&str, usize> = self.nodes.iter()
+            .enumerate()
+            .map(|(i, n)| (n.id.as_str(), i))
+            .collect();
+        let mut assigned = vec![0u32; self.nodes.len()];
+
+        for edge in &self.edges {
+            if let (Some(&s), Some(&t)) = (index.get(edge.source.as_str()), index.get(edge.target.as_str())) {
+                match (&self.nodes[s].node_type, &self.nodes[t].node_type) {
+                    (NodeType::Assessor, NodeType::Application) => assigned[s] += 1,
+                    (NodeType::Application, NodeType::Assessor) => assigned[t] += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        let assessors: Vec<usize> = (0..self.nodes.len())
+            .filter(|&i| self.nodes[i].node_type == NodeType::Assessor)
+            .collect();
+        let n = assessors.len().max(1) as f64;
+        let mean = assessors.iter().map(|&i| assigned[i] as f64).sum::<f64>() / n;
+        let stddev = (assessors.iter().map(|&i| (assigned[i] as f64 - mean).powi(2)).sum::<f64>() / n).sqrt();
+
+        let ids_where = |keep: &dyn Fn(f64) -> bool| -> Vec<&str> {
+            assessors.iter()
+                .filter(|&&i| keep(assigned[i] as f64))
+                .map(|&i| self.nodes[i].id.as_str())
+                .collect()
+        };
+
+        serde_json::json!({
+            "assessors": assessors.iter().map(|&i| serde_json::json!({
+                "id": self.nodes[i].id,
+                "label": self.nodes[i].label,
+                "assigned": assigned[i]
+            })).collect::<Vec<_>>(),
+            "mean": mean,
+            "stddev": stddev,
+            "overloaded": ids_where(&|c| c > mean + stddev),
+            "underloaded": ids_where(&|c| c < mean - stddev)
+        })
+    }
+
     /// Get statistics
     pub fn get_stats(&self) -> JsValue {
         let assessor_count = self.nodes.iter().filter(|n| n.node_type == NodeType::Assessor).count();
@@ -623,22 +2472,159 @@ impl NetworkGraphChart {
         let stats = serde_json::json!({
             "nodeCount": self.nodes.len(),
             "edgeCount": self.edges.len(),
+            "droppedEdges": self.dropped_edges,
             "assessorCount": assessor_count,
             "applicationCount": app_count,
             "selectedCount": self.selected_nodes.len(),
             "zoom": self.zoom,
-            "simulationRunning": self.simulation_running
+            "simulationRunning": self.simulation_running,
+            "qualityLevel": self.quality_level,
+            "lastFrameMs": self.last_frame_ms
         });
         serde_wasm_bindgen::to_value(&stats).unwrap()
     }
 
+    /// One-sentence summary for screen readers (e.g. a canvas `aria-label`)
+    pub fn describe(&self) -> String {
+        let assessor_count = self.nodes.iter().filter(|n| n.node_type == NodeType::Assessor).count();
+        let app_count = self.nodes.len() - assessor_count;
+
+        let mut text = format!(
+            "Assignment network of {} assessors and {} applications with {} assignments",
+            assessor_count,
+            app_count,
+            self.edges.len()
+        );
+        if !self.conflicts.is_empty() {
+            text.push_str(&format!("; {} conflicts of interest", self.conflicts.len()));
+        }
+        if !self.selected_nodes.is_empty() {
+            text.push_str(&format!("; {} selected", self.selected_nodes.len()));
+        }
+        text
+    }
+
+    /// Get aggregate edge metrics for the current selection
+    pub fn get_selection_summary(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.selection_summary()).unwrap()
+    }
+
+    /// Summary behind `get_selection_summary`
+    fn selection_summary(&self) -> serde_json::Value {
+        let selected_ids: Vec<&str> = self.selected_nodes.iter()
+            .map(|&idx| self.nodes[idx].id.as_str())
+            .collect();
+
+        let mut pending = 0;
+        let mut in_progress = 0;
+        let mut completed = 0;
+
+        let incident: Vec<&NetworkEdge> = self.edges.iter()
+            .filter(|e| selected_ids.contains(&e.source.as_str()) || selected_ids.contains(&e.target.as_str()))
+            .collect();
+
+        for edge in &incident {
+            match edge.status.as_deref() {
+                Some("completed") => completed += 1,
+                Some("in_progress") => in_progress += 1,
+                _ => pending += 1,
+            }
+        }
+
+        let assessors: Vec<_> = self.selected_nodes.iter()
+            .map(|&idx| &self.nodes[idx])
+            .filter(|n| n.node_type == NodeType::Assessor)
+            .map(|n| {
+                let assigned: Vec<&&NetworkEdge> = incident.iter()
+                    .filter(|e| e.source == n.id || e.target == n.id)
+                    .collect();
+                let done = assigned.iter()
+                    .filter(|e| e.status.as_deref() == Some("completed"))
+                    .count();
+
+                serde_json::json!({
+                    "id": n.id,
+                    "label": n.label,
+                    "assignedApplications": assigned.len(),
+                    "completed": done,
+                    "completionRate": if assigned.is_empty() {
+                        0.0
+                    } else {
+                        done as f64 / assigned.len() as f64
+                    }
+                })
+            })
+            .collect();
+
+        serde_json::json!({
+            "selected": selected_ids,
+            "incidentEdges": incident.len(),
+            "statusBreakdown": {
+                "pending": pending,
+                "in_progress": in_progress,
+                "completed": completed
+            },
+            "assessors": assessors
+        })
+    }
+
     /// Reset view to default
     pub fn reset_view(&mut self) {
+        self.diverged = false;
         self.zoom = 1.0;
         self.pan_x = 0.0;
         self.pan_y = 0.0;
         self.selected_nodes.clear();
-        self.render().ok();
+        self.refresh();
+    }
+
+    /// Zoom and pan so the given nodes fill the viewport with a 50px margin,
+    /// zooming in no further than `max_zoom`
+    fn frame_nodes(&mut self, indices: &[usize], max_zoom: f64) {
+        let nodes = indices.iter().map(|&i| &self.nodes[i]);
+        let min_x = nodes.clone().map(|n| n.x).fold(f64::INFINITY, f64::min);
+        let max_x = nodes.clone().map(|n| n.x).fold(f64::NEG_INFINITY, f64::max);
+        let min_y = nodes.clone().map(|n| n.y).fold(f64::INFINITY, f64::min);
+        let max_y = nodes.map(|n| n.y).fold(f64::NEG_INFINITY, f64::max);
+
+        let content_width = max_x - min_x + 100.0;
+        let content_height = max_y - min_y + 100.0;
+
+        self.zoom = ((self.config.width / content_width).min(self.config.height / content_height) * 0.9).clamp(0.3, max_zoom);
+
+        self.pan_x = (self.config.width - content_width * self.zoom) / 2.0 - min_x * self.zoom + 50.0;
+        self.pan_y = (self.config.height - content_height * self.zoom) / 2.0 - min_y * self.zoom + 50.0;
+    }
+
+    /// Zoom to the double-clicked node and its direct neighbors; a miss fits the whole graph
+    pub fn on_double_click(&mut self, x: f64, y: f64) {
+        let (tx, ty) = self.to_graph(x, y);
+        let Some(i) = self.node_at(tx, ty) else {
+            self.fit_to_content();
+            return;
+        };
+
+        let id = self.nodes[i].id.as_str();
+        let neighbor_ids: HashSet<&str> = self.edges.iter()
+            .filter_map(|e| {
+                if e.source == id {
+                    Some(e.target.as_str())
+                } else if e.target == id {
+                    Some(e.source.as_str())
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let mut indices: Vec<usize> = self.nodes.iter()
+            .enumerate()
+            .filter(|(_, n)| neighbor_ids.contains(n.id.as_str()))
+            .map(|(j, _)| j)
+            .collect();
+        indices.push(i);
+
+        self.frame_nodes(&indices, 3.0);
+        self.refresh();
     }
 
     /// Fit view to content
@@ -647,33 +2633,927 @@ impl NetworkGraphChart {
             return;
         }
 
-        let min_x = self.nodes.iter().map(|n| n.x).fold(f64::INFINITY, f64::min);
-        let max_x = self.nodes.iter().map(|n| n.x).fold(f64::NEG_INFINITY, f64::max);
-        let min_y = self.nodes.iter().map(|n| n.y).fold(f64::INFINITY, f64::min);
-        let max_y = self.nodes.iter().map(|n| n.y).fold(f64::NEG_INFINITY, f64::max);
+        self.fit_layout();
+        self.refresh();
+    }
 
-        let content_width = max_x - min_x + 100.0;
-        let content_height = max_y - min_y + 100.0;
+    fn fit_layout(&mut self) {
+        let all: Vec<usize> = (0..self.nodes.len()).collect();
+        self.frame_nodes(&all, 2.0);
+
+        if self.freeze_on_fit && self.simulation_running {
+            self.simulation_running = false;
+            self.frozen_by_fit = true;
+        }
+    }
 
-        self.zoom = ((self.config.width / content_width).min(self.config.height / content_height) * 0.9).clamp(0.3, 2.0);
+    /// Stop the simulation whenever `fit_to_content` runs so the framed layout
+    /// stays put; dragging a node resumes a simulation the fit stopped
+    pub fn freeze_after_fit(&mut self, enabled: bool) {
+        self.freeze_on_fit = enabled;
+    }
+}
 
-        self.pan_x = (self.config.width - content_width * self.zoom) / 2.0 - min_x * self.zoom + 50.0;
-        self.pan_y = (self.config.height - content_height * self.zoom) / 2.0 - min_y * self.zoom + 50.0;
+/// Distance from a point to the segment (x1, y1)-(x2, y2)
+fn point_segment_distance(px: f64, py: f64, x1: f64, y1: f64, x2: f64, y2: f64) -> f64 {
+    let (dx, dy) = (x2 - x1, y2 - y1);
+    let len_sq = dx * dx + dy * dy;
+    let t = if len_sq > 0.0 {
+        (((px - x1) * dx + (py - y1) * dy) / len_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let (cx, cy) = (x1 + t * dx, y1 + t * dy);
+    ((px - cx).powi(2) + (py - cy).powi(2)).sqrt()
+}
+
+/// Pinch from one pair of touches to the next: (scale, previous midpoint, new midpoint).
+/// Scale is 1.0 when the previous fingers were on the same point.
+fn pinch_delta(prev: (&TouchPoint, &TouchPoint), next: (&TouchPoint, &TouchPoint)) -> (f64, (f64, f64), (f64, f64)) {
+    let distance = |a: &TouchPoint, b: &TouchPoint| ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt();
+    let midpoint = |a: &TouchPoint, b: &TouchPoint| ((a.x + b.x) / 2.0, (a.y + b.y) / 2.0);
 
-        self.render().ok();
+    let prev_distance = distance(prev.0, prev.1);
+    let scale = if prev_distance > 0.0 { distance(next.0, next.1) / prev_distance } else { 1.0 };
+    (scale, midpoint(prev.0, prev.1), midpoint(next.0, next.1))
+}
+
+/// Circle (center x, center y, radius) for a self-loop, sitting on top of the node
+fn self_loop_circle(node: &PhysicsNode) -> (f64, f64, f64) {
+    let r = node.size * 0.6;
+    (node.x, node.y - node.size - r * 0.5, r)
+}
+
+/// Point on the quadratic Bézier p0 → p2 with control point c
+fn quad_point(p0: (f64, f64), c: (f64, f64), p2: (f64, f64), t: f64) -> (f64, f64) {
+    let u = 1.0 - t;
+    (
+        u * u * p0.0 + 2.0 * u * t * c.0 + t * t * p2.0,
+        u * u * p0.1 + 2.0 * u * t * c.1 + t * t * p2.1,
+    )
+}
+
+/// Distance from a point to the quadratic Bézier p0 → p2 with control point c,
+/// measured against a polyline through samples of the curve
+fn point_curve_distance(px: f64, py: f64, p0: (f64, f64), c: (f64, f64), p2: (f64, f64)) -> f64 {
+    const SAMPLES: usize = 16;
+    let mut prev = p0;
+    let mut best = f64::INFINITY;
+    for i in 1..=SAMPLES {
+        let next = quad_point(p0, c, p2, i as f64 / SAMPLES as f64);
+        best = best.min(point_segment_distance(px, py, prev.0, prev.1, next.0, next.1));
+        prev = next;
     }
+    best
 }
 
-/// Simple pseudo-random number generator for initial positions
-fn rand_float() -> f64 {
-    use std::cell::RefCell;
-    thread_local! {
-        static SEED: RefCell<u64> = RefCell::new(12345);
+/// Derivative of the quadratic Bézier at t; at t = 1 it points from c to p2
+fn quad_tangent(p0: (f64, f64), c: (f64, f64), p2: (f64, f64), t: f64) -> (f64, f64) {
+    let u = 1.0 - t;
+    (
+        2.0 * u * (c.0 - p0.0) + 2.0 * t * (p2.0 - c.0),
+        2.0 * u * (c.1 - p0.1) + 2.0 * t * (p2.1 - c.1),
+    )
+}
+
+/// Whether a point lies inside a node's drawn shape (square or circle)
+fn node_contains(node: &PhysicsNode, x: f64, y: f64) -> bool {
+    let (dx, dy) = (x - node.x, y - node.y);
+    match node.node_type {
+        NodeType::Assessor => dx.abs() <= node.size && dy.abs() <= node.size,
+        NodeType::Application => dx * dx + dy * dy <= node.size * node.size,
+    }
+}
+
+/// Curve parameter where an edge ending at `target` enters its shape, by bisection
+fn curve_exit_t(p0: (f64, f64), c: (f64, f64), p2: (f64, f64), target: &PhysicsNode) -> f64 {
+    let (mut lo, mut hi) = (0.0, 1.0);
+    for _ in 0..16 {
+        let mid = (lo + hi) / 2.0;
+        let (x, y) = quad_point(p0, c, p2, mid);
+        if node_contains(target, x, y) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
     }
+    lo
+}
+
+/// Numeric metadata value for a key, if present
+fn metadata_number(node: &PhysicsNode, key: &str) -> Option<f64> {
+    node.metadata.as_ref()?.get(key)?.as_f64()
+}
+
+thread_local! {
+    static RAND_SEED: std::cell::RefCell<u64> = const { std::cell::RefCell::new(DETERMINISTIC_SEED) };
+}
+
+/// Seed used at startup and restored by `set_deterministic`
+const DETERMINISTIC_SEED: u64 = 12345;
+
+/// Reset the layout RNG to a known state
+fn seed_rand(seed: u64) {
+    RAND_SEED.with(|s| *s.borrow_mut() = seed);
+}
 
-    SEED.with(|seed| {
+/// Simple pseudo-random number generator for initial positions
+fn rand_float() -> f64 {
+    RAND_SEED.with(|seed| {
         let mut s = seed.borrow_mut();
         *s = s.wrapping_mul(6364136223846793005).wrapping_add(1);
         (*s as f64) / (u64::MAX as f64)
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::charts::RecordingTarget;
+
+    fn node(id: &str) -> NetworkNode {
+        NetworkNode {
+            id: id.to_string(),
+            label: id.to_string(),
+            node_type: if id.starts_with('a') { NodeType::Assessor } else { NodeType::Application },
+            size: None,
+            color: None,
+            metadata: None,
+        }
+    }
+
+    fn edge(source: &str, target: &str) -> NetworkEdge {
+        NetworkEdge {
+            source: source.to_string(),
+            target: target.to_string(),
+            weight: None,
+            color: None,
+            status: None,
+            label: None,
+        }
+    }
+
+    fn graph(ids: &[&str], edges: &[(&str, &str)]) -> NetworkGraphChart {
+        let mut chart = NetworkGraphChart::with_config("test", ChartConfig::default());
+        chart.load_graph(
+            ids.iter().map(|id| node(id)).collect(),
+            edges.iter().map(|(s, t)| edge(s, t)).collect(),
+        );
+        chart
+    }
+
+    fn index_of(chart: &NetworkGraphChart, id: &str) -> usize {
+        chart.nodes.iter().position(|n| n.id == id).unwrap()
+    }
+
+    #[test]
+    fn shortest_path_takes_the_fewest_hops() {
+        let chart = graph(
+            &["a1", "a2", "a3", "x1", "x2", "x3", "x4"],
+            &[("a1", "x1"), ("a2", "x1"), ("a2", "x2"), ("a3", "x2"), ("a1", "x3"), ("a3", "x3")],
+        );
+
+        let (nodes, edges) = chart.shortest_path(index_of(&chart, "a1"), index_of(&chart, "a3")).unwrap();
+        let ids: Vec<&str> = nodes.iter().map(|&i| chart.nodes[i].id.as_str()).collect();
+        assert_eq!(ids, vec!["a1", "x3", "a3"]);
+        assert_eq!(edges, vec![4, 5]);
+
+        assert!(chart.shortest_path(index_of(&chart, "a1"), index_of(&chart, "x4")).is_none());
+    }
+
+    #[test]
+    fn two_cliques_split_into_two_communities() {
+        let left = ["a1", "a2", "x1", "x2"];
+        let right = ["a3", "a4", "x3", "x4"];
+        let mut edges = Vec::new();
+        for clique in [&left, &right] {
+            for (i, s) in clique.iter().enumerate() {
+                for t in &clique[i + 1..] {
+                    edges.push((*s, *t));
+                }
+            }
+        }
+        // One bridge between the cliques
+        edges.push(("x2", "a3"));
+
+        let ids: Vec<&str> = left.iter().chain(&right).copied().collect();
+        let chart = graph(&ids, &edges);
+        let communities = chart.detect_communities();
+
+        let community = |id: &str| communities[index_of(&chart, id)];
+        assert!(left.iter().all(|id| community(id) == community("a1")));
+        assert!(right.iter().all(|id| community(id) == community("a3")));
+        assert_ne!(community("a1"), community("a3"));
+    }
+
+    #[test]
+    fn bezier_tangent_at_endpoints_follows_the_control_legs() {
+        let (p0, c, p2) = ((0.0, 0.0), (50.0, 40.0), (100.0, 0.0));
+
+        // At t = 1 the tangent is 2·(p2 - c); at t = 0 it is 2·(c - p0)
+        assert_eq!(quad_tangent(p0, c, p2, 1.0), (100.0, -80.0));
+        assert_eq!(quad_tangent(p0, c, p2, 0.0), (100.0, 80.0));
+
+        // Matches a finite difference of the curve itself
+        let h = 1e-6;
+        let (x1, y1) = quad_point(p0, c, p2, 1.0 - h);
+        let (x2, y2) = quad_point(p0, c, p2, 1.0);
+        let (tx, ty) = quad_tangent(p0, c, p2, 1.0);
+        assert!(((x2 - x1) / h - tx).abs() < 1e-3);
+        assert!(((y2 - y1) / h - ty).abs() < 1e-3);
+    }
+
+    #[test]
+    fn arrow_tip_sits_on_the_target_outline() {
+        let chart = graph(&["a1", "x1"], &[("a1", "x1")]);
+        let target = &chart.nodes[1];
+        let (p0, p2) = ((target.x - 200.0, target.y), (target.x, target.y));
+        let c = (target.x - 100.0, target.y + 20.0);
+
+        let t = curve_exit_t(p0, c, p2, target);
+        let (x, y) = quad_point(p0, c, p2, t);
+        let distance = ((x - target.x).powi(2) + (y - target.y).powi(2)).sqrt();
+        assert!((distance - target.size).abs() < 0.01, "tip {} from center, radius {}", distance, target.size);
+    }
+
+    fn all_finite(chart: &NetworkGraphChart) -> bool {
+        chart.nodes.iter().all(|n| n.x.is_finite() && n.y.is_finite() && n.vx.is_finite() && n.vy.is_finite())
+    }
+
+    #[test]
+    fn malformed_edges_are_dropped_and_self_loops_stay_finite() {
+        let mut chart = graph(
+            &["a1", "x1", "x2"],
+            &[("a1", "a1"), ("a1", "missing"), ("ghost", "x1"), ("x1", "x2"), ("x2", "x2")],
+        );
+        chart.set_auto_stop(false);
+
+        assert_eq!(chart.edges.len(), 3);
+        assert_eq!(chart.dropped_edges, 2);
+
+        for _ in 0..300 {
+            chart.step_simulation();
+        }
+        assert!(all_finite(&chart));
+        assert!(!chart.has_diverged());
+    }
+
+    #[test]
+    fn set_physics_clamps_extreme_and_non_finite_values() {
+        let mut chart = graph(&["a1"], &[]);
+        chart.set_physics(1e12, 50.0, 5.0);
+        assert_eq!((chart.repulsion_strength, chart.attraction_strength, chart.damping), (100_000.0, 1.0, 0.99));
+
+        chart.set_physics(f64::NAN, f64::INFINITY, -1.0);
+        assert_eq!((chart.repulsion_strength, chart.attraction_strength, chart.damping), (100_000.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn huge_repulsion_keeps_coordinates_finite() {
+        let mut chart = graph(&["a1", "a2", "x1", "x2"], &[("a1", "x1"), ("a2", "x2")]);
+        chart.set_auto_stop(false);
+        chart.set_physics(1e12, 1.0, 0.99);
+        // Beyond what set_physics allows, and every node stacked on one point
+        chart.repulsion_strength = f64::MAX;
+        for node in &mut chart.nodes {
+            node.x = 100.0;
+            node.y = 100.0;
+        }
+
+        for _ in 0..200 {
+            chart.step_simulation();
+            assert!(all_finite(&chart));
+        }
+    }
+
+    fn touch(id: f64, x: f64, y: f64) -> TouchPoint {
+        TouchPoint { id, x, y }
+    }
+
+    #[test]
+    fn pinch_delta_scales_by_finger_spread_around_the_midpoint() {
+        let (p0, p1) = (touch(1.0, 100.0, 100.0), touch(2.0, 200.0, 100.0));
+        let (n0, n1) = (touch(1.0, 50.0, 120.0), touch(2.0, 250.0, 120.0));
+
+        let (scale, prev_mid, next_mid) = pinch_delta((&p0, &p1), (&n0, &n1));
+        assert_eq!(scale, 2.0);
+        assert_eq!(prev_mid, (150.0, 100.0));
+        assert_eq!(next_mid, (150.0, 120.0));
+
+        // Pinching in halves the scale
+        let (scale, _, _) = pinch_delta((&n0, &n1), (&p0, &p1));
+        assert_eq!(scale, 0.5);
+    }
+
+    #[test]
+    fn pinch_delta_from_coincident_fingers_does_not_scale() {
+        let (p0, p1) = (touch(1.0, 80.0, 80.0), touch(2.0, 80.0, 80.0));
+        let (n0, n1) = (touch(1.0, 60.0, 80.0), touch(2.0, 100.0, 80.0));
+
+        let (scale, _, next_mid) = pinch_delta((&p0, &p1), (&n0, &n1));
+        assert_eq!(scale, 1.0);
+        assert_eq!(next_mid, (80.0, 80.0));
+    }
+
+    #[test]
+    fn describe_counts_both_sides_of_the_network() {
+        let chart = graph(&["a1", "a2", "p1", "p2", "p3"], &[("a1", "p1"), ("a1", "p2"), ("a2", "p3")]);
+        assert_eq!(
+            chart.describe(),
+            "Assignment network of 2 assessors and 3 applications with 3 assignments"
+        );
+    }
+
+    fn place(chart: &mut NetworkGraphChart, id: &str, x: f64, y: f64) {
+        let i = index_of(chart, id);
+        chart.nodes[i].x = x;
+        chart.nodes[i].y = y;
+        chart.nodes[i].size = 10.0;
+    }
+
+    #[test]
+    fn overlapping_nodes_are_all_hit_nearest_first() {
+        let mut chart = graph(&["a1", "p1", "p2"], &[]);
+        place(&mut chart, "a1", 100.0, 100.0);
+        place(&mut chart, "p1", 106.0, 100.0);
+        place(&mut chart, "p2", 300.0, 300.0);
+
+        let (x, y) = (104.0 * chart.zoom + chart.pan_x, 100.0 * chart.zoom + chart.pan_y);
+        let hits: Vec<(usize, bool)> = chart.hits_at(x, y).into_iter().map(|(_, i, is_node)| (i, is_node)).collect();
+        assert_eq!(hits, vec![(index_of(&chart, "p1"), true), (index_of(&chart, "a1"), true)]);
+    }
+
+    #[test]
+    fn edge_hits_follow_the_drawn_curve_not_the_chord() {
+        let mut chart = graph(&["a1", "p1"], &[("a1", "p1")]);
+        place(&mut chart, "a1", 0.0, 0.0);
+        place(&mut chart, "p1", 200.0, 0.0);
+        let screen = |x: f64, y: f64| (x * chart.zoom + chart.pan_x, y * chart.zoom + chart.pan_y);
+
+        // Control point (100, 20) puts the curve's midpoint at (100, 10)
+        let (x, y) = screen(100.0, 10.0);
+        assert_eq!(chart.hits_at(x, y).iter().map(|h| (h.1, h.2)).collect::<Vec<_>>(), vec![(0, false)]);
+        let (x, y) = screen(100.0, 0.0);
+        assert!(chart.hits_at(x, y).is_empty());
+
+        chart.curved_edges = false;
+        assert_eq!(chart.hits_at(x, y).len(), 1);
+    }
+
+    #[test]
+    fn budgeted_step_rejects_unusable_budgets() {
+        let mut chart = graph(&["a1", "p1"], &[("a1", "p1")]);
+        chart.simulation_running = true;
+        let before: Vec<(f64, f64)> = chart.nodes.iter().map(|n| (n.x, n.y)).collect();
+
+        for budget in [0.0, -5.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(!chart.step_simulation_budgeted(budget), "{}", budget);
+        }
+        assert!(chart.simulation_running);
+        assert_eq!(chart.nodes.iter().map(|n| (n.x, n.y)).collect::<Vec<_>>(), before);
+    }
+
+    #[test]
+    fn draw_to_renders_every_node_onto_a_recording_target() {
+        let mut chart = graph(&["a1", "p1", "p2"], &[("a1", "p1"), ("a1", "p2")]);
+        place(&mut chart, "a1", 0.0, 0.0);
+        place(&mut chart, "p1", 100.0, 0.0);
+        place(&mut chart, "p2", 0.0, 100.0);
+        let target = RecordingTarget::new();
+        chart.draw_to(&target).unwrap();
+
+        // Assessors are squares, applications circles centred on the node
+        let squares = target.calls().iter().filter(|c| c.op == "fill_rect" && c.args == [-10.0, -10.0, 20.0, 20.0]).count();
+        assert_eq!(squares, 1);
+        for (x, y) in [(100.0, 0.0), (0.0, 100.0)] {
+            assert!(target.calls().iter().any(|c| c.op == "arc" && c.args[..3] == [x, y, 10.0]), "({}, {})", x, y);
+        }
+    }
+
+    #[test]
+    fn select_all_by_type_respects_the_selection_cap() {
+        let mut chart = graph(&["a1", "p1", "a2", "p2", "a3"], &[]);
+        chart.select_all_of(Some(NodeType::Assessor));
+        let ids: Vec<&str> = chart.selected_nodes.iter().map(|&i| chart.nodes[i].id.as_str()).collect();
+        assert_eq!(ids, vec!["a1", "a2", "a3"]);
+
+        // Over the cap the earliest selected are dropped
+        chart.max_selection = Some(2);
+        chart.select_all_of(None);
+        let ids: Vec<&str> = chart.selected_nodes.iter().map(|&i| chart.nodes[i].id.as_str()).collect();
+        assert_eq!(ids, vec!["p2", "a3"]);
+    }
+
+    #[test]
+    fn fit_freezes_the_layout_until_a_drag() {
+        let mut chart = graph(&["a1", "p1", "p2"], &[("a1", "p1"), ("a1", "p2")]);
+        chart.freeze_on_fit = true;
+        chart.simulation_running = true;
+        chart.fit_layout();
+
+        let positions = |chart: &NetworkGraphChart| chart.nodes.iter().map(|n| (n.x, n.y)).collect::<Vec<_>>();
+        let fitted = positions(&chart);
+        for _ in 0..5 {
+            assert!(!chart.step_simulation());
+        }
+        assert_eq!(positions(&chart), fitted);
+
+        // Dropping a dragged node resumes the simulation the fit stopped
+        chart.dragging_node = Some(0);
+        chart.on_mouse_up();
+        assert!(chart.simulation_running);
+
+        // A simulation stopped by hand stays stopped after a drag
+        chart.toggle_simulation();
+        chart.dragging_node = Some(0);
+        chart.on_mouse_up();
+        assert!(!chart.simulation_running);
+    }
+
+    #[test]
+    fn disabled_hover_effect_draws_the_hovered_node_at_base_size() {
+        let mut chart = graph(&["a1"], &[]);
+        place(&mut chart, "a1", 0.0, 0.0);
+        chart.hovered_node = Some(0);
+        let node_rect = |chart: &NetworkGraphChart| {
+            let target = RecordingTarget::new();
+            chart.draw_nodes(&target).unwrap();
+            target.calls().into_iter().find(|c| c.op == "fill_rect").unwrap().args
+        };
+
+        assert_eq!(node_rect(&chart), vec![-12.0, -12.0, 24.0, 24.0]);
+        chart.hover_effect = false;
+        assert_eq!(node_rect(&chart), vec![-10.0, -10.0, 20.0, 20.0]);
+    }
+
+    #[test]
+    fn tiny_frame_budget_lowers_quality_one_level_per_frame() {
+        let mut chart = graph(&["a1", "p1"], &[("a1", "p1")]);
+        chart.last_frame_ms = 4.0;
+        chart.adapt_quality(0.01);
+        assert_eq!(chart.quality_level, 1);
+        for _ in 0..10 {
+            chart.adapt_quality(0.01);
+        }
+        assert_eq!(chart.quality_level, QUALITY_THIN_EDGES);
+
+        // Fast frames restore detail gradually; within budget but not well under holds
+        chart.adapt_quality(100.0);
+        assert_eq!(chart.quality_level, QUALITY_THIN_EDGES - 1);
+        chart.adapt_quality(6.0);
+        assert_eq!(chart.quality_level, QUALITY_THIN_EDGES - 1);
+    }
+
+    #[test]
+    fn screen_and_graph_coordinates_round_trip() {
+        let mut chart = graph(&["a1", "p1"], &[]);
+        chart.zoom = 2.5;
+        chart.pan_x = -120.0;
+        chart.pan_y = 45.0;
+        place(&mut chart, "p1", 100.0, 200.0);
+
+        // The node's screen position maps back onto the node
+        let (sx, sy) = chart.to_screen(100.0, 200.0);
+        assert_eq!((sx, sy), (130.0, 545.0));
+        let (gx, gy) = chart.to_graph(sx, sy);
+        assert_eq!(chart.node_at(gx, gy), Some(index_of(&chart, "p1")));
+
+        for &(x, y) in &[(0.0, 0.0), (-37.5, 12.25), (800.0, 400.0)] {
+            let (gx, gy) = chart.to_graph(x, y);
+            let (back_x, back_y) = chart.to_screen(gx, gy);
+            assert!((back_x - x).abs() < 1e-9 && (back_y - y).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn selection_summary_breaks_down_one_assessors_edges() {
+        let mut chart = graph(
+            &["a1", "a2", "p1", "p2", "p3", "p4"],
+            &[("a1", "p1"), ("a1", "p2"), ("a1", "p3"), ("a1", "p4"), ("a2", "p1")],
+        );
+        let statuses = [Some("completed"), Some("completed"), Some("in_progress"), None, Some("completed")];
+        for (edge, status) in chart.edges.iter_mut().zip(statuses) {
+            edge.status = status.map(str::to_string);
+        }
+        chart.selected_nodes = vec![index_of(&chart, "a1")];
+
+        let summary = chart.selection_summary();
+        assert_eq!(summary["incidentEdges"], 4);
+        assert_eq!(summary["statusBreakdown"], serde_json::json!({ "pending": 1, "in_progress": 1, "completed": 2 }));
+        assert_eq!(summary["assessors"][0]["assignedApplications"], 4);
+        assert_eq!(summary["assessors"][0]["completed"], 2);
+        assert_eq!(summary["assessors"][0]["completionRate"], 0.5);
+    }
+
+    #[test]
+    fn edge_mid_transition_draws_an_interpolated_color() {
+        let mut chart = graph(&["a1", "p1"], &[("a1", "p1")]);
+        place(&mut chart, "a1", 100.0, 100.0);
+        place(&mut chart, "p1", 300.0, 100.0);
+        chart.edge_transitions.push(EdgeTransition {
+            edge_index: 0,
+            from_color: "#000000".to_string(),
+            to_color: "#ffffff".to_string(),
+            elapsed_ms: chart.transition_ms / 2.0,
+        });
+
+        let target = RecordingTarget::new();
+        chart.draw_to(&target).unwrap();
+        let strokes: Vec<String> = target
+            .calls()
+            .iter()
+            .filter(|c| c.op == "set_stroke_style")
+            .filter_map(|c| c.text.clone())
+            .collect();
+        assert!(strokes.contains(&"#7f7f7f".to_string()), "{:?}", strokes);
+        assert!(!strokes.contains(&chart.edge_color(&chart.edges[0])));
+    }
+
+    #[test]
+    fn settled_graph_keeps_stepping_without_auto_stop() {
+        // A lone node resting on the gravity center never moves
+        let settled = |auto_stop: bool| {
+            let mut chart = graph(&["a1"], &[]);
+            let (cx, cy) = (chart.config.width / 2.0, chart.config.height / 2.0);
+            place(&mut chart, "a1", cx, cy);
+            chart.set_auto_stop(auto_stop);
+            chart.simulation_running = true;
+            chart
+        };
+
+        let mut stopping = settled(true);
+        assert!(stopping.step_simulation());
+        assert!(!stopping.step_simulation());
+        assert!(!stopping.simulation_running);
+
+        let mut continuous = settled(false);
+        for _ in 0..10 {
+            assert!(continuous.step_simulation());
+        }
+        assert!(continuous.simulation_running);
+    }
+
+    #[test]
+    fn custom_status_color_is_drawn_on_matching_edges() {
+        let mut chart = graph(&["a1", "p1", "p2"], &[("a1", "p1"), ("a1", "p2")]);
+        place(&mut chart, "p1", 300.0, 100.0);
+        place(&mut chart, "p2", 300.0, 300.0);
+        chart.edges[0].status = Some("conflict_review".to_string());
+        chart.edges[1].status = Some("completed".to_string());
+        chart.status_colors.insert("conflict_review".to_string(), "#aa00ff".to_string());
+
+        assert_eq!(chart.edge_color(&chart.edges[0]), "#aa00ff");
+        assert_eq!(chart.edge_color(&chart.edges[1]), chart.config.theme.success);
+
+        let target = RecordingTarget::new();
+        chart.draw_to(&target).unwrap();
+        let custom = target
+            .calls()
+            .iter()
+            .filter(|c| c.op == "set_stroke_style" && c.text.as_deref() == Some("#aa00ff"))
+            .count();
+        assert_eq!(custom, 1);
+    }
+
+    #[test]
+    fn isolated_nodes_drift_toward_a_custom_gravity_center() {
+        let mut chart = graph(&["p1", "p2"], &[]);
+        place(&mut chart, "p1", 400.0, 200.0);
+        place(&mut chart, "p2", 700.0, 50.0);
+        chart.set_gravity_center(Some(100.0), Some(350.0));
+        chart.set_auto_stop(false);
+        chart.simulation_running = true;
+
+        let distance = |chart: &NetworkGraphChart, i: usize| (chart.nodes[i].x - 100.0).hypot(chart.nodes[i].y - 350.0);
+        let before: Vec<f64> = (0..2).map(|i| distance(&chart, i)).collect();
+        for _ in 0..300 {
+            chart.step_simulation();
+        }
+        for (i, start) in before.into_iter().enumerate() {
+            assert!(distance(&chart, i) < start / 2.0, "node {} only moved from {} to {}", i, start, distance(&chart, i));
+        }
+    }
+
+    #[test]
+    fn heavier_assessor_moves_less_under_the_same_force() {
+        let mut chart = graph(&["a1", "p1"], &[]);
+        place(&mut chart, "a1", 300.0, 200.0);
+        place(&mut chart, "p1", 500.0, 200.0);
+        chart.set_type_physics("assessor", 1.0, 4.0).unwrap();
+        chart.set_center_gravity(0.0);
+        chart.simulation_running = true;
+
+        // Their mutual repulsion is the only force, equal and opposite
+        chart.step_simulation();
+        let assessor_moved = 300.0 - chart.nodes[index_of(&chart, "a1")].x;
+        let application_moved = chart.nodes[index_of(&chart, "p1")].x - 500.0;
+        assert!(assessor_moved > 0.0);
+        assert!((application_moved / assessor_moved - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn size_and_color_encodings_apply_to_the_same_node() {
+        let mut chart = graph(&["a1", "p1", "p2", "p3"], &[]);
+        let metadata = [(10_000.0, 2.0), (50_000.0, 0.0), (30_000.0, 1.0)];
+        for (id, (funding, variance)) in ["p1", "p2", "p3"].iter().zip(metadata) {
+            let i = index_of(&chart, id);
+            chart.nodes[i].metadata = Some(serde_json::json!({ "funding": funding, "variance": variance }));
+        }
+        chart.application_encoding = Some(NodeEncoding {
+            size_key: Some("funding".to_string()),
+            color_key: Some("variance".to_string()),
+            size_range: (8.0, 24.0),
+            color_range: ("#000000".to_string(), "#ffffff".to_string()),
+        });
+        chart.apply_encodings();
+
+        let encoded = |id: &str| {
+            let node = &chart.nodes[index_of(&chart, id)];
+            (node.size, node.color.clone())
+        };
+        assert_eq!(encoded("p1"), (8.0, "#ffffff".to_string()));
+        assert_eq!(encoded("p2"), (24.0, "#000000".to_string()));
+        assert_eq!(encoded("p3"), (16.0, "#7f7f7f".to_string()));
+        // The assessor type has no encoding
+        assert_eq!(encoded("a1").1, chart.config.theme.primary);
+    }
+
+    #[test]
+    fn capped_repulsion_is_zero_beyond_its_range() {
+        let pushed_apart = |model: RepulsionModel, gap: f64| {
+            let mut chart = graph(&["p1", "p2"], &[]);
+            place(&mut chart, "p1", 100.0, 200.0);
+            place(&mut chart, "p2", 100.0 + gap, 200.0);
+            chart.repulsion_model = model;
+            chart.set_repulsion_range(300.0);
+            chart.set_center_gravity(0.0);
+            chart.simulation_running = true;
+            chart.step_simulation();
+            chart.nodes[index_of(&chart, "p2")].x - (100.0 + gap)
+        };
+
+        assert_eq!(pushed_apart(RepulsionModel::Capped, 400.0), 0.0);
+        assert!(pushed_apart(RepulsionModel::Capped, 200.0) > 0.0);
+        assert!(pushed_apart(RepulsionModel::InverseSquare, 400.0) > 0.0);
+    }
+
+    #[test]
+    fn collisions_push_stacked_nodes_a_combined_radius_apart() {
+        let mut chart = graph(&["p1", "p2"], &[]);
+        place(&mut chart, "p1", 200.0, 200.0);
+        place(&mut chart, "p2", 200.0, 200.0);
+        chart.collision_padding = 4.0;
+        chart.resolve_collisions();
+
+        let (p1, p2) = (&chart.nodes[0], &chart.nodes[1]);
+        let gap = (p2.x - p1.x).hypot(p2.y - p1.y);
+        assert!(gap >= p1.size + p2.size + chart.collision_padding - 1e-9);
+    }
+
+    #[test]
+    fn collision_grid_matches_brute_force() {
+        // Overlapping pairs spread across many grid cells, including cell borders
+        let ids: Vec<String> = (0..40).map(|i| format!("p{}", i)).collect();
+        let mut chart = graph(&ids.iter().map(String::as_str).collect::<Vec<_>>(), &[]);
+        for pair in 0..20 {
+            let x = 23.0 + (pair % 5) as f64 * 150.0;
+            let y = 21.0 + (pair / 5) as f64 * 95.0;
+            let offset = (3.0 + pair as f64, (pair % 3) as f64 * 4.0 - 4.0);
+            place(&mut chart, &ids[pair * 2], x, y);
+            place(&mut chart, &ids[pair * 2 + 1], x + offset.0, y + offset.1);
+        }
+        chart.collision_padding = 2.0;
+
+        let mut brute = graph(&ids.iter().map(String::as_str).collect::<Vec<_>>(), &[]);
+        brute.nodes = chart.nodes.clone();
+        brute.collision_padding = chart.collision_padding;
+        for i in 0..brute.nodes.len() {
+            for j in (i + 1)..brute.nodes.len() {
+                brute.separate(i, j);
+            }
+        }
+
+        chart.resolve_collisions();
+        for (grid_node, brute_node) in chart.nodes.iter().zip(&brute.nodes) {
+            assert!((grid_node.x - brute_node.x).abs() < 1e-9, "{}", grid_node.id);
+            assert!((grid_node.y - brute_node.y).abs() < 1e-9, "{}", grid_node.id);
+        }
+        for pair in chart.nodes.chunks(2) {
+            assert!((pair[1].x - pair[0].x).hypot(pair[1].y - pair[0].y) >= 22.0 - 1e-9);
+        }
+    }
+
+    #[test]
+    fn load_balance_flags_assessors_a_stddev_from_the_mean() {
+        // Assignments: a1 8, a2-a4 2 each, a5 none; mean 2.8, stddev ~2.71
+        let mut edges: Vec<(String, String)> = (1..=8).map(|p| ("a1".to_string(), format!("p{}", p))).collect();
+        for (assessor, first) in [("a2", 1), ("a3", 3), ("a4", 5)] {
+            edges.push((assessor.to_string(), format!("p{}", first)));
+            edges.push((format!("p{}", first + 1), assessor.to_string()));
+        }
+        let ids: Vec<String> = (1..=5).map(|a| format!("a{}", a)).chain((1..=8).map(|p| format!("p{}", p))).collect();
+        let chart = graph(
+            &ids.iter().map(String::as_str).collect::<Vec<_>>(),
+            &edges.iter().map(|(s, t)| (s.as_str(), t.as_str())).collect::<Vec<_>>(),
+        );
+
+        let report = chart.load_balance();
+        assert!((report["mean"].as_f64().unwrap() - 2.8).abs() < 1e-9);
+        assert_eq!(report["overloaded"], serde_json::json!(["a1"]));
+        assert_eq!(report["underloaded"], serde_json::json!(["a5"]));
+        assert_eq!(report["assessors"][1]["assigned"], 2);
+    }
+
+    #[test]
+    fn zoom_sensitivity_scales_and_invert_flips_the_wheel() {
+        let zoom_after = |sensitivity: f64, inverted: bool| {
+            let mut chart = graph(&["a1"], &[]);
+            chart.set_zoom_sensitivity(sensitivity);
+            chart.set_invert_zoom(inverted);
+            chart.zoom_by(-100.0, 400.0, 200.0);
+            chart.zoom - 1.0
+        };
+
+        let base = zoom_after(1.0, false);
+        assert!(base > 0.0);
+        assert!((zoom_after(2.0, false) - 2.0 * base).abs() < 1e-9);
+        assert!((zoom_after(1.0, true) + base).abs() < 1e-9);
+    }
+
+    #[test]
+    fn progress_ring_sweeps_the_completed_share() {
+        let mut chart = graph(&["a1", "p1", "p2", "p3", "p4"], &[("a1", "p1"), ("a1", "p2"), ("a1", "p3"), ("a1", "p4")]);
+        chart.edges[0].status = Some("completed".to_string());
+        chart.edges[2].status = Some("completed".to_string());
+        for (i, id) in ["a1", "p1", "p2", "p3", "p4"].iter().enumerate() {
+            place(&mut chart, id, 100.0 + i as f64 * 120.0, 200.0);
+        }
+        chart.show_progress_ring = true;
+
+        let target = RecordingTarget::new();
+        chart.draw_to(&target).unwrap();
+        let a1 = &chart.nodes[index_of(&chart, "a1")];
+        let rings: Vec<Vec<f64>> = target
+            .calls()
+            .iter()
+            .filter(|c| c.op == "arc" && c.args[0] == a1.x && c.args[1] == a1.y && c.args[3] == -PI / 2.0)
+            .map(|c| c.args.clone())
+            .collect();
+        // 2 of 4 assignments done: half a turn from 12 o'clock
+        assert_eq!(rings.len(), 1);
+        assert!((rings[0][4] - PI / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn deterministic_renders_issue_identical_draw_calls() {
+        let render = |deterministic: bool| {
+            let mut chart = NetworkGraphChart::with_config("test", ChartConfig::default());
+            chart.set_deterministic(deterministic);
+            chart.load_graph(
+                ["a1", "a2", "p1", "p2", "p3"].iter().map(|id| node(id)).collect(),
+                [("a1", "p1"), ("a1", "p2"), ("a2", "p2"), ("a2", "p3")].iter().map(|(s, t)| edge(s, t)).collect(),
+            );
+            for _ in 0..50 {
+                chart.step_simulation();
+            }
+            let target = RecordingTarget::new();
+            chart.draw_to(&target).unwrap();
+            target.calls()
+        };
+
+        let first = render(true);
+        assert_eq!(first, render(true));
+        // Without reseeding, the jittered starting layout differs
+        assert_ne!(first, render(false));
+    }
+
+    #[test]
+    fn hovered_node_is_filled_after_every_other_node() {
+        let mut chart = graph(&["a1", "a2", "a3"], &[]);
+        for (i, id) in ["a1", "a2", "a3"].iter().enumerate() {
+            place(&mut chart, id, 100.0 + i as f64 * 100.0, 200.0);
+        }
+        chart.hovered_node = Some(index_of(&chart, "a1"));
+        chart.selected_nodes = vec![index_of(&chart, "a3")];
+
+        let target = RecordingTarget::new();
+        chart.draw_nodes(&target).unwrap();
+        // Assessor squares, identified by their center x
+        let filled: Vec<f64> = target
+            .calls()
+            .iter()
+            .filter(|c| c.op == "fill_rect")
+            .map(|c| c.args[0] + c.args[2] / 2.0)
+            .collect();
+        assert_eq!(filled, vec![200.0, 300.0, 100.0]);
+    }
+
+    #[test]
+    fn edge_labels_only_draw_when_zoomed_in() {
+        let mut chart = graph(&["a1", "p1"], &[("a1", "p1")]);
+        place(&mut chart, "a1", 100.0, 200.0);
+        place(&mut chart, "p1", 300.0, 200.0);
+        chart.edges[0].label = Some("Due Fri".to_string());
+        chart.show_edge_labels = true;
+        let labelled = |chart: &NetworkGraphChart| {
+            let target = RecordingTarget::new();
+            chart.draw_to(&target).unwrap();
+            target.texts().contains(&"Due Fri".to_string())
+        };
+
+        chart.zoom = EDGE_LABEL_MIN_ZOOM + 0.3;
+        assert!(labelled(&chart));
+        chart.zoom = EDGE_LABEL_MIN_ZOOM - 0.2;
+        assert!(!labelled(&chart));
+    }
+
+    #[test]
+    fn coincident_nodes_share_one_label_with_collision_on() {
+        let mut chart = graph(&["p1", "p2"], &[]);
+        place(&mut chart, "p1", 200.0, 200.0);
+        place(&mut chart, "p2", 200.0, 200.0);
+        let labels = |chart: &NetworkGraphChart| {
+            let target = RecordingTarget::new();
+            chart.draw_nodes(&target).unwrap();
+            target.texts()
+        };
+
+        assert_eq!(labels(&chart).len(), 2);
+        chart.label_collision = true;
+        assert_eq!(labels(&chart).len(), 1);
+    }
+
+    #[test]
+    fn conflict_pair_draws_a_marker_and_shows_on_hover() {
+        let mut chart = graph(&["a1", "p1", "p2"], &[("a1", "p2")]);
+        place(&mut chart, "a1", 100.0, 200.0);
+        place(&mut chart, "p1", 300.0, 100.0);
+        place(&mut chart, "p2", 300.0, 300.0);
+        chart.conflicts = vec![AssignmentConflict { assessor_id: "a1".to_string(), application_id: "p1".to_string() }];
+
+        let target = RecordingTarget::new();
+        chart.draw_to(&target).unwrap();
+        // Warning glyph at the pair's midpoint
+        let glyph = target.calls().iter().find(|c| c.text.as_deref() == Some("!")).cloned().unwrap();
+        assert_eq!(glyph.args, vec![200.0, 150.0]);
+        assert!(target.calls().iter().any(|c| c.op == "arc" && c.args[..2] == [200.0, 150.0]));
+
+        let hover = |id: &str| chart.node_hit(index_of(&chart, id), None).data.unwrap()["conflicts"].clone();
+        assert_eq!(hover("a1"), serde_json::json!(["p1"]));
+        assert_eq!(hover("p1"), serde_json::json!(["a1"]));
+        assert_eq!(hover("p2"), serde_json::json!([]));
+    }
+
+    #[test]
+    fn reload_keeps_surviving_selection_by_id() {
+        let mut chart = graph(&["a1", "p1", "p2"], &[("a1", "p1"), ("a1", "p2")]);
+        chart.selected_nodes = vec![index_of(&chart, "p1"), index_of(&chart, "p2")];
+        chart.hovered_node = Some(index_of(&chart, "p1"));
+
+        // p1 is gone and the survivors move to new indices
+        chart.load_graph(
+            ["p3", "p2", "a1"].iter().map(|id| node(id)).collect(),
+            vec![edge("a1", "p2"), edge("a1", "p3")],
+        );
+        assert_eq!(chart.selected_nodes, vec![index_of(&chart, "p2")]);
+        assert_eq!(chart.hovered_node, None);
+    }
+
+    #[test]
+    fn node_stroke_outlines_each_node_once() {
+        let mut chart = graph(&["a1", "p1", "p2"], &[]);
+        for (i, id) in ["a1", "p1", "p2"].iter().enumerate() {
+            place(&mut chart, id, 100.0 + i as f64 * 100.0, 200.0);
+        }
+        let strokes = |chart: &NetworkGraphChart| {
+            let target = RecordingTarget::new();
+            chart.draw_nodes(&target).unwrap();
+            target.count("stroke") + target.count("stroke_rect")
+        };
+
+        let without = strokes(&chart);
+        chart.node_stroke = true;
+        assert_eq!(strokes(&chart), without + 3);
+    }
+
+    #[test]
+    fn switching_themes_changes_the_clear_color() {
+        let mut chart = graph(&["a1", "p1"], &[]);
+        let background = |chart: &NetworkGraphChart| {
+            let target = RecordingTarget::new();
+            chart.draw_to(&target).unwrap();
+            target.calls().iter().find(|c| c.op == "set_fill_style").and_then(|c| c.text.clone()).unwrap()
+        };
+        assert_eq!(background(&chart), ColorTheme::default().background);
+
+        let dark = ColorTheme {
+            background: "#111827".to_string(),
+            primary: "#60A5FA".to_string(),
+            ..ColorTheme::default()
+        };
+        chart.apply_theme(dark);
+        assert_eq!(background(&chart), "#111827");
+        // Theme-colored nodes follow the switch
+        assert_eq!(chart.nodes[index_of(&chart, "a1")].color, "#60A5FA");
+    }
+}
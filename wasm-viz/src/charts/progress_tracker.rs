@@ -5,10 +5,16 @@
 
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
-use web_sys::CanvasRenderingContext2d;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+use std::cell::Cell;
+use std::collections::HashMap;
 use std::f64::consts::PI;
 
-use super::common::{get_canvas_context, clear_canvas, ChartConfig, HitTestResult};
+use super::common::{get_canvas_context, track_context_loss, surface_context_loss, clear_canvas, readable_on, is_transparent, wrap_text, payload_hash, is_same_payload, render_image_data, draw_legend_swatch, now_ms, RenderTiming, ChartConfig, HitTestResult, SwatchShape, MIN_RENDER_SIZE};
+use super::draw_target::DrawTarget;
+
+/// Minimum segment sweep (radians) before an in-arc percentage label is drawn
+const MIN_LABEL_SWEEP: f64 = 0.35;
 
 /// Progress data for an assessor or category
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -20,6 +26,13 @@ pub struct ProgressSegment {
     pub color: Option<String>,
 }
 
+/// Historical completion percentages for one segment
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SegmentHistory {
+    pub id: String,
+    pub points: Vec<f64>,
+}
+
 /// Progress tracker chart with radial visualization
 #[wasm_bindgen]
 pub struct ProgressTrackerChart {
@@ -30,6 +43,19 @@ pub struct ProgressTrackerChart {
     center_value: String,
     hovered_segment: Option<usize>,
     animation_progress: f64,
+    deterministic: bool,
+    show_segment_labels: bool,
+    rotate_segment_labels: bool,
+    hide_empty_segments: bool,
+    // Donut start angle in radians and sweep direction
+    start_angle: f64,
+    clockwise: bool,
+    // Segment id -> completion % snapshots, oldest first
+    segment_history: HashMap<String, Vec<f64>>,
+    // Fingerprint of the last loaded payload
+    data_hash: Option<u64>,
+    render_timing: RenderTiming,
+    context_lost: Cell<bool>,
 }
 
 #[wasm_bindgen]
@@ -39,8 +65,12 @@ impl ProgressTrackerChart {
     pub fn new(canvas_id: &str, config_js: JsValue) -> Result<ProgressTrackerChart, JsValue> {
         let config: ChartConfig = serde_wasm_bindgen::from_value(config_js)
             .unwrap_or_else(|_| ChartConfig::default());
+        Ok(Self::with_config(canvas_id, config))
+    }
 
-        Ok(Self {
+    /// Create a chart from an already-parsed config
+    pub(crate) fn with_config(canvas_id: &str, config: ChartConfig) -> Self {
+Self {
             canvas_id: canvas_id.to_string(),
             config,
             segments: Vec::new(),
@@ -48,12 +78,44 @@ impl ProgressTrackerChart {
             center_value: "0%".to_string(),
             hovered_segment: None,
             animation_progress: 1.0,
-        })
+            deterministic: false,
+            show_segment_labels: false,
+            rotate_segment_labels: false,
+            hide_empty_segments: false,
+            start_angle: -PI / 2.0,
+            clockwise: true,
+            segment_history: HashMap::new(),
+            data_hash: None,
+            render_timing: RenderTiming::default(),
+            context_lost: Cell::new(false),
+        }
     }
 
     /// Set the progress data
     pub fn set_data(&mut self, data_js: JsValue) -> Result<(), JsValue> {
+        self.set_data_with_options(data_js, false)
+    }
+
+    /// Set the progress data; identical payloads are skipped unless `force` is set
+    pub fn set_data_with_options(&mut self, data_js: JsValue, force: bool) -> Result<(), JsValue> {
+        let hash = payload_hash(&[&data_js]);
+        if is_same_payload(hash, self.data_hash, force) {
+            return Ok(());
+        }
+
+        self.load_data(data_js)?;
+        self.data_hash = hash;
+        Ok(())
+    }
+
+    fn load_data(&mut self, data_js: JsValue) -> Result<(), JsValue> {
         let segments: Vec<ProgressSegment> = serde_wasm_bindgen::from_value(data_js)?;
+        self.load_segments(segments);
+        Ok(())
+    }
+
+    /// Replace the segments with parsed data
+    fn load_segments(&mut self, segments: Vec<ProgressSegment>) {
         self.segments = segments;
 
         // Calculate overall progress for center display
@@ -61,14 +123,20 @@ impl ProgressTrackerChart {
         let total_items: u32 = self.segments.iter().map(|s| s.total).sum();
 
         if total_items > 0 {
-            let pct = (total_completed as f64 / total_items as f64) * 100.0;
-            self.center_value = format!("{:.1}%", pct);
+            self.center_value = format_percent(total_completed as f64 / total_items as f64, 1);
         } else {
             self.center_value = "N/A".to_string();
         }
 
-        self.animation_progress = 0.0;
-        Ok(())
+        self.animation_progress = if self.deterministic { 1.0 } else { 0.0 };
+    }
+
+    /// Disable the intro animation so every render shows the final state
+    pub fn set_deterministic(&mut self, enabled: bool) {
+        self.deterministic = enabled;
+        if enabled {
+            self.animation_progress = 1.0;
+        }
     }
 
     /// Set the center label text
@@ -76,81 +144,203 @@ impl ProgressTrackerChart {
         self.center_label = label.to_string();
     }
 
+    /// Show completion percentage labels inside donut segments
+    pub fn set_show_segment_labels(&mut self, enabled: bool) {
+        self.show_segment_labels = enabled;
+    }
+
+    /// Rotate segment labels to follow the arc (default keeps them horizontal)
+    pub fn set_segment_label_rotation(&mut self, follow_arc: bool) {
+        self.rotate_segment_labels = follow_arc;
+    }
+
+    /// Leave out segments with `total == 0` from the donut and legend; colors are
+    /// assigned over the remaining segments only
+    pub fn set_hide_empty_segments(&mut self, enabled: bool) {
+        self.hide_empty_segments = enabled;
+        self.hovered_segment = None;
+        self.refresh();
+    }
+
+    /// Segments that are drawn, in order
+    fn visible_segments(&self) -> Vec<&ProgressSegment> {
+        self.segments.iter()
+            .filter(|s| !self.hide_empty_segments || s.total > 0)
+            .collect()
+    }
+
+    /// Color of the `i`th drawn segment: its own, or the accent palette in draw order
+    fn segment_color(&self, i: usize, segment: &ProgressSegment) -> String {
+        segment.color.clone().unwrap_or_else(|| {
+            self.config.theme.accent[i % self.config.theme.accent.len()].clone()
+        })
+    }
+
+    /// Set where the donut starts (degrees, 0 = 3 o'clock, -90 = top) and which way it sweeps
+    pub fn set_donut_orientation(&mut self, start_degrees: f64, clockwise: bool) {
+        self.start_angle = start_degrees.to_radians();
+        self.clockwise = clockwise;
+        self.refresh();
+    }
+
+    /// Sweep sign: +1 clockwise, -1 counter-clockwise
+    fn direction(&self) -> f64 {
+        if self.clockwise { 1.0 } else { -1.0 }
+    }
+
+    /// Set per-segment completion history (`[{id, points}]`) shown as legend trend sparklines
+    pub fn set_segment_history(&mut self, history_js: JsValue) -> Result<(), JsValue> {
+        let history: Vec<SegmentHistory> = serde_wasm_bindgen::from_value(history_js)?;
+        self.segment_history = history.into_iter().map(|h| (h.id, h.points)).collect();
+        Ok(())
+    }
+
+    /// Replace the color theme (a `ColorTheme` object) and re-render
+    pub fn set_theme(&mut self, theme_js: JsValue) -> Result<(), JsValue> {
+        self.config.theme = serde_wasm_bindgen::from_value(theme_js)?;
+        self.render()
+    }
+
+    /// Whether the last render failed because the 2D context was lost
+    pub fn is_context_lost(&self) -> bool {
+        self.context_lost.get()
+    }
+
+    /// Re-acquire the canvas context after a loss and re-render
+    pub fn restore(&mut self) -> Result<(), JsValue> {
+        self.context_lost.set(false);
+        self.render()
+    }
+
+    /// Re-render after a state change, warning once if the context was lost
+    fn refresh(&self) {
+        surface_context_loss(&self.context_lost, &self.canvas_id, || self.render());
+    }
+
     /// Render the chart
     pub fn render(&self) -> Result<(), JsValue> {
+        if !self.config.has_drawable_size() {
+            return Ok(());
+        }
+
         let (canvas, ctx) = get_canvas_context(&self.canvas_id)?;
+        let start = now_ms();
+        let result = track_context_loss(&self.context_lost, &ctx, || self.draw_frame(&canvas, &ctx));
+        self.render_timing.record(now_ms() - start);
+        result
+    }
+
+    /// Duration of the last render in milliseconds
+    pub fn last_render_ms(&self) -> f64 {
+        self.render_timing.last()
+    }
 
+    /// Average render duration over recent renders in milliseconds
+    pub fn avg_render_ms(&self) -> f64 {
+        self.render_timing.average()
+    }
+
+    /// Render offscreen and return raw RGBA bytes for pixel-level regression tests
+    pub fn render_to_image_data(&self, width: u32, height: u32) -> Result<Vec<u8>, JsValue> {
+        render_image_data(&self.config, width, height, |canvas, ctx| self.draw_frame(canvas, ctx))
+    }
+
+    fn draw_frame(&self, canvas: &HtmlCanvasElement, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
         canvas.set_width(self.config.width as u32);
         canvas.set_height(self.config.height as u32);
 
-        clear_canvas(&ctx, self.config.width, self.config.height, &self.config.theme.background);
+        self.draw_to(ctx)
+    }
+
+    /// Draw the full chart onto any target; the canvas must already be sized
+    pub(crate) fn draw_to<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        clear_canvas(ctx, self.config.width, self.config.height, &self.config.theme.background);
 
         if self.segments.is_empty() {
-            self.draw_empty_state(&ctx)?;
+            self.draw_empty_state(ctx)?;
             return Ok(());
         }
 
         // Draw the main donut chart
-        self.draw_donut(&ctx)?;
+        self.draw_donut(ctx)?;
 
         // Draw center text
-        self.draw_center_text(&ctx)?;
+        self.draw_center_text(ctx)?;
 
         // Draw legend if enabled
         if self.config.show_legend {
-            self.draw_legend(&ctx)?;
+            self.draw_legend(ctx)?;
         }
 
         Ok(())
     }
 
-    fn draw_donut(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
+    fn draw_donut<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
         let center_x = self.config.width / 2.0;
         let center_y = self.config.height / 2.0;
         let outer_radius = (self.config.width.min(self.config.height) / 2.0 - 60.0).max(50.0);
         let inner_radius = outer_radius * 0.6;
 
-        let total: f64 = self.segments.iter().map(|s| s.total as f64).sum();
+        let segments = self.visible_segments();
+        let total: f64 = segments.iter().map(|s| s.total as f64).sum();
         if total == 0.0 {
             return Ok(());
         }
 
-        let mut current_angle = -PI / 2.0; // Start from top
+        let dir = self.direction();
+        let mut current_angle = self.start_angle;
 
-        for (i, segment) in self.segments.iter().enumerate() {
+        for (i, segment) in segments.iter().enumerate() {
             let segment_angle = (segment.total as f64 / total) * 2.0 * PI * self.animation_progress;
-            let completed_ratio = segment.completed as f64 / segment.total.max(1) as f64;
+            // Over-committed segments fill their full sweep and get an overflow tick instead
+            let completed_ratio = (segment.completed as f64 / segment.total.max(1) as f64).min(1.0);
 
             // Get color for this segment
-            let color = segment.color.clone().unwrap_or_else(|| {
-                self.config.theme.accent[i % self.config.theme.accent.len()].clone()
-            });
+            let color = self.segment_color(i, segment);
 
             let is_hovered = self.hovered_segment == Some(i);
             let radius_offset = if is_hovered { 5.0 } else { 0.0 };
 
             // Draw background arc (total)
-            ctx.set_fill_style(&JsValue::from_str(&self.config.theme.grid));
+            ctx.set_fill_style(&self.config.theme.grid);
             ctx.begin_path();
-            ctx.arc(center_x, center_y, outer_radius + radius_offset, current_angle, current_angle + segment_angle)?;
-            ctx.arc_with_anticlockwise(center_x, center_y, inner_radius + radius_offset, current_angle + segment_angle, current_angle, true)?;
+            ctx.arc_with_anticlockwise(center_x, center_y, outer_radius + radius_offset, current_angle, current_angle + dir * segment_angle, !self.clockwise)?;
+            ctx.arc_with_anticlockwise(center_x, center_y, inner_radius + radius_offset, current_angle + dir * segment_angle, current_angle, self.clockwise)?;
             ctx.close_path();
             ctx.fill();
 
             // Draw completed arc
             let completed_angle = segment_angle * completed_ratio;
-            ctx.set_fill_style(&JsValue::from_str(&color));
+            ctx.set_fill_style(&color);
             ctx.set_global_alpha(if is_hovered { 1.0 } else { 0.9 });
             ctx.begin_path();
-            ctx.arc(center_x, center_y, outer_radius + radius_offset, current_angle, current_angle + completed_angle)?;
-            ctx.arc_with_anticlockwise(center_x, center_y, inner_radius + radius_offset, current_angle + completed_angle, current_angle, true)?;
+            ctx.arc_with_anticlockwise(center_x, center_y, outer_radius + radius_offset, current_angle, current_angle + dir * completed_angle, !self.clockwise)?;
+            ctx.arc_with_anticlockwise(center_x, center_y, inner_radius + radius_offset, current_angle + dir * completed_angle, current_angle, self.clockwise)?;
             ctx.close_path();
             ctx.fill();
             ctx.set_global_alpha(1.0);
 
+            if segment.completed > segment.total {
+                let end = current_angle + dir * segment_angle;
+                let outer = outer_radius + radius_offset;
+                ctx.set_stroke_style(&self.config.theme.danger);
+                ctx.set_line_width(3.0);
+                ctx.begin_path();
+                ctx.move_to(center_x + outer * end.cos(), center_y + outer * end.sin());
+                ctx.line_to(center_x + (outer + 8.0) * end.cos(), center_y + (outer + 8.0) * end.sin());
+                ctx.stroke();
+            }
+
             // Draw segment separator
-            if self.segments.len() > 1 {
-                ctx.set_stroke_style(&JsValue::from_str(&self.config.theme.background));
+            if segments.len() > 1 {
+                // On a transparent background, cut the gap out instead of painting it
+                let transparent = is_transparent(&self.config.theme.background);
+                if transparent {
+                    ctx.set_global_composite_operation("destination-out")?;
+                    ctx.set_stroke_style("#000000");
+                } else {
+                    ctx.set_stroke_style(&self.config.theme.background);
+                }
                 ctx.set_line_width(2.0);
                 ctx.begin_path();
                 ctx.move_to(
@@ -162,83 +352,206 @@ impl ProgressTrackerChart {
                     center_y + outer_radius * current_angle.sin(),
                 );
                 ctx.stroke();
+                if transparent {
+                    ctx.set_global_composite_operation("source-over")?;
+                }
+            }
+
+            current_angle += dir * segment_angle;
+        }
+
+        if self.show_segment_labels {
+            self.draw_segment_labels(ctx, center_x, center_y, (outer_radius + inner_radius) / 2.0, total)?;
+        }
+
+        Ok(())
+    }
+
+    fn draw_segment_labels<T: DrawTarget + ?Sized>(
+        &self,
+        ctx: &T,
+        center_x: f64,
+        center_y: f64,
+        label_radius: f64,
+        total: f64,
+    ) -> Result<(), JsValue> {
+        ctx.set_fill_style(&self.config.theme.text);
+        ctx.set_font(&self.config.font("bold", self.config.axis_size()));
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
+
+        let dir = self.direction();
+        let mut current_angle = self.start_angle;
+
+        for segment in self.visible_segments() {
+            let segment_angle = (segment.total as f64 / total) * 2.0 * PI * self.animation_progress;
+
+            // Skip segments too narrow to hold a label without clutter
+            if segment_angle < MIN_LABEL_SWEEP {
+                current_angle += dir * segment_angle;
+                continue;
             }
 
-            current_angle += segment_angle;
+            let mid_angle = current_angle + dir * segment_angle / 2.0;
+            let x = center_x + label_radius * mid_angle.cos();
+            let y = center_y + label_radius * mid_angle.sin();
+            let text = format_percent(segment.completed as f64 / segment.total.max(1) as f64, 0);
+
+            if self.rotate_segment_labels {
+                // Follow the arc tangent, flipping on the lower half so text stays upright
+                let mut rotation = mid_angle + PI / 2.0;
+                if mid_angle.sin() > 0.0 {
+                    rotation += PI;
+                }
+                ctx.save();
+                ctx.translate(x, y)?;
+                ctx.rotate(rotation)?;
+                ctx.fill_text(&text, 0.0, 0.0)?;
+                ctx.restore();
+            } else {
+                ctx.fill_text(&text, x, y)?;
+            }
+
+            current_angle += dir * segment_angle;
         }
 
+        ctx.set_text_baseline("alphabetic");
         Ok(())
     }
 
-    fn draw_center_text(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
+    fn draw_center_text<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
         let center_x = self.config.width / 2.0;
         let center_y = self.config.height / 2.0;
 
         // Main percentage value
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
-        ctx.set_font(&format!("bold {}px {}", self.config.font_size * 2.5, self.config.font_family));
+        ctx.set_fill_style(&self.config.theme.text);
+        ctx.set_font(&self.config.font("bold", self.config.value_size()));
         ctx.set_text_align("center");
         ctx.set_text_baseline("middle");
         ctx.fill_text(&self.center_value, center_x, center_y - 10.0)?;
 
         // Label below
-        ctx.set_font(&format!("{}px {}", self.config.font_size, self.config.font_family));
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.secondary));
+        ctx.set_font(&self.config.font("", self.config.label_size()));
+        ctx.set_fill_style(&self.config.theme.secondary);
         ctx.fill_text(&self.center_label, center_x, center_y + 20.0)?;
 
         Ok(())
     }
 
-    fn draw_legend(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
+    fn draw_legend<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
         let legend_x = self.config.width - self.config.padding.right - 150.0;
         let mut legend_y = self.config.padding.top + 20.0;
         let item_height = 24.0;
+        let line_height = self.config.legend_size() + 3.0;
+        // Labels wrap before the count column at +100
+        let label_width = 100.0 - 18.0 - 6.0;
 
-        ctx.set_font(&format!("{}px {}", self.config.font_size - 1.0, self.config.font_family));
+        ctx.set_font(&self.config.font("", self.config.legend_size()));
         ctx.set_text_align("left");
 
-        for (i, segment) in self.segments.iter().enumerate() {
-            let color = segment.color.clone().unwrap_or_else(|| {
-                self.config.theme.accent[i % self.config.theme.accent.len()].clone()
-            });
+        for (i, segment) in self.visible_segments().into_iter().enumerate() {
+            let color = self.segment_color(i, segment);
 
             // Color box
-            ctx.set_fill_style(&JsValue::from_str(&color));
-            ctx.fill_rect(legend_x, legend_y - 8.0, 12.0, 12.0);
-
-            // Label
-            ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
-            ctx.fill_text(&segment.label, legend_x + 18.0, legend_y)?;
+            draw_legend_swatch(ctx, SwatchShape::Square, &color, legend_x, legend_y - 8.0, 12.0)?;
+
+            // Label, wrapped to two lines
+            let lines = wrap_text(ctx, &segment.label, label_width, 2);
+            let extra_height = (lines.len().max(1) - 1) as f64 * line_height;
+            ctx.set_fill_style(&self.config.theme.text);
+            for (line_idx, line) in lines.iter().enumerate() {
+                ctx.fill_text(line, legend_x + 18.0, legend_y + line_idx as f64 * line_height)?;
+            }
 
-            // Progress count
-            ctx.set_fill_style(&JsValue::from_str(&self.config.theme.secondary));
+            // Progress count, aligned with the last label line
+            ctx.set_fill_style(&self.config.theme.secondary);
             ctx.fill_text(
                 &format!("{}/{}", segment.completed, segment.total),
                 legend_x + 100.0,
-                legend_y,
+                legend_y + extra_height,
             )?;
 
-            legend_y += item_height;
+            if let Some(points) = self.segment_history.get(&segment.id) {
+                self.draw_trend(ctx, points, &color, legend_x + 135.0, legend_y + extra_height - 10.0);
+            }
+
+            legend_y += item_height + extra_height;
         }
 
         Ok(())
     }
 
-    fn draw_empty_state(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
+    /// Draw a 40px sparkline with an up/down indicator; `y` is the top of the row
+    fn draw_trend<T: DrawTarget + ?Sized>(&self, ctx: &T, points: &[f64], color: &str, x: f64, y: f64) {
+        if points.len() < 2 {
+            return;
+        }
+
+        let width = 40.0;
+        let height = 12.0;
+        let min = points.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = points.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let range = if max > min { max - min } else { 1.0 };
+        let step = width / (points.len() - 1) as f64;
+
+        ctx.set_stroke_style(color);
+        ctx.set_line_width(1.5);
+        ctx.begin_path();
+        for (i, p) in points.iter().enumerate() {
+            let px = x + i as f64 * step;
+            let py = if max > min { y + height - (p - min) / range * height } else { y + height / 2.0 };
+            if i == 0 {
+                ctx.move_to(px, py);
+            } else {
+                ctx.line_to(px, py);
+            }
+        }
+        ctx.stroke();
+
+        // Direction indicator
+        let ax = x + width + 7.0;
+        let ay = y + height / 2.0;
+        match trend_direction(points) {
+            1 => {
+                ctx.set_fill_style(&self.config.theme.success);
+                ctx.begin_path();
+                ctx.move_to(ax, ay - 5.0);
+                ctx.line_to(ax + 4.0, ay + 3.0);
+                ctx.line_to(ax - 4.0, ay + 3.0);
+                ctx.close_path();
+                ctx.fill();
+            }
+            -1 => {
+                ctx.set_fill_style(&self.config.theme.danger);
+                ctx.begin_path();
+                ctx.move_to(ax, ay + 5.0);
+                ctx.line_to(ax + 4.0, ay - 3.0);
+                ctx.line_to(ax - 4.0, ay - 3.0);
+                ctx.close_path();
+                ctx.fill();
+            }
+            _ => {
+                ctx.set_fill_style(&self.config.theme.secondary);
+                ctx.fill_rect(ax - 4.0, ay - 1.0, 8.0, 2.0);
+            }
+        }
+    }
+
+    fn draw_empty_state<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
         let center_x = self.config.width / 2.0;
         let center_y = self.config.height / 2.0;
         let radius = (self.config.width.min(self.config.height) / 2.0 - 60.0).max(50.0);
 
         // Draw empty circle
-        ctx.set_stroke_style(&JsValue::from_str(&self.config.theme.grid));
+        ctx.set_stroke_style(&self.config.theme.grid);
         ctx.set_line_width(20.0);
         ctx.begin_path();
         ctx.arc(center_x, center_y, radius - 10.0, 0.0, 2.0 * PI)?;
         ctx.stroke();
 
         // Empty state text
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.secondary));
-        ctx.set_font(&format!("{}px {}", self.config.font_size, self.config.font_family));
+        ctx.set_fill_style(readable_on(&self.config.theme.secondary, &self.config.theme.background));
+        ctx.set_font(&self.config.font("", self.config.label_size()));
         ctx.set_text_align("center");
         ctx.fill_text("No data available", center_x, center_y)?;
 
@@ -252,10 +565,31 @@ impl ProgressTrackerChart {
         }
 
         self.animation_progress = (self.animation_progress + delta_ms / 500.0).min(1.0);
-        self.render().ok();
+        self.refresh();
         self.animation_progress < 1.0
     }
 
+    /// Index of the drawn segment under a canvas angle (radians, as from `atan2`),
+    /// honoring the donut's start angle and direction
+    fn segment_at_angle(&self, canvas_angle: f64) -> Option<usize> {
+        // Angle swept from the start in the donut's direction, in [0, 2π)
+        let angle = ((canvas_angle - self.start_angle) * self.direction()).rem_euclid(2.0 * PI);
+        let segments = self.visible_segments();
+        let total: f64 = segments.iter().map(|s| s.total as f64).sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut cumulative_angle = 0.0;
+        for (i, segment) in segments.iter().enumerate() {
+            cumulative_angle += (segment.total as f64 / total) * 2.0 * PI;
+            if angle <= cumulative_angle {
+                return Some(i);
+            }
+        }
+        None
+    }
+
     /// Handle mouse move for hover effects
     pub fn on_mouse_move(&mut self, x: f64, y: f64) -> JsValue {
         let center_x = self.config.width / 2.0;
@@ -270,54 +604,45 @@ impl ProgressTrackerChart {
         let old_hovered = self.hovered_segment;
 
         if distance >= inner_radius && distance <= outer_radius {
-            let mut angle = dy.atan2(dx) + PI / 2.0;
-            if angle < 0.0 {
-                angle += 2.0 * PI;
-            }
-
-            let total: f64 = self.segments.iter().map(|s| s.total as f64).sum();
-            if total > 0.0 {
-                let mut cumulative_angle = 0.0;
-                for (i, segment) in self.segments.iter().enumerate() {
-                    let segment_angle = (segment.total as f64 / total) * 2.0 * PI;
-                    if angle <= cumulative_angle + segment_angle {
-                        self.hovered_segment = Some(i);
-
-                        if old_hovered != self.hovered_segment {
-                            self.render().ok();
-                        }
-
-                        let result = HitTestResult::hit(
-                            &segment.id,
-                            "progress_segment",
-                            serde_json::json!({
-                                "id": segment.id,
-                                "label": segment.label,
-                                "completed": segment.completed,
-                                "total": segment.total,
-                                "percentage": (segment.completed as f64 / segment.total.max(1) as f64) * 100.0
-                            }),
-                        );
-                        return serde_wasm_bindgen::to_value(&result).unwrap();
-                    }
-                    cumulative_angle += segment_angle;
+            if let Some(i) = self.segment_at_angle(dy.atan2(dx)) {
+                self.hovered_segment = Some(i);
+                if old_hovered != self.hovered_segment {
+                    self.refresh();
                 }
+
+                let segment = self.visible_segments()[i];
+                let result = HitTestResult::hit(
+                    &segment.id,
+                    "progress_segment",
+                    serde_json::json!({
+                        "id": segment.id,
+                        "label": segment.label,
+                        "completed": segment.completed,
+                        "total": segment.total,
+                        "percentage": (segment.completed as f64 / segment.total.max(1) as f64) * 100.0
+                    }),
+                );
+                return serde_wasm_bindgen::to_value(&result).unwrap();
             }
         }
 
         self.hovered_segment = None;
         if old_hovered.is_some() {
-            self.render().ok();
+            self.refresh();
         }
-        serde_wasm_bindgen::to_value(&HitTestResult::miss()).unwrap()
+        HitTestResult::miss_value()
     }
 
     /// Get overall progress statistics
     pub fn get_stats(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.stats()).unwrap()
+    }
+
+    fn stats(&self) -> serde_json::Value {
         let total_completed: u32 = self.segments.iter().map(|s| s.completed).sum();
         let total_items: u32 = self.segments.iter().map(|s| s.total).sum();
 
-        let stats = serde_json::json!({
+        serde_json::json!({
             "totalCompleted": total_completed,
             "totalItems": total_items,
             "overallPercentage": if total_items > 0 {
@@ -326,17 +651,103 @@ impl ProgressTrackerChart {
                 0.0
             },
             "segmentCount": self.segments.len(),
+            "hiddenSegments": if self.hide_empty_segments {
+                self.segments.iter().filter(|s| s.total == 0).count()
+            } else {
+                0
+            },
+            "overCommitted": self.segments.iter().any(|s| s.completed > s.total),
             "segments": self.segments.iter().map(|s| {
                 serde_json::json!({
                     "id": s.id,
                     "label": s.label,
                     "completed": s.completed,
                     "total": s.total,
-                    "percentage": (s.completed as f64 / s.total.max(1) as f64) * 100.0
+                    "percentage": (s.completed as f64 / s.total.max(1) as f64) * 100.0,
+                    "overCommitted": s.completed > s.total
                 })
             }).collect::<Vec<_>>()
-        });
-        serde_wasm_bindgen::to_value(&stats).unwrap()
+        })
+    }
+
+    /// One-sentence summary for screen readers (e.g. a canvas `aria-label`)
+    pub fn describe(&self) -> String {
+        let total_completed: u32 = self.segments.iter().map(|s| s.completed).sum();
+        let total_items: u32 = self.segments.iter().map(|s| s.total).sum();
+        let fraction = if total_items > 0 { total_completed as f64 / total_items as f64 } else { 0.0 };
+
+        let mut text = format!(
+            "Progress {} of {} complete ({})",
+            total_completed,
+            total_items,
+            format_percent(fraction, 0)
+        );
+        if !self.segments.is_empty() {
+            let parts: Vec<String> = self.segments.iter()
+                .map(|s| format!("{} {} of {}", s.label, s.completed, s.total))
+                .collect();
+            text.push_str(&format!("; {}", parts.join(", ")));
+        }
+        text
+    }
+}
+
+/// Center text of a simple progress chart: "value/max" or the clamped percentage
+fn simple_progress_text(value: f64, max_value: f64, options: &SimpleProgressOptions) -> String {
+    if options.show_raw {
+        format!("{:.*}/{:.*}", options.decimals, value, options.decimals, max_value)
+    } else {
+        format_percent((value / max_value).clamp(0.0, 1.0), options.decimals)
+    }
+}
+
+/// Format a completion fraction as a percentage, rounded to `decimals` but
+/// capped just below 100% so an unfinished value never displays as complete
+fn format_percent(fraction: f64, decimals: usize) -> String {
+    if fraction >= 1.0 {
+        return format!("{:.*}%", decimals, fraction * 100.0);
+    }
+    let factor = 10f64.powi(decimals as i32);
+    let rounded = ((fraction * 100.0 * factor).round() / factor).min(100.0 - 1.0 / factor);
+    format!("{:.*}%", decimals, rounded)
+}
+
+/// Trend of a history series: 1 if the last point is above the first, -1 if below, else 0
+fn trend_direction(points: &[f64]) -> i8 {
+    match (points.first(), points.last()) {
+        (Some(first), Some(last)) if points.len() >= 2 => {
+            if last > first {
+                1
+            } else if last < first {
+                -1
+            } else {
+                0
+            }
+        }
+        _ => 0,
+    }
+}
+
+/// Display options for `render_simple_progress_with_options`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimpleProgressOptions {
+    /// Decimal places for the center value
+    pub decimals: usize,
+    /// Show "value/max" instead of a percentage
+    pub show_raw: bool,
+    pub text_color: String,
+    pub label_color: String,
+}
+
+impl Default for SimpleProgressOptions {
+    fn default() -> Self {
+        Self {
+            decimals: 0,
+            show_raw: false,
+            text_color: "#1F2937".to_string(),
+            label_color: "#6B7280".to_string(),
+        }
     }
 }
 
@@ -348,12 +759,56 @@ pub fn render_simple_progress(
     max_value: f64,
     label: &str,
     color: &str,
+) -> Result<(), JsValue> {
+    draw_simple_progress(canvas_id, value, max_value, label, color, &SimpleProgressOptions::default())
+}
+
+/// Create a simple radial progress chart with display options
+#[wasm_bindgen]
+pub fn render_simple_progress_with_options(
+    canvas_id: &str,
+    value: f64,
+    max_value: f64,
+    label: &str,
+    color: &str,
+    options_js: JsValue,
+) -> Result<(), JsValue> {
+    let options: SimpleProgressOptions = serde_wasm_bindgen::from_value(options_js)
+        .unwrap_or_default();
+    draw_simple_progress(canvas_id, value, max_value, label, color, &options)
+}
+
+fn draw_simple_progress(
+    canvas_id: &str,
+    value: f64,
+    max_value: f64,
+    label: &str,
+    color: &str,
+    options: &SimpleProgressOptions,
 ) -> Result<(), JsValue> {
     let (canvas, ctx) = get_canvas_context(canvas_id)?;
     let width = canvas.width() as f64;
     let height = canvas.height() as f64;
 
-    clear_canvas(&ctx, width, height, "#FFFFFF");
+    if width < MIN_RENDER_SIZE || height < MIN_RENDER_SIZE {
+        return Ok(());
+    }
+
+    draw_simple_progress_to(&ctx, width, height, value, max_value, label, color, options)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw_simple_progress_to<T: DrawTarget + ?Sized>(
+    ctx: &T,
+    width: f64,
+    height: f64,
+    value: f64,
+    max_value: f64,
+    label: &str,
+    color: &str,
+    options: &SimpleProgressOptions,
+) -> Result<(), JsValue> {
+    clear_canvas(ctx, width, height, "#FFFFFF");
 
     let center_x = width / 2.0;
     let center_y = height / 2.0;
@@ -361,7 +816,7 @@ pub fn render_simple_progress(
     let line_width = radius * 0.15;
 
     // Background arc
-    ctx.set_stroke_style(&JsValue::from_str("#E5E7EB"));
+    ctx.set_stroke_style("#E5E7EB");
     ctx.set_line_width(line_width);
     ctx.set_line_cap("round");
     ctx.begin_path();
@@ -371,21 +826,191 @@ pub fn render_simple_progress(
     // Progress arc
     let progress = (value / max_value).min(1.0).max(0.0);
     let end_angle = -PI / 2.0 + progress * 2.0 * PI;
-    ctx.set_stroke_style(&JsValue::from_str(color));
+    ctx.set_stroke_style(color);
     ctx.begin_path();
     ctx.arc(center_x, center_y, radius - line_width / 2.0, -PI / 2.0, end_angle)?;
     ctx.stroke();
 
     // Center text
-    ctx.set_fill_style(&JsValue::from_str("#1F2937"));
+    ctx.set_fill_style(&options.text_color);
     ctx.set_font(&format!("bold {}px Inter, system-ui, sans-serif", radius * 0.4));
     ctx.set_text_align("center");
     ctx.set_text_baseline("middle");
-    ctx.fill_text(&format!("{:.0}%", progress * 100.0), center_x, center_y - 5.0)?;
+    ctx.fill_text(&simple_progress_text(value, max_value, options), center_x, center_y - 5.0)?;
 
     ctx.set_font(&format!("{}px Inter, system-ui, sans-serif", radius * 0.2));
-    ctx.set_fill_style(&JsValue::from_str("#6B7280"));
+    ctx.set_fill_style(&options.label_color);
     ctx.fill_text(label, center_x, center_y + radius * 0.25)?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::charts::RecordingTarget;
+
+    fn segment(label: &str, completed: u32, total: u32) -> ProgressSegment {
+        ProgressSegment {
+            id: label.to_lowercase(),
+            label: label.to_string(),
+            completed,
+            total,
+            color: None,
+        }
+    }
+
+    fn chart_with(segments: Vec<ProgressSegment>) -> ProgressTrackerChart {
+        let mut chart = ProgressTrackerChart::with_config("test", ChartConfig::default());
+        chart.deterministic = true;
+        chart.load_segments(segments);
+        chart
+    }
+
+    #[test]
+    fn trend_direction_compares_last_with_first() {
+        assert_eq!(trend_direction(&[10.0, 5.0, 40.0]), 1);
+        assert_eq!(trend_direction(&[40.0, 90.0, 20.0]), -1);
+        assert_eq!(trend_direction(&[30.0, 10.0, 30.0]), 0);
+    }
+
+    #[test]
+    fn trend_direction_is_flat_without_two_points() {
+        assert_eq!(trend_direction(&[]), 0);
+        assert_eq!(trend_direction(&[50.0]), 0);
+    }
+
+    #[test]
+    fn format_percent_never_rounds_unfinished_work_up_to_complete() {
+        assert_eq!(format_percent(0.9995, 1), "99.9%");
+        assert_eq!(format_percent(0.9995, 0), "99%");
+        assert_eq!(format_percent(0.29, 0), "29%");
+        assert_eq!(format_percent(0.9949, 1), "99.5%");
+    }
+
+    #[test]
+    fn simple_progress_text_follows_the_options() {
+        let one_decimal = SimpleProgressOptions { decimals: 1, ..Default::default() };
+        assert_eq!(simple_progress_text(2.0, 3.0, &one_decimal), "66.7%");
+        assert_eq!(simple_progress_text(2.0, 3.0, &SimpleProgressOptions::default()), "67%");
+
+        let raw = SimpleProgressOptions { decimals: 1, show_raw: true, ..Default::default() };
+        assert_eq!(simple_progress_text(2.0, 3.0, &raw), "2.0/3.0");
+    }
+
+    #[test]
+    fn format_percent_shows_complete_work_as_100() {
+        let (completed, total) = (40u32, 40u32);
+        assert_eq!(format_percent(completed as f64 / total as f64, 1), "100.0%");
+        assert_eq!(format_percent(1.0, 0), "100%");
+    }
+
+    #[test]
+    fn describe_totals_segments_in_order() {
+        let chart = chart_with(vec![segment("Reviewers A", 10, 20), segment("Reviewers B", 20, 20)]);
+        assert_eq!(
+            chart.describe(),
+            "Progress 30 of 40 complete (75%); Reviewers A 10 of 20, Reviewers B 20 of 20"
+        );
+    }
+
+    #[test]
+    fn over_committed_segment_fills_its_arc_and_is_flagged() {
+        let chart = chart_with(vec![segment("Reviewers", 12, 10)]);
+        let target = RecordingTarget::new();
+        chart.draw_donut(&target).unwrap();
+
+        // Background and completed arcs both sweep the full circle, no further
+        let outer_ends: Vec<f64> = target.calls().iter()
+            .filter(|c| c.op == "arc" && c.args[3] == -PI / 2.0)
+            .map(|c| c.args[4])
+            .collect();
+        assert_eq!(outer_ends, vec![1.5 * PI, 1.5 * PI]);
+        // Overflow tick in the danger color
+        let danger = Some(chart.config.theme.danger.as_str());
+        assert!(target.calls().iter().any(|c| c.op == "set_stroke_style" && c.text.as_deref() == danger));
+
+        let stats = chart.stats();
+        assert_eq!(stats["overCommitted"], true);
+        assert_eq!(stats["segments"][0]["overCommitted"], true);
+        assert_eq!(chart_with(vec![segment("Reviewers", 10, 10)]).stats()["overCommitted"], false);
+    }
+
+    #[test]
+    fn hidden_empty_segments_leave_the_donut_legend_and_colors_untouched() {
+        let mut chart = chart_with(vec![segment("Alpha", 5, 10), segment("Empty", 0, 0), segment("Beta", 3, 10)]);
+        chart.hide_empty_segments = true;
+        let hidden = RecordingTarget::new();
+        chart.draw_to(&hidden).unwrap();
+
+        // Drawn exactly as if the empty segment had never been sent
+        let without = chart_with(vec![segment("Alpha", 5, 10), segment("Beta", 3, 10)]);
+        let expected = RecordingTarget::new();
+        without.draw_to(&expected).unwrap();
+        assert_eq!(hidden.calls(), expected.calls());
+        assert!(!hidden.texts().contains(&"Empty".to_string()));
+        assert_eq!(chart.stats()["hiddenSegments"], 1);
+
+        chart.hide_empty_segments = false;
+        let shown = RecordingTarget::new();
+        chart.draw_to(&shown).unwrap();
+        assert!(shown.texts().contains(&"Empty".to_string()));
+    }
+
+    #[test]
+    fn start_angle_shifts_which_segment_an_angle_hits() {
+        let mut chart = chart_with(vec![segment("Alpha", 1, 10), segment("Beta", 1, 10)]);
+        // Lower left of the center, 135° clockwise from 3 o'clock
+        let angle = 1.0f64.atan2(-1.0);
+
+        // Default start at the top: 225° into the sweep, in the second half
+        assert_eq!(chart.segment_at_angle(angle), Some(1));
+
+        // Starting 90° later at 3 o'clock puts it 135° in, in the first half
+        chart.start_angle = 0.0;
+        assert_eq!(chart.segment_at_angle(angle), Some(0));
+
+        // Sweeping the other way from 3 o'clock it is 225° in again
+        chart.clockwise = false;
+        assert_eq!(chart.segment_at_angle(angle), Some(1));
+    }
+
+    #[test]
+    fn segment_labels_skip_sweeps_below_the_threshold() {
+        // The 1-of-51 segment sweeps ~0.12 rad, under MIN_LABEL_SWEEP
+        let mut chart = chart_with(vec![segment("Large", 25, 50), segment("Tiny", 1, 1)]);
+        chart.animation_progress = 1.0;
+
+        for follow_arc in [false, true] {
+            chart.rotate_segment_labels = follow_arc;
+            let target = RecordingTarget::new();
+            chart.draw_segment_labels(&target, 400.0, 200.0, 100.0, 51.0).unwrap();
+            assert_eq!(target.texts(), vec!["50%"]);
+        }
+    }
+
+    #[test]
+    fn long_legend_label_wraps_and_pushes_its_count_down() {
+        let chart = chart_with(vec![segment("Done", 1, 2), segment("Panel reviewers", 3, 4)]);
+        let target = RecordingTarget::new();
+        chart.draw_legend(&target).unwrap();
+
+        let texts: Vec<(String, f64)> = target
+            .calls()
+            .iter()
+            .filter(|c| c.op == "fill_text")
+            .map(|c| (c.text.clone().unwrap(), c.args[1]))
+            .collect();
+        assert_eq!(texts.len(), 5);
+        let line_height = chart.config.legend_size() + 3.0;
+
+        // Short label: one line, count on the same baseline
+        assert_eq!(texts[0].0, "Done");
+        assert_eq!(texts[1], ("1/2".to_string(), texts[0].1));
+
+        // Long label: two lines, count level with the second
+        assert_eq!(format!("{} {}", texts[2].0, texts[3].0), "Panel reviewers");
+        assert_eq!(texts[3].1, texts[2].1 + line_height);
+        assert_eq!(texts[4], ("3/4".to_string(), texts[3].1));
+    }
+}
@@ -5,9 +5,19 @@
 
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
-use web_sys::CanvasRenderingContext2d;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+use std::cell::Cell;
 
-use super::common::{get_canvas_context, clear_canvas, draw_grid, ChartConfig, HitTestResult};
+use super::draw_target::DrawTarget;
+use super::common::{get_canvas_context, create_offscreen_canvas, track_context_loss, surface_context_loss, clear_canvas, truncate_to_width, draw_grid, payload_hash, is_same_payload, draw_tick_label, rotated_label_height, render_image_data, now_ms, RenderTiming, ChartConfig, SparklineBase, ColorTheme, HitTestResult, ValueFormatters, ValueLabelMode, DEFAULT_VALUE_LABEL_MIN_HEIGHT};
+
+/// Gap between the x-axis and its tick labels
+const X_TICK_GAP: f64 = 10.0;
+/// Bins used by chunked loads until `set_bin_count` or `set_data` picks another count
+const DEFAULT_BIN_COUNT: u32 = 10;
+/// References listed in the in-canvas hover tooltip, and the tooltip's width
+const TOOLTIP_REFERENCES: usize = 3;
+const TOOLTIP_WIDTH: f64 = 140.0;
 
 /// Score data point for a single application
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -18,6 +28,8 @@ pub struct ScoreDataPoint {
     pub max_score: f64,
     pub assessor_count: u32,
     pub variance: Option<f64>,
+    #[serde(default)]
+    pub metadata: Option<serde_json::Value>,
 }
 
 /// Histogram bin with aggregated data
@@ -27,7 +39,28 @@ struct HistogramBin {
     max: f64,
     count: u32,
     applications: Vec<String>,
+    // Parallel to `applications`
+    references: Vec<String>,
+    scores: Vec<f64>,
+    variances: Vec<Option<f64>>,
+    // Running sum behind `avg_variance`, so finalizing twice gives the same average
+    variance_sum: f64,
     avg_variance: f64,
+    // Bar value: the count, or the sum of the aggregate metadata key
+    total: f64,
+}
+
+/// Order of the applications listed for each bin
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BinSort {
+    /// Dataset order
+    Insertion,
+    /// Highest score first
+    Score,
+    /// Reference, ascending
+    Reference,
+    /// Highest variance first; applications without variance last
+    Variance,
 }
 
 /// Score distribution chart state (kept between renders for interactivity)
@@ -36,10 +69,41 @@ pub struct ScoreDistributionChart {
     canvas_id: String,
     config: ChartConfig,
     bins: Vec<HistogramBin>,
+    // Raw points kept so bins can be rebuilt at a different count
+    points: Vec<ScoreDataPoint>,
+    requested_bins: u32,
+    // Minimum bar width for auto-binning (0 = off)
+    min_bar_px: f64,
     total_count: u32,
+    // Applications left out of the bins because no assessor has scored them
+    exclude_unscored: bool,
+    excluded_count: u32,
     max_count: u32,
+    // Largest bin total; the y-axis scale
+    max_value: f64,
+    // Metadata key summed per bin; None counts applications
+    aggregate_key: Option<String>,
     score_range: (f64, f64),
     hovered_bin: Option<usize>,
+    hover_effect: bool,
+    // Draw the hovered bin's top references next to the bar
+    hover_tooltip: bool,
+    bin_sort: BinSort,
+    value_labels: ValueLabelMode,
+    min_bar_height: f64,
+    // Reference line at a target score percentage
+    target_score: Option<f64>,
+    target_label: String,
+    formatters: ValueFormatters,
+    // Settings sparkline mode overrode, while it is on
+    sparkline_base: Option<SparklineBase>,
+    x_label_rotation: f64,
+    // Configured bottom padding before growing it for rotated labels
+    base_padding_bottom: f64,
+    // Fingerprint of the last loaded payload
+    data_hash: Option<u64>,
+    render_timing: RenderTiming,
+    context_lost: Cell<bool>,
 }
 
 #[wasm_bindgen]
@@ -49,41 +113,95 @@ impl ScoreDistributionChart {
     pub fn new(canvas_id: &str, config_js: JsValue) -> Result<ScoreDistributionChart, JsValue> {
         let config: ChartConfig = serde_wasm_bindgen::from_value(config_js)
             .unwrap_or_else(|_| ChartConfig::default());
+        Ok(Self::with_config(canvas_id, config))
+    }
+
+    /// Create a chart from an already-parsed config
+    pub(crate) fn with_config(canvas_id: &str, config: ChartConfig) -> Self {
+        let base_padding_bottom = config.padding.bottom;
 
-        Ok(Self {
+        Self {
             canvas_id: canvas_id.to_string(),
             config,
             bins: Vec::new(),
+            points: Vec::new(),
+            requested_bins: DEFAULT_BIN_COUNT,
+            min_bar_px: 0.0,
             total_count: 0,
+            exclude_unscored: false,
+            excluded_count: 0,
             max_count: 0,
+            max_value: 0.0,
+            aggregate_key: None,
             score_range: (0.0, 100.0),
             hovered_bin: None,
-        })
+            hover_effect: true,
+            hover_tooltip: false,
+            bin_sort: BinSort::Insertion,
+            value_labels: ValueLabelMode::Auto(DEFAULT_VALUE_LABEL_MIN_HEIGHT),
+            min_bar_height: 0.0,
+            target_score: None,
+            target_label: String::new(),
+            formatters: ValueFormatters::default(),
+            sparkline_base: None,
+            x_label_rotation: 0.0,
+            base_padding_bottom,
+            data_hash: None,
+            render_timing: RenderTiming::default(),
+            context_lost: Cell::new(false),
+        }
     }
 
     /// Update chart data and recalculate bins
     pub fn set_data(&mut self, data_js: JsValue, bin_count: u32) -> Result<(), JsValue> {
+        self.set_data_with_options(data_js, bin_count, false)
+    }
+
+    /// Update chart data and recalculate bins; identical payloads are skipped unless `force` is set
+    pub fn set_data_with_options(&mut self, data_js: JsValue, bin_count: u32, force: bool) -> Result<(), JsValue> {
+        let hash = payload_hash(&[&data_js, &JsValue::from(bin_count)]);
+        if is_same_payload(hash, self.data_hash, force) {
+            return Ok(());
+        }
+
+        self.load_data(data_js, bin_count)?;
+        self.data_hash = hash;
+        Ok(())
+    }
+
+    fn load_data(&mut self, data_js: JsValue, bin_count: u32) -> Result<(), JsValue> {
         let data: Vec<ScoreDataPoint> = serde_wasm_bindgen::from_value(data_js)?;
 
         if data.is_empty() {
             self.bins.clear();
+            self.points.clear();
             self.total_count = 0;
+            self.excluded_count = 0;
             self.max_count = 0;
+            self.max_value = 0.0;
             return Ok(());
         }
 
-        // Calculate score range from data
-        let min_score = data.iter().map(|d| d.score).fold(f64::INFINITY, f64::min);
-        let max_score = data.iter().map(|d| d.score).fold(f64::NEG_INFINITY, f64::max);
+        self.requested_bins = bin_count.max(1);
+        self.begin_data();
+        self.ingest(&data);
+        self.finalize_data();
 
-        // Normalize to percentage if max_score varies
-        let normalized: Vec<(f64, &ScoreDataPoint)> = data.iter()
-            .map(|d| {
-                let pct = if d.max_score > 0.0 { (d.score / d.max_score) * 100.0 } else { 0.0 };
-                (pct, d)
-            })
-            .collect();
+        Ok(())
+    }
+
+    /// Start a chunked load, resetting to empty bins at the current bin count
+    /// (see `set_bin_count`). Feed chunks with `append_data` and call
+    /// `finalize_data` once they are all in.
+    pub fn begin_data(&mut self) {
+        self.points.clear();
+        self.reset_bins();
+        self.data_hash = None;
+    }
 
+    /// Rebuild empty bins at the effective count
+    fn reset_bins(&mut self) {
+        let bin_count = self.effective_bin_count();
         self.score_range = (0.0, 100.0);
         let bin_width = 100.0 / bin_count as f64;
 
@@ -94,77 +212,493 @@ impl ScoreDistributionChart {
                 max: (i + 1) as f64 * bin_width,
                 count: 0,
                 applications: Vec::new(),
+                references: Vec::new(),
+                scores: Vec::new(),
+                variances: Vec::new(),
+                variance_sum: 0.0,
                 avg_variance: 0.0,
+                total: 0.0,
             })
             .collect();
 
-        // Distribute data into bins
-        for (pct, point) in &normalized {
-            let bin_idx = ((pct / bin_width).floor() as usize).min(bin_count as usize - 1);
-            self.bins[bin_idx].count += 1;
-            self.bins[bin_idx].applications.push(point.application_id.clone());
-            if let Some(v) = point.variance {
-                self.bins[bin_idx].avg_variance += v;
-            }
+        self.total_count = 0;
+        self.excluded_count = 0;
+        self.max_count = 0;
+        self.max_value = 0.0;
+    }
+
+    /// Set the requested number of bins, re-binning any current data
+    pub fn set_bin_count(&mut self, bin_count: u32) {
+        self.requested_bins = bin_count.max(1);
+        self.rebin();
+        self.refresh();
+    }
+
+    /// Requested bin count, capped so each bar is at least `min_bar_px` wide
+    fn effective_bin_count(&self) -> u32 {
+        if self.min_bar_px <= 0.0 {
+            return self.requested_bins.max(1);
         }
+        let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
+        let max_bins = (plot_width / self.min_bar_px).floor().max(1.0) as u32;
+        self.requested_bins.clamp(1, max_bins)
+    }
 
+    /// Re-bin the retained points at the current effective count
+    fn rebin(&mut self) {
+        if self.bins.is_empty() || self.bins.len() as u32 == self.effective_bin_count() {
+            return;
+        }
+        self.rebuild_bins();
+    }
+
+    /// Re-bin all retained points from scratch
+    fn rebuild_bins(&mut self) {
+        let points = std::mem::take(&mut self.points);
+        self.reset_bins();
+        self.ingest(&points);
+        self.finalize_data();
+        self.hovered_bin = None;
+    }
+
+    /// Bin a chunk of data points (call between `begin_data` and `finalize_data`)
+    pub fn append_data(&mut self, chunk_js: JsValue) -> Result<(), JsValue> {
+        let chunk: Vec<ScoreDataPoint> = serde_wasm_bindgen::from_value(chunk_js)?;
+        self.ingest(&chunk);
+        Ok(())
+    }
+
+    /// Compute per-bin averages and maxima once all chunks are loaded
+    pub fn finalize_data(&mut self) {
         // Calculate averages
         for bin in &mut self.bins {
             if bin.count > 0 {
-                bin.avg_variance /= bin.count as f64;
+                bin.avg_variance = bin.variance_sum / bin.count as f64;
             }
         }
 
-        self.total_count = data.len() as u32;
         self.max_count = self.bins.iter().map(|b| b.count).max().unwrap_or(0);
+        self.max_value = self.bins.iter().map(|b| b.total).fold(0.0, f64::max);
+    }
+
+    /// Distribute data into bins, accumulating variance sums for `finalize_data`
+    fn ingest(&mut self, data: &[ScoreDataPoint]) {
+        if self.bins.is_empty() {
+            return;
+        }
+
+        let bin_count = self.bins.len();
+        let bin_width = 100.0 / bin_count as f64;
+
+        for point in data {
+            // A zero score with no assessors means "not scored yet", not "scored zero"
+            if self.exclude_unscored && point.assessor_count == 0 {
+                self.excluded_count += 1;
+                continue;
+            }
+
+            // Normalize to percentage if max_score varies
+            let pct = if point.max_score > 0.0 { (point.score / point.max_score) * 100.0 } else { 0.0 };
+            let bin_idx = ((pct / bin_width).floor() as usize).min(bin_count - 1);
+
+            self.bins[bin_idx].count += 1;
+            self.bins[bin_idx].total += self.aggregate_value(point);
+            self.bins[bin_idx].applications.push(point.application_id.clone());
+            self.bins[bin_idx].references.push(point.reference.clone());
+            self.bins[bin_idx].scores.push(pct);
+            self.bins[bin_idx].variances.push(point.variance);
+            if let Some(v) = point.variance {
+                self.bins[bin_idx].variance_sum += v;
+            }
+        }
 
+        self.points.extend_from_slice(data);
+        self.total_count = self.bins.iter().map(|b| b.count).sum();
+    }
+
+    /// What a point adds to its bin's total: 1, or its aggregate metadata value
+    fn aggregate_value(&self, point: &ScoreDataPoint) -> f64 {
+        match &self.aggregate_key {
+            None => 1.0,
+            Some(key) => point.metadata.as_ref()
+                .and_then(|m| m.get(key))
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.0),
+        }
+    }
+
+    /// Leave applications with no assessors out of the bins, counting them
+    /// separately (`excludedUnscored` in `get_stats`)
+    pub fn set_exclude_unscored(&mut self, enabled: bool) {
+        self.exclude_unscored = enabled;
+        if !self.bins.is_empty() {
+            self.rebuild_bins();
+        }
+        self.refresh();
+    }
+
+    /// Sum a numeric metadata key per bin (e.g. requested funding) instead of
+    /// counting applications; `None` restores counting
+    pub fn set_bin_aggregate(&mut self, key: Option<String>) {
+        self.aggregate_key = key.filter(|k| !k.is_empty());
+        if !self.bins.is_empty() {
+            self.rebuild_bins();
+        }
+        self.refresh();
+    }
+
+    /// Bar label / axis text for a bin total: the "count" format, or the
+    /// "total" format (default `#,##0`) when summing a metadata key
+    fn format_total(&self, value: f64) -> String {
+        match self.aggregate_key {
+            None => self.formatters.number("count", value, "0"),
+            Some(_) => self.formatters.number("total", value, "#,##0"),
+        }
+    }
+
+    /// Cap the bin count so each bar is at least `min_bar_px` wide, re-binning
+    /// the current data (0 = use the requested count as-is)
+    pub fn set_auto_bin_to_width(&mut self, min_bar_px: f64) {
+        self.min_bar_px = min_bar_px.max(0.0);
+        self.rebin();
+        self.refresh();
+    }
+
+    /// Draw a dashed, labeled reference line at `score_pct` (0-100 on the x-axis)
+    pub fn set_target_score(&mut self, score_pct: f64, label: &str) {
+        self.target_score = Some(score_pct.clamp(0.0, 100.0));
+        self.target_label = label.to_string();
+        self.refresh();
+    }
+
+    /// Remove the target score line
+    pub fn clear_target_score(&mut self) {
+        self.target_score = None;
+        self.refresh();
+    }
+
+    /// Count labels above bars: "auto" (bars taller than `min_height`, default 20px),
+    /// "always" or "never"
+    pub fn set_value_labels(&mut self, mode: &str, min_height: Option<f64>) -> Result<(), JsValue> {
+        self.value_labels = ValueLabelMode::parse(mode, min_height)?;
+        self.render()
+    }
+
+    /// Draw any nonzero bar at least `px` tall so tiny counts stay visible;
+    /// the axis scale is unchanged and empty bars stay empty
+    pub fn set_min_bar_height(&mut self, px: f64) {
+        self.min_bar_height = px.max(0.0);
+        self.refresh();
+    }
+
+    /// Order of each bin's application list in hover data and `get_histogram`:
+    /// "score" (highest first), "reference", "variance" (highest first) or "none"
+    pub fn set_bin_sort(&mut self, key: &str) -> Result<(), JsValue> {
+        self.bin_sort = match key {
+            "none" => BinSort::Insertion,
+            "score" => BinSort::Score,
+            "reference" => BinSort::Reference,
+            "variance" => BinSort::Variance,
+            _ => return Err(JsValue::from_str(&format!("Unknown bin sort: {}", key))),
+        };
         Ok(())
     }
 
+    /// Indices into a bin's members in the configured sort order
+    fn member_order(&self, bin: &HistogramBin) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..bin.applications.len()).collect();
+        match self.bin_sort {
+            BinSort::Insertion => {}
+            BinSort::Score => order.sort_by(|&a, &b| bin.scores[b].total_cmp(&bin.scores[a])),
+            BinSort::Reference => order.sort_by(|&a, &b| bin.references[a].cmp(&bin.references[b])),
+            BinSort::Variance => order.sort_by(|&a, &b| {
+                let va = bin.variances[a].unwrap_or(f64::NEG_INFINITY);
+                let vb = bin.variances[b].unwrap_or(f64::NEG_INFINITY);
+                vb.total_cmp(&va)
+            }),
+        }
+        order
+    }
+
+    /// Enable or disable the hover highlight on bars
+    pub fn set_hover_effect(&mut self, enabled: bool) {
+        self.hover_effect = enabled;
+        self.refresh();
+    }
+
+    /// Show the hovered bin's top references in a tooltip drawn on the canvas
+    pub fn set_hover_tooltip(&mut self, enabled: bool) {
+        self.hover_tooltip = enabled;
+        self.refresh();
+    }
+
+    /// Toggle sparkline mode: only the data shape, filling the full canvas
+    pub fn set_sparkline(&mut self, enabled: bool) {
+        self.apply_sparkline(enabled);
+        self.refresh();
+    }
+
+    fn apply_sparkline(&mut self, enabled: bool) {
+        if enabled {
+            if self.sparkline_base.is_none() {
+                self.sparkline_base = Some(self.config.enter_sparkline());
+            }
+        } else if let Some(base) = self.sparkline_base.take() {
+            self.config.exit_sparkline(base);
+            self.update_label_padding();
+        }
+        self.rebin();
+    }
+
+    /// Rotate x-axis tick labels by `degrees` (0 = horizontal); bottom padding
+    /// grows to fit the rotated labels
+    pub fn set_x_label_rotation(&mut self, degrees: f64) {
+        self.x_label_rotation = degrees;
+        self.update_label_padding();
+        self.refresh();
+    }
+
+    /// Grow bottom padding to fit rotated tick labels, never shrinking below the configured value.
+    /// Text is measured on the chart's canvas, or a scratch canvas before it is mounted.
+    fn update_label_padding(&mut self) {
+        if self.x_label_rotation == 0.0 {
+            // Horizontal labels fit the configured padding without measuring
+            if self.sparkline_base.is_none() {
+                self.config.padding.bottom = self.base_padding_bottom;
+            }
+            return;
+        }
+
+        let measured = get_canvas_context(&self.canvas_id).or_else(|_| create_offscreen_canvas(1, 1));
+        if let Ok((_, ctx)) = measured {
+            self.fit_label_padding(&ctx);
+        }
+    }
+
+    fn fit_label_padding<T: DrawTarget + ?Sized>(&mut self, ctx: &T) {
+        if self.sparkline_base.is_some() {
+            return;
+        }
+
+        let mut bottom = self.base_padding_bottom;
+        if self.x_label_rotation != 0.0 {
+            let font_size = self.config.axis_size();
+            ctx.set_font(&self.config.font("", font_size));
+            let labels: Vec<String> = self.x_tick_labels().into_iter().map(|(_, label)| label).collect();
+            // Tick gap above the labels, axis title below them
+            bottom = bottom.max(X_TICK_GAP + rotated_label_height(ctx, &labels, font_size, self.x_label_rotation) + 25.0);
+        }
+        self.config.padding.bottom = bottom;
+    }
+
+    /// Set the display format for a value kind used in tooltips and overlay text.
+    ///
+    /// Numbers accept specs like `"0.0"` or `"#,##0"`; timestamps accept date
+    /// patterns like `"YYYY-MM-DD HH:mm"`. An empty format restores the default.
+    pub fn set_value_formatter(&mut self, kind: &str, format: &str) {
+        self.formatters.set(kind, format);
+        self.update_label_padding();
+        self.refresh();
+    }
+
+    /// Replace the color theme (a `ColorTheme` object) and re-render
+    pub fn set_theme(&mut self, theme_js: JsValue) -> Result<(), JsValue> {
+        let theme: ColorTheme = serde_wasm_bindgen::from_value(theme_js)?;
+        self.config.theme = theme;
+        self.render()
+    }
+
+    /// Whether the last render failed because the 2D context was lost
+    pub fn is_context_lost(&self) -> bool {
+        self.context_lost.get()
+    }
+
+    /// Re-acquire the canvas context after a loss and re-render
+    pub fn restore(&mut self) -> Result<(), JsValue> {
+        self.context_lost.set(false);
+        self.render()
+    }
+
+    /// Re-render after a state change, warning once if the context was lost
+    fn refresh(&self) {
+        surface_context_loss(&self.context_lost, &self.canvas_id, || self.render());
+    }
+
     /// Render the chart to canvas
     pub fn render(&self) -> Result<(), JsValue> {
+        if !self.config.has_drawable_size() {
+            return Ok(());
+        }
+
         let (canvas, ctx) = get_canvas_context(&self.canvas_id)?;
+        let start = now_ms();
+        let result = track_context_loss(&self.context_lost, &ctx, || self.draw_frame(&canvas, &ctx));
+        self.render_timing.record(now_ms() - start);
+        result
+    }
+
+    /// Duration of the last render in milliseconds
+    pub fn last_render_ms(&self) -> f64 {
+        self.render_timing.last()
+    }
+
+    /// Average render duration over recent renders in milliseconds
+    pub fn avg_render_ms(&self) -> f64 {
+        self.render_timing.average()
+    }
+
+    /// Render offscreen and return raw RGBA bytes for pixel-level regression tests
+    pub fn render_to_image_data(&self, width: u32, height: u32) -> Result<Vec<u8>, JsValue> {
+        render_image_data(&self.config, width, height, |canvas, ctx| self.draw_frame(canvas, ctx))
+    }
+
+    fn draw_frame(&self, canvas: &HtmlCanvasElement, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
 
         // Set canvas size
         canvas.set_width(self.config.width as u32);
         canvas.set_height(self.config.height as u32);
 
+        self.draw_to(ctx)
+    }
+
+    /// Draw the full chart onto any target; the canvas must already be sized
+    pub(crate) fn draw_to<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
         // Clear background
-        clear_canvas(&ctx, self.config.width, self.config.height, &self.config.theme.background);
+        clear_canvas(ctx, self.config.width, self.config.height, &self.config.theme.background);
 
         // Draw grid if enabled
         if self.config.show_grid {
-            draw_grid(&ctx, &self.config, self.bins.len() as u32, 5);
+            draw_grid(ctx, &self.config, self.bins.len() as u32, 5);
         }
 
         // Draw bars
-        self.draw_bars(&ctx)?;
+        self.draw_bars(ctx)?;
+
+        if let Some(score) = self.target_score {
+            self.draw_target_line(ctx, score)?;
+        }
 
         // Draw axes
-        self.draw_axes(&ctx)?;
+        if self.sparkline_base.is_none() {
+            self.draw_axes(ctx)?;
+        }
 
         // Draw title and legend
         if self.config.show_labels {
-            self.draw_labels(&ctx)?;
+            self.draw_labels(ctx)?;
+        }
+
+        if let Some(bin) = self.hovered_bin.filter(|_| self.hover_tooltip) {
+            self.draw_tooltip(ctx, bin)?;
         }
 
         Ok(())
     }
 
-    fn draw_bars(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
-        if self.bins.is_empty() || self.max_count == 0 {
+    /// Tooltip beside the hovered bar listing its first references in the
+    /// configured member order, each cut to the tooltip width
+    fn draw_tooltip<T: DrawTarget + ?Sized>(&self, ctx: &T, bin_idx: usize) -> Result<(), JsValue> {
+        let Some(bin) = self.bins.get(bin_idx) else {
+            return Ok(());
+        };
+        if bin.references.is_empty() {
             return Ok(());
         }
 
+        let order = self.member_order(bin);
+        let mut lines: Vec<String> = order.iter()
+            .take(TOOLTIP_REFERENCES)
+            .map(|&i| bin.references[i].clone())
+            .collect();
+        if order.len() > TOOLTIP_REFERENCES {
+            lines.push(format!("+{} more", order.len() - TOOLTIP_REFERENCES));
+        }
+
+        let line_height = self.config.axis_size() + 4.0;
+        let height = lines.len() as f64 * line_height + 8.0;
+        let (bar_x, bar_y, bar_width, _) = self.bar_rect(bin_idx);
+        // Right of the bar, flipped left near the edge and kept inside the canvas
+        let mut x = bar_x + bar_width + 6.0;
+        if x + TOOLTIP_WIDTH > self.config.width {
+            x = bar_x - TOOLTIP_WIDTH - 6.0;
+        }
+        let x = x.max(0.0);
+        let y = bar_y.min(self.config.height - height).max(0.0);
+
+        ctx.set_fill_style(&self.config.theme.background);
+        ctx.fill_rect(x, y, TOOLTIP_WIDTH, height);
+        ctx.set_stroke_style(&self.config.theme.grid);
+        ctx.set_line_width(1.0);
+        ctx.stroke_rect(x, y, TOOLTIP_WIDTH, height);
+
+        ctx.set_fill_style(&self.config.theme.text);
+        ctx.set_font(&self.config.font("", self.config.axis_size()));
+        ctx.set_text_align("left");
+        ctx.set_text_baseline("top");
+        for (i, line) in lines.iter().enumerate() {
+            let text = truncate_to_width(ctx, line, TOOLTIP_WIDTH - 12.0);
+            ctx.fill_text(&text, x + 6.0, y + 4.0 + i as f64 * line_height)?;
+        }
+        ctx.set_text_baseline("alphabetic");
+        Ok(())
+    }
+
+    /// Canvas x for a score percentage, matching the x-axis ticks
+    fn score_to_x(&self, score_pct: f64) -> f64 {
+        let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
+        self.config.padding.left + (score_pct / 100.0) * plot_width
+    }
+
+    fn draw_target_line<T: DrawTarget + ?Sized>(&self, ctx: &T, score_pct: f64) -> Result<(), JsValue> {
+        let x = self.score_to_x(score_pct);
+        let top = self.config.padding.top;
+        let bottom = self.config.height - self.config.padding.bottom;
+
+        ctx.set_stroke_style(&self.config.theme.primary);
+        ctx.set_line_width(1.5);
+        ctx.set_line_dash(&[6.0, 4.0])?;
+        ctx.begin_path();
+        ctx.move_to(x, top);
+        ctx.line_to(x, bottom);
+        ctx.stroke();
+        ctx.set_line_dash(&[])?;
+
+        if !self.target_label.is_empty() {
+            ctx.set_fill_style(&self.config.theme.primary);
+            ctx.set_font(&self.config.font("", self.config.axis_size()));
+            ctx.set_text_align("center");
+            ctx.set_text_baseline("bottom");
+            ctx.fill_text(&self.target_label, x, top - 4.0)?;
+            ctx.set_text_baseline("alphabetic");
+        }
+
+        Ok(())
+    }
+
+    /// Left, top, width and height of bin `i`'s bar
+    fn bar_rect(&self, i: usize) -> (f64, f64, f64, f64) {
         let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
         let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
         let bar_width = plot_width / self.bins.len() as f64;
         let bar_gap = 2.0;
 
+        let total = self.bins[i].total;
+        let mut height = if self.max_value > 0.0 { (total / self.max_value) * plot_height } else { 0.0 };
+        if total > 0.0 {
+            height = height.max(self.min_bar_height.min(plot_height));
+        }
+        let x = self.config.padding.left + i as f64 * bar_width + bar_gap / 2.0;
+        let y = self.config.height - self.config.padding.bottom - height;
+        (x, y, bar_width - bar_gap, height)
+    }
+
+    fn draw_bars<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        if self.bins.is_empty() || self.max_value <= 0.0 {
+            return Ok(());
+        }
+
         for (i, bin) in self.bins.iter().enumerate() {
-            let height = (bin.count as f64 / self.max_count as f64) * plot_height;
-            let x = self.config.padding.left + i as f64 * bar_width + bar_gap / 2.0;
-            let y = self.config.height - self.config.padding.bottom - height;
+            let (x, y, bw, height) = self.bar_rect(i);
 
             // Color based on score range (green for high, yellow for mid, red for low)
             let score_pct = (bin.min + bin.max) / 2.0 / 100.0;
@@ -177,14 +711,13 @@ impl ScoreDistributionChart {
             };
 
             // Highlight hovered bin
-            let is_hovered = self.hovered_bin == Some(i);
+            let is_hovered = self.hover_effect && self.hovered_bin == Some(i);
 
-            ctx.set_fill_style(&JsValue::from_str(color));
+            ctx.set_fill_style(color);
             ctx.set_global_alpha(if is_hovered { 1.0 } else { 0.8 });
 
             // Draw rounded rectangle for bar
-            let radius = 4.0;
-            let bw = bar_width - bar_gap;
+            let radius = (height / 2.0).min(4.0);
             ctx.begin_path();
             ctx.move_to(x + radius, y);
             ctx.line_to(x + bw - radius, y);
@@ -196,30 +729,33 @@ impl ScoreDistributionChart {
             ctx.close_path();
             ctx.fill();
 
-            // Draw count label on top of bar
-            if bin.count > 0 && height > 20.0 {
+            // Draw value label on top of bar
+            if bin.total > 0.0 && self.value_labels.shows(height) && self.sparkline_base.is_none() {
                 ctx.set_global_alpha(1.0);
-                ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
-                ctx.set_font(&format!("bold {}px {}", self.config.font_size - 2.0, self.config.font_family));
+                ctx.set_fill_style(&self.config.theme.text);
+                ctx.set_font(&self.config.font("bold", self.config.axis_size()));
                 ctx.set_text_align("center");
+                ctx.set_text_baseline("bottom");
+                // Keep the tallest bar's label inside the canvas
                 ctx.fill_text(
-                    &format!("{}", bin.count),
+                    &self.format_total(bin.total),
                     x + bw / 2.0,
-                    y - 5.0,
+                    (y - 4.0).max(self.config.padding.top),
                 )?;
             }
         }
 
         ctx.set_global_alpha(1.0);
+        ctx.set_text_baseline("alphabetic");
         Ok(())
     }
 
-    fn draw_axes(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
+    fn draw_axes<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
         let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
         let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
 
-        ctx.set_stroke_style(&JsValue::from_str(&self.config.theme.text));
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
+        ctx.set_stroke_style(&self.config.theme.text);
+        ctx.set_fill_style(&self.config.theme.text);
         ctx.set_line_width(1.0);
 
         // X-axis
@@ -235,39 +771,46 @@ impl ScoreDistributionChart {
         ctx.stroke();
 
         // X-axis labels (score percentages)
-        ctx.set_font(&format!("{}px {}", self.config.font_size - 2.0, self.config.font_family));
-        ctx.set_text_align("center");
-
-        let labels = ["0%", "25%", "50%", "75%", "100%"];
-        for (i, label) in labels.iter().enumerate() {
-            let x = self.config.padding.left + (i as f64 / 4.0) * plot_width;
-            ctx.fill_text(
-                label,
+        ctx.set_font(&self.config.font("", self.config.axis_size()));
+        for (t, label) in self.x_tick_labels() {
+            let x = self.config.padding.left + t * plot_width;
+            draw_tick_label(
+                ctx,
+                &label,
                 x,
-                self.config.height - self.config.padding.bottom + 20.0,
+                self.config.height - self.config.padding.bottom + X_TICK_GAP,
+                self.x_label_rotation,
             )?;
         }
 
-        // Y-axis labels (counts)
+        // Y-axis labels (counts or aggregate totals)
         ctx.set_text_align("right");
+        ctx.set_text_baseline("middle");
         for i in 0..=5 {
             let y = self.config.height - self.config.padding.bottom - (i as f64 / 5.0) * plot_height;
-            let count = (i as f64 / 5.0 * self.max_count as f64).round() as u32;
+            let value = i as f64 / 5.0 * self.max_value;
             ctx.fill_text(
-                &format!("{}", count),
+                &self.format_total(value),
                 self.config.padding.left - 10.0,
-                y + 4.0,
+                y,
             )?;
         }
 
+        ctx.set_text_baseline("alphabetic");
         Ok(())
     }
 
-    fn draw_labels(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
+    /// X tick labels as (fraction of plot width, text)
+    fn x_tick_labels(&self) -> Vec<(f64, String)> {
+        (0..=4).map(|i| (i as f64 / 4.0, format!("{}%", i * 25))).collect()
+    }
+
+    fn draw_labels<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        ctx.set_fill_style(&self.config.theme.text);
+        ctx.set_text_baseline("alphabetic");
 
         // Title
-        ctx.set_font(&format!("bold {}px {}", self.config.font_size + 4.0, self.config.font_family));
+        ctx.set_font(&self.config.title_font());
         ctx.set_text_align("center");
         ctx.fill_text(
             "Score Distribution",
@@ -276,7 +819,7 @@ impl ScoreDistributionChart {
         )?;
 
         // X-axis label
-        ctx.set_font(&format!("{}px {}", self.config.font_size, self.config.font_family));
+        ctx.set_font(&self.config.font("", self.config.label_size()));
         ctx.fill_text(
             "Score (%)",
             self.config.width / 2.0,
@@ -287,12 +830,12 @@ impl ScoreDistributionChart {
         ctx.save();
         ctx.translate(15.0, self.config.height / 2.0)?;
         ctx.rotate(-std::f64::consts::FRAC_PI_2)?;
-        ctx.fill_text("Applications", 0.0, 0.0)?;
+        ctx.fill_text(self.aggregate_key.as_deref().unwrap_or("Applications"), 0.0, 0.0)?;
         ctx.restore();
 
         // Summary stats
         if self.total_count > 0 {
-            ctx.set_font(&format!("{}px {}", self.config.font_size - 2.0, self.config.font_family));
+            ctx.set_font(&self.config.font("", self.config.axis_size()));
             ctx.set_text_align("right");
             ctx.fill_text(
                 &format!("Total: {} applications", self.total_count),
@@ -304,6 +847,46 @@ impl ScoreDistributionChart {
         Ok(())
     }
 
+    /// Hover payload for a bin: bounds, totals and its first ten members' ids and references
+    fn bin_data(&self, bin_idx: usize) -> serde_json::Value {
+        let bin = &self.bins[bin_idx];
+        let top: Vec<usize> = self.member_order(bin).into_iter().take(10).collect();
+        serde_json::json!({
+            "binIndex": bin_idx,
+            "min": bin.min,
+            "max": bin.max,
+            "count": bin.count,
+            "total": bin.total,
+            "avgVariance": bin.avg_variance,
+            "applications": top.iter().map(|&i| &bin.applications[i]).collect::<Vec<_>>(),
+            "references": top.iter().map(|&i| &bin.references[i]).collect::<Vec<_>>(),
+            "formatted": {
+                "min": self.formatters.number("score", bin.min, "0"),
+                "max": self.formatters.number("score", bin.max, "0"),
+                "count": self.formatters.number("count", bin.count as f64, "0"),
+                "total": self.format_total(bin.total),
+                "avgVariance": self.formatters.number("variance", bin.avg_variance, "0.0")
+            }
+        })
+    }
+
+    /// Histogram model behind `get_histogram`
+    fn histogram(&self) -> serde_json::Value {
+        let mut edges: Vec<f64> = self.bins.iter().map(|b| b.min).collect();
+        if let Some(last) = self.bins.last() {
+            edges.push(last.max);
+        }
+
+        serde_json::json!({
+            "edges": edges,
+            "counts": self.bins.iter().map(|b| b.count).collect::<Vec<_>>(),
+            "totals": self.bins.iter().map(|b| b.total).collect::<Vec<_>>(),
+            "applicationsPerBin": self.bins.iter().map(|b| {
+                self.member_order(b).into_iter().map(|i| &b.applications[i]).collect::<Vec<_>>()
+            }).collect::<Vec<_>>()
+        })
+    }
+
     /// Handle mouse move for hover effects
     pub fn on_mouse_move(&mut self, x: f64, y: f64) -> JsValue {
         let old_hovered = self.hovered_bin;
@@ -320,23 +903,10 @@ impl ScoreDistributionChart {
 
             if bin_idx < self.bins.len() {
                 self.hovered_bin = Some(bin_idx);
-                let bin = &self.bins[bin_idx];
-
-                let result = HitTestResult::hit(
-                    &format!("bin-{}", bin_idx),
-                    "histogram_bin",
-                    serde_json::json!({
-                        "binIndex": bin_idx,
-                        "min": bin.min,
-                        "max": bin.max,
-                        "count": bin.count,
-                        "avgVariance": bin.avg_variance,
-                        "applications": &bin.applications[..bin.applications.len().min(10)]
-                    }),
-                );
+                let result = HitTestResult::hit(&format!("bin-{}", bin_idx), "histogram_bin", self.bin_data(bin_idx));
 
                 if old_hovered != self.hovered_bin {
-                    self.render().ok();
+                    self.refresh();
                 }
 
                 return serde_wasm_bindgen::to_value(&result).unwrap();
@@ -345,25 +915,460 @@ impl ScoreDistributionChart {
 
         self.hovered_bin = None;
         if old_hovered.is_some() {
-            self.render().ok();
+            self.refresh();
         }
-        serde_wasm_bindgen::to_value(&HitTestResult::miss()).unwrap()
+        HitTestResult::miss_value()
+    }
+
+    /// Get the full histogram model: `edges` (n + 1 bounds in percent), `counts`
+    /// and `totals` (n) and `applicationsPerBin` (application ids per bin)
+    pub fn get_histogram(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.histogram()).unwrap()
+    }
+
+    /// Convert canvas coordinates to data space: `{score, value, binIndex}` with
+    /// `score` in percent and `value` on the count (or aggregate) axis
+    pub fn screen_to_data(&self, x: f64, y: f64) -> JsValue {
+        let (score, value) = self.to_data(x, y);
+        let bin_index = if (0.0..=100.0).contains(&score) && !self.bins.is_empty() {
+            Some(((score / 100.0 * self.bins.len() as f64).floor() as usize).min(self.bins.len() - 1))
+        } else {
+            None
+        };
+
+        let result = serde_json::json!({
+            "score": score,
+            "value": value,
+            "binIndex": bin_index
+        });
+        serde_wasm_bindgen::to_value(&result).unwrap()
+    }
+
+    /// Convert a score percentage and axis value to canvas `{x, y}`; the inverse of `screen_to_data`
+    pub fn data_to_screen(&self, score: f64, value: f64) -> JsValue {
+        let (x, y) = self.to_screen(score, value);
+        serde_wasm_bindgen::to_value(&serde_json::json!({ "x": x, "y": y })).unwrap()
+    }
+
+    /// Canvas coordinates to (score percent, axis value)
+    fn to_data(&self, x: f64, y: f64) -> (f64, f64) {
+        let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
+        let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
+        let score = (x - self.config.padding.left) / plot_width * 100.0;
+        let value = (self.config.height - self.config.padding.bottom - y) / plot_height * self.max_value;
+        (score, value)
+    }
+
+    /// (score percent, axis value) to canvas coordinates
+    fn to_screen(&self, score: f64, value: f64) -> (f64, f64) {
+        let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
+        let y = self.config.height - self.config.padding.bottom - value / self.max_value * plot_height;
+        (self.score_to_x(score), y)
     }
 
     /// Get current chart statistics
     pub fn get_stats(&self) -> JsValue {
         let stats = serde_json::json!({
             "totalApplications": self.total_count,
+            "excludedUnscored": self.excluded_count,
             "binCount": self.bins.len(),
+            "requestedBinCount": self.requested_bins,
+            "targetScore": self.target_score,
             "maxBinCount": self.max_count,
+            "aggregate": self.aggregate_key,
+            "maxBinTotal": self.max_value,
             "bins": self.bins.iter().map(|b| {
                 serde_json::json!({
                     "range": format!("{:.0}%-{:.0}%", b.min, b.max),
                     "count": b.count,
+                    "total": b.total,
                     "avgVariance": b.avg_variance
                 })
             }).collect::<Vec<_>>()
         });
         serde_wasm_bindgen::to_value(&stats).unwrap()
     }
+
+    /// One-sentence summary for screen readers (e.g. a canvas `aria-label`)
+    pub fn describe(&self) -> String {
+        if self.total_count == 0 {
+            return "Score distribution with no applications".to_string();
+        }
+
+        // First bin wins ties so the wording is stable
+        let peak = self.bins.iter().fold(None::<&HistogramBin>, |best, b| match best {
+            Some(best) if best.count >= b.count => Some(best),
+            _ => Some(b),
+        });
+        let score_sum: f64 = self.bins.iter().flat_map(|b| b.scores.iter()).sum();
+        let mean = score_sum / self.total_count as f64;
+
+        let mut text = format!("Score distribution of {} applications", self.total_count);
+        if let Some(peak) = peak {
+            text.push_str(&format!(
+                "; most common band {:.0}-{:.0}% with {} applications",
+                peak.min, peak.max, peak.count
+            ));
+        }
+        text.push_str(&format!("; mean {:.0}%", mean));
+        if self.excluded_count > 0 {
+            text.push_str(&format!("; {} unscored applications excluded", self.excluded_count));
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::charts::RecordingTarget;
+
+    fn point(id: &str, score: f64) -> ScoreDataPoint {
+        ScoreDataPoint {
+            application_id: id.to_string(),
+            reference: format!("APP-{}", id),
+            score,
+            max_score: 100.0,
+            assessor_count: 2,
+            variance: Some(1.0),
+            metadata: None,
+        }
+    }
+
+    fn chart_with(points: &[ScoreDataPoint], bin_count: u32) -> ScoreDistributionChart {
+        let mut chart = ScoreDistributionChart::with_config("test", ChartConfig::default());
+        chart.requested_bins = bin_count;
+        chart.begin_data();
+        chart.ingest(points);
+        chart.finalize_data();
+        chart
+    }
+
+    #[test]
+    fn draws_one_bar_per_bin_after_clearing() {
+        let chart = chart_with(&[point("a", 10.0), point("b", 30.0), point("c", 35.0), point("d", 90.0)], 4);
+        let target = RecordingTarget::new();
+        chart.draw_to(&target).unwrap();
+
+        let calls = target.calls();
+        assert_eq!(calls[0].op, "set_fill_style");
+        assert_eq!(calls[1].op, "fill_rect");
+        assert_eq!(calls[1].args, vec![0.0, 0.0, chart.config.width, chart.config.height]);
+        assert_eq!(target.count("fill"), 4);
+        for label in ["1", "2"] {
+            assert!(target.texts().iter().any(|t| t == label), "missing bar label {}", label);
+        }
+    }
+
+    #[test]
+    fn describe_names_the_peak_band_and_mean() {
+        let chart = chart_with(&[point("a", 10.0), point("b", 30.0), point("c", 35.0), point("d", 90.0)], 4);
+        assert_eq!(
+            chart.describe(),
+            "Score distribution of 4 applications; most common band 25-50% with 2 applications; mean 41%"
+        );
+
+        let empty = chart_with(&[], 4);
+        assert_eq!(empty.describe(), "Score distribution with no applications");
+    }
+
+    #[test]
+    fn sparkline_draws_bars_edge_to_edge_without_axes_or_labels() {
+        let mut chart = chart_with(&[point("a", 10.0), point("b", 30.0), point("c", 90.0)], 4);
+        chart.apply_sparkline(true);
+        assert_eq!(chart.config.padding.left, 0.0);
+        assert_eq!(chart.config.padding.bottom, 0.0);
+
+        let target = RecordingTarget::new();
+        chart.draw_to(&target).unwrap();
+        assert!(target.texts().is_empty());
+
+        chart.config.theme.background = "#000000".to_string();
+        chart.apply_sparkline(false);
+        assert!(chart.config.padding.left > 0.0);
+        assert_eq!(chart.config.theme.background, "#000000");
+    }
+
+    #[test]
+    fn chunked_ingest_matches_a_single_load() {
+        let points: Vec<ScoreDataPoint> = [12.0, 18.0, 47.0, 51.0, 55.0, 88.0, 99.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &score)| ScoreDataPoint { variance: Some(i as f64 * 1.5), ..point(&i.to_string(), score) })
+            .collect();
+        let whole = chart_with(&points, 5);
+
+        let mut chunked = ScoreDistributionChart::with_config("test", ChartConfig::default());
+        chunked.requested_bins = 5;
+        chunked.begin_data();
+        for chunk in points.chunks(3) {
+            chunked.ingest(chunk);
+        }
+        chunked.finalize_data();
+        // Finalizing again must not divide the averages a second time
+        chunked.finalize_data();
+
+        assert_eq!(chunked.total_count, whole.total_count);
+        assert_eq!(chunked.max_count, whole.max_count);
+        assert_eq!(chunked.bins.len(), whole.bins.len());
+        for (a, b) in chunked.bins.iter().zip(&whole.bins) {
+            assert_eq!(a.count, b.count);
+            assert_eq!(a.applications, b.applications);
+            assert_eq!(a.avg_variance, b.avg_variance);
+        }
+    }
+
+    #[test]
+    fn bin_data_returns_member_references() {
+        let chart = chart_with(&[point("1", 15.0), point("2", 18.0), point("3", 85.0)], 10);
+        let data = chart.bin_data(1);
+        assert_eq!(data["applications"], serde_json::json!(["1", "2"]));
+        assert_eq!(data["references"], serde_json::json!(["APP-1", "APP-2"]));
+        assert_eq!(chart.bin_data(8)["references"], serde_json::json!(["APP-3"]));
+    }
+
+    #[test]
+    fn hover_tooltip_lists_truncated_references() {
+        let mut points: Vec<ScoreDataPoint> = (0..5).map(|i| point(&i.to_string(), 55.0)).collect();
+        points[0].reference = "APP-2024-ENVIRONMENTAL-RESEARCH-0001".to_string();
+        let mut chart = chart_with(&points, 10);
+        chart.hovered_bin = Some(5);
+
+        let target = RecordingTarget::new();
+        chart.draw_to(&target).unwrap();
+        assert!(!target.texts().iter().any(|t| t.starts_with("APP-1")), "tooltip is off by default");
+
+        chart.hover_tooltip = true;
+        let target = RecordingTarget::new();
+        chart.draw_to(&target).unwrap();
+        let texts = target.texts();
+        let tooltip = &texts[texts.len() - 4..];
+        assert!(tooltip[0].starts_with("APP-2024") && tooltip[0].ends_with('…'), "{:?}", tooltip);
+        assert_eq!(tooltip[1..], ["APP-1", "APP-2", "+2 more"]);
+    }
+
+    #[test]
+    fn aggregate_mode_sums_the_metric_and_formats_its_labels() {
+        let funded = |id: &str, score: f64, amount: f64| ScoreDataPoint {
+            metadata: Some(serde_json::json!({ "requested": amount })),
+            ..point(id, score)
+        };
+        let mut chart = chart_with(&[funded("1", 15.0, 1200.0), funded("2", 18.0, 800.0), funded("3", 85.0, 2500.0)], 10);
+        chart.aggregate_key = Some("requested".to_string());
+        chart.rebuild_bins();
+
+        let totals: Vec<f64> = chart.bins.iter().map(|b| b.total).collect();
+        assert_eq!(totals, vec![0.0, 2000.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 2500.0, 0.0]);
+        assert_eq!(chart.max_value, 2500.0);
+
+        // Bar labels and the y-axis share the "total" format
+        chart.formatters.set("total", "#,##0.00");
+        let target = RecordingTarget::new();
+        chart.draw_to(&target).unwrap();
+        let texts = target.texts();
+        assert!(texts.contains(&"2,000.00".to_string()), "{:?}", texts);
+        assert!(texts.contains(&"1,500.00".to_string()), "{:?}", texts);
+    }
+
+    #[test]
+    fn rotated_tick_labels_grow_the_bottom_padding() {
+        let mut chart = chart_with(&[point("1", 40.0)], 10);
+        chart.base_padding_bottom = 30.0;
+        let target = RecordingTarget::new();
+        chart.fit_label_padding(&target);
+        let flat = chart.config.padding.bottom;
+        assert_eq!(flat, 30.0);
+
+        chart.x_label_rotation = 45.0;
+        chart.fit_label_padding(&target);
+        assert!(chart.config.padding.bottom > flat, "{} <= {}", chart.config.padding.bottom, flat);
+
+        // Back to horizontal restores the configured padding
+        chart.x_label_rotation = 0.0;
+        chart.fit_label_padding(&target);
+        assert_eq!(chart.config.padding.bottom, flat);
+    }
+
+    #[test]
+    fn narrow_canvas_caps_the_bin_count() {
+        let points: Vec<ScoreDataPoint> = (0..50).map(|i| point(&i.to_string(), i as f64 * 2.0)).collect();
+        let mut chart = chart_with(&points, 40);
+        chart.min_bar_px = 20.0;
+        assert_eq!(chart.effective_bin_count(), 35, "default 700px plot fits 35 bars");
+
+        chart.config.width = 300.0;
+        // 200px of plot
+        assert_eq!(chart.effective_bin_count(), 10);
+        chart.rebin();
+        assert_eq!(chart.bins.len(), 10);
+        assert_eq!(chart.bins.iter().map(|b| b.count).sum::<u32>(), 50);
+    }
+
+    #[test]
+    fn target_line_lands_on_the_score_axis() {
+        let mut chart = chart_with(&[point("1", 40.0)], 10);
+        chart.target_score = Some(75.0);
+        chart.target_label = "Fund line".to_string();
+        let target = RecordingTarget::new();
+        chart.draw_to(&target).unwrap();
+
+        // 60px left padding + 75% of the 700px plot
+        let dashed_from = target.calls().iter().position(|c| c.op == "set_line_dash" && c.args == [6.0, 4.0]).unwrap();
+        let line: Vec<_> = target.calls()[dashed_from..]
+            .iter()
+            .filter(|c| c.op == "move_to" || c.op == "line_to")
+            .take(2)
+            .map(|c| c.args.clone())
+            .collect();
+        let (top, bottom) = (chart.config.padding.top, chart.config.height - chart.config.padding.bottom);
+        assert_eq!(line, vec![vec![585.0, top], vec![585.0, bottom]]);
+        assert!(target.calls().iter().any(|c| c.text.as_deref() == Some("Fund line") && c.args[0] == 585.0));
+    }
+
+    #[test]
+    fn histogram_has_one_more_edge_than_counts() {
+        let points: Vec<_> = [5.0, 12.0, 18.0, 47.0, 50.0, 99.0, 100.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &score)| point(&i.to_string(), score))
+            .collect();
+        let chart = chart_with(&points, 5);
+        let histogram = chart.histogram();
+
+        let edges = histogram["edges"].as_array().unwrap();
+        let counts = histogram["counts"].as_array().unwrap();
+        assert_eq!(edges.len(), counts.len() + 1);
+        assert_eq!(counts.iter().map(|c| c.as_u64().unwrap()).sum::<u64>(), chart.total_count as u64);
+        assert_eq!(chart.total_count, 7);
+    }
+
+    #[test]
+    fn value_label_modes_follow_bar_height() {
+        let mut points = vec![point("low", 5.0)];
+        points.extend((0..40).map(|i| point(&format!("high{}", i), 95.0)));
+        let mut chart = chart_with(&points, 2);
+        let labels = |chart: &ScoreDistributionChart| {
+            let target = RecordingTarget::new();
+            chart.draw_bars(&target).unwrap();
+            target.calls().into_iter().filter(|c| c.op == "fill_text").collect::<Vec<_>>()
+        };
+
+        // The one-unit bar is far below the default 20px "auto" threshold
+        assert_eq!(labels(&chart).iter().map(|c| c.text.clone().unwrap()).collect::<Vec<_>>(), vec!["40"]);
+
+        chart.value_labels = ValueLabelMode::Auto(1.0);
+        assert_eq!(labels(&chart).len(), 2);
+
+        chart.value_labels = ValueLabelMode::Always;
+        let always = labels(&chart);
+        assert_eq!(always.iter().map(|c| c.text.clone().unwrap()).collect::<Vec<_>>(), vec!["1", "40"]);
+        // The full-height bar's label is held at the top of the plot
+        assert_eq!(always[1].args[1], chart.config.padding.top);
+
+        chart.value_labels = ValueLabelMode::Never;
+        assert!(labels(&chart).is_empty());
+    }
+
+    #[test]
+    fn variance_sort_puts_the_highest_variance_members_first() {
+        // Variances 0..15 in a scrambled order, plus one without a variance
+        let mut points: Vec<_> = (0..15)
+            .map(|i| {
+                let mut p = point(&format!("v{}", (i * 7) % 15), 50.0);
+                p.variance = Some(((i * 7) % 15) as f64);
+                p
+            })
+            .collect();
+        let mut unscored = point("none", 50.0);
+        unscored.variance = None;
+        points.insert(0, unscored);
+
+        let mut chart = chart_with(&points, 1);
+        chart.bin_sort = BinSort::Variance;
+        let applications = chart.bin_data(0)["applications"].clone();
+        let expected: Vec<String> = (5..15).rev().map(|v| format!("v{}", v)).collect();
+        assert_eq!(applications, serde_json::json!(expected));
+    }
+
+    #[test]
+    fn excluding_unscored_drops_and_counts_zero_assessor_applications() {
+        let mut points = vec![point("a", 40.0), point("b", 80.0), point("c", 0.0), point("d", 0.0)];
+        points[2].assessor_count = 0;
+        points[3].assessor_count = 0;
+        let mut chart = chart_with(&points, 4);
+        assert_eq!((chart.total_count, chart.excluded_count), (4, 0));
+
+        // Same steps as set_exclude_unscored, which also re-renders
+        chart.exclude_unscored = true;
+        chart.rebuild_bins();
+        assert_eq!((chart.total_count, chart.excluded_count), (2, 2));
+        assert_eq!(chart.bins[0].count, 0);
+
+        chart.exclude_unscored = false;
+        chart.rebuild_bins();
+        assert_eq!((chart.total_count, chart.excluded_count), (4, 0));
+        assert_eq!(chart.bins[0].count, 2);
+    }
+
+    #[test]
+    fn min_bar_height_lifts_small_bars_but_not_empty_ones() {
+        let mut points = vec![point("low", 5.0)];
+        points.extend((0..200).map(|i| point(&format!("high{}", i), 95.0)));
+        let mut chart = chart_with(&points, 3);
+        chart.min_bar_height = 6.0;
+
+        assert_eq!(chart.bar_rect(0).3, 6.0);
+        assert_eq!(chart.bar_rect(1).3, 0.0);
+
+        // A minimum taller than the plot is held to the plot
+        chart.min_bar_height = 1000.0;
+        let plot_height = chart.config.height - chart.config.padding.top - chart.config.padding.bottom;
+        assert_eq!(chart.bar_rect(0).3, plot_height);
+        assert_eq!(chart.bar_rect(0).1, chart.config.padding.top);
+        assert_eq!(chart.bar_rect(1).3, 0.0);
+    }
+
+    #[test]
+    fn screen_and_data_coordinates_round_trip() {
+        let points: Vec<_> = (0..8).map(|i| point(&i.to_string(), 30.0)).collect();
+        let chart = chart_with(&points, 4);
+
+        // The 30% bin's bar top sits at its count
+        let (x, y) = chart.to_screen(30.0, 8.0);
+        assert_eq!((x, y), (chart.score_to_x(30.0), chart.bar_rect(1).1));
+
+        for &(score, value) in &[(0.0, 0.0), (30.0, 8.0), (62.5, 3.25), (100.0, 1.0)] {
+            let (x, y) = chart.to_screen(score, value);
+            let (back_score, back_value) = chart.to_data(x, y);
+            assert!((back_score - score).abs() < 1e-9);
+            assert!((back_value - value).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn axis_labels_set_their_baseline_first() {
+        let chart = chart_with(&[point("1", 40.0)], 4);
+        let target = RecordingTarget::new();
+        chart.draw_axes(&target).unwrap();
+
+        // Pair each label with the baseline in effect when it was drawn
+        let mut baseline = None;
+        let mut labels = Vec::new();
+        for call in target.calls() {
+            match call.op {
+                "set_text_baseline" => baseline = call.text.clone(),
+                "fill_text" => labels.push((call.text.clone().unwrap(), baseline.clone())),
+                _ => {}
+            }
+        }
+
+        assert_eq!(labels.len(), 11);
+        for (text, baseline) in &labels[..5] {
+            assert_eq!(baseline.as_deref(), Some("top"), "x label {}", text);
+        }
+        for (text, baseline) in &labels[5..] {
+            assert_eq!(baseline.as_deref(), Some("middle"), "y label {}", text);
+        }
+        assert_eq!(baseline.as_deref(), Some("alphabetic"));
+    }
 }
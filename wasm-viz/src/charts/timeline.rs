@@ -5,9 +5,26 @@
 
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
-use web_sys::CanvasRenderingContext2d;
-
-use super::common::{get_canvas_context, clear_canvas, draw_grid, ChartConfig, HitTestResult, format_number};
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+use std::cell::{Cell, RefCell};
+
+use super::common::{get_canvas_context, create_offscreen_canvas, track_context_loss, surface_context_loss, clear_canvas, readable_on, draw_grid, payload_hash, is_same_payload, draw_tick_label, rotated_label_height, render_image_data, draw_legend_swatch, now_ms, RenderTiming, ChartConfig, SparklineBase, ColorTheme, HitTestResult, ValueFormatters, ValueLabelMode, SwatchShape, format_number};
+use super::draw_target::DrawTarget;
+
+const HOUR_MS: f64 = 3_600_000.0;
+const DAY_MS: f64 = 86_400_000.0;
+const WEEK_MS: f64 = 7.0 * DAY_MS;
+/// Gap between the x-axis and its tick labels
+const X_TICK_GAP: f64 = 5.0;
+/// The Unix epoch fell on a Thursday; weeks are aligned to start on Monday
+const WEEK_OFFSET_MS: f64 = 4.0 * DAY_MS;
+/// Share of a bucket's width filled by its bar when not snapping; the rest is gap
+const BAR_FILL: f64 = 0.8;
+/// Share of the plot height reached by the tallest bar when counts have their own axis
+const BAR_HEADROOM: f64 = 0.8;
+/// Bar width bounds when not snapping, for readability at extreme zoom levels
+const MIN_BAR_WIDTH: f64 = 2.0;
+const MAX_BAR_WIDTH: f64 = 30.0;
 
 /// Timeline data point
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -18,6 +35,13 @@ pub struct TimelineDataPoint {
     pub label: Option<String>,
 }
 
+/// Cumulative completed assessments at a point in time
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CompletionPoint {
+    pub timestamp: f64,
+    pub completed: u32,
+}
+
 /// Important event marker
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TimelineEvent {
@@ -26,19 +50,66 @@ pub struct TimelineEvent {
     pub event_type: String, // "deadline", "open", "milestone"
 }
 
+/// Clickable legend entry, recorded while the legend is drawn
+#[derive(Clone, Debug)]
+struct LegendHitBox {
+    series: &'static str,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
 /// Timeline chart
 #[wasm_bindgen]
 pub struct TimelineChart {
     canvas_id: String,
     config: ChartConfig,
+    // Full series, used for stats
+    raw_data: Vec<TimelineDataPoint>,
+    // Rendered series (downsampled when max_points is set)
     data: Vec<TimelineDataPoint>,
+    max_points: Option<usize>,
     events: Vec<TimelineEvent>,
     time_range: (f64, f64),
     max_count: u32,
     max_cumulative: u32,
+    // Cumulative completions, drawn against the cumulative axis with the backlog shaded
+    completions: Vec<CompletionPoint>,
+    max_completed: u32,
+    // Cumulative line on its own right-hand scale (false = share the left scale)
+    independent_axes: bool,
+    // Cumulative point markers: radius and minimum gap between drawn markers
+    point_markers: bool,
+    marker_shape: SwatchShape,
+    marker_size: f64,
+    marker_min_spacing: f64,
     show_cumulative: bool,
+    show_bars: bool,
+    legend_boxes: RefCell<Vec<LegendHitBox>>,
     hovered_point: Option<usize>,
+    hover_effect: bool,
     granularity: String, // "hour", "day", "week"
+    snap_to_granularity: bool,
+    calendar_shading: bool,
+    // Dashed guides from the hovered point to the axes
+    show_crosshair: bool,
+    // Y-axis count labels: "1.5k"-style compacting and decimals
+    count_compact: bool,
+    count_decimals: usize,
+    value_labels: ValueLabelMode,
+    min_bar_height: f64,
+    formatters: ValueFormatters,
+    // Settings sparkline mode overrode, while it is on
+    sparkline_base: Option<SparklineBase>,
+    x_label_rotation: f64,
+    event_label_rotation: f64,
+    // Configured bottom padding before growing it for rotated labels
+    base_padding_bottom: f64,
+    // Fingerprint of the last loaded payload
+    data_hash: Option<u64>,
+    render_timing: RenderTiming,
+    context_lost: Cell<bool>,
 }
 
 #[wasm_bindgen]
@@ -48,19 +119,52 @@ impl TimelineChart {
     pub fn new(canvas_id: &str, config_js: JsValue) -> Result<TimelineChart, JsValue> {
         let config: ChartConfig = serde_wasm_bindgen::from_value(config_js)
             .unwrap_or_else(|_| ChartConfig::default());
+        Ok(Self::with_config(canvas_id, config))
+    }
+
+    /// Create a chart from an already-parsed config
+    pub(crate) fn with_config(canvas_id: &str, config: ChartConfig) -> Self {
+        let base_padding_bottom = config.padding.bottom;
 
-        Ok(Self {
+        Self {
             canvas_id: canvas_id.to_string(),
             config,
+            raw_data: Vec::new(),
             data: Vec::new(),
+            max_points: None,
             events: Vec::new(),
             time_range: (0.0, 0.0),
             max_count: 0,
             max_cumulative: 0,
+            completions: Vec::new(),
+            max_completed: 0,
+            independent_axes: true,
+            point_markers: true,
+            marker_shape: SwatchShape::Circle,
+            marker_size: 4.0,
+            marker_min_spacing: 0.0,
             show_cumulative: true,
+            show_bars: true,
+            legend_boxes: RefCell::new(Vec::new()),
             hovered_point: None,
+            hover_effect: true,
             granularity: "day".to_string(),
-        })
+            snap_to_granularity: false,
+            calendar_shading: false,
+            show_crosshair: true,
+            count_compact: true,
+            count_decimals: 0,
+            value_labels: ValueLabelMode::Never,
+            min_bar_height: 0.0,
+            formatters: ValueFormatters::default(),
+            sparkline_base: None,
+            x_label_rotation: 0.0,
+            event_label_rotation: 45.0,
+            base_padding_bottom,
+            data_hash: None,
+            render_timing: RenderTiming::default(),
+            context_lost: Cell::new(false),
+        }
     }
 
     /// Set whether to show cumulative line
@@ -68,13 +172,60 @@ impl TimelineChart {
         self.show_cumulative = show;
     }
 
+    /// Toggle the series whose legend entry is at (x, y). Returns
+    /// `{series, visible}` for the toggled series, or `{series: null}` on a miss.
+    pub fn on_legend_click(&mut self, x: f64, y: f64) -> JsValue {
+        let hit = self.legend_boxes.borrow().iter()
+            .find(|b| x >= b.x && x <= b.x + b.width && y >= b.y && y <= b.y + b.height)
+            .map(|b| b.series);
+
+        let result = match hit {
+            Some("submissions") => {
+                self.show_bars = !self.show_bars;
+                serde_json::json!({ "series": "submissions", "visible": self.show_bars })
+            }
+            Some(_) => {
+                self.show_cumulative = !self.show_cumulative;
+                serde_json::json!({ "series": "cumulative", "visible": self.show_cumulative })
+            }
+            None => serde_json::json!({ "series": null }),
+        };
+
+        if hit.is_some() {
+            self.refresh();
+        }
+        serde_wasm_bindgen::to_value(&result).unwrap()
+    }
+
     /// Set timeline data
     pub fn set_data(&mut self, data_js: JsValue) -> Result<(), JsValue> {
+        self.set_data_with_options(data_js, false)
+    }
+
+    /// Set timeline data; identical payloads are skipped unless `force` is set
+    pub fn set_data_with_options(&mut self, data_js: JsValue, force: bool) -> Result<(), JsValue> {
+        let hash = payload_hash(&[&data_js]);
+        if is_same_payload(hash, self.data_hash, force) {
+            return Ok(());
+        }
+
+        self.load_data(data_js)?;
+        self.data_hash = hash;
+        Ok(())
+    }
+
+    fn load_data(&mut self, data_js: JsValue) -> Result<(), JsValue> {
         let data: Vec<TimelineDataPoint> = serde_wasm_bindgen::from_value(data_js)?;
+        self.load_points(data);
+        Ok(())
+    }
 
+    /// Replace the series with parsed points
+    fn load_points(&mut self, data: Vec<TimelineDataPoint>) {
         if data.is_empty() {
+            self.raw_data.clear();
             self.data.clear();
-            return Ok(());
+            return;
         }
 
         // Calculate ranges
@@ -86,8 +237,26 @@ impl TimelineChart {
         self.max_count = data.iter().map(|d| d.count).max().unwrap_or(0);
         self.max_cumulative = data.iter().map(|d| d.cumulative).max().unwrap_or(0);
 
-        self.data = data;
-        Ok(())
+        self.raw_data = data;
+        self.resample();
+        self.update_label_padding();
+    }
+
+    /// Limit the number of rendered points (0 = no limit).
+    ///
+    /// Larger series are downsampled with largest-triangle-three-buckets, always
+    /// keeping the peak point. Stats are still computed from the full data.
+    pub fn set_max_points(&mut self, n: usize) {
+        self.max_points = if n == 0 { None } else { Some(n.max(3)) };
+        self.resample();
+    }
+
+    fn resample(&mut self) {
+        self.hovered_point = None;
+        self.data = match self.max_points {
+            Some(n) => downsample_lttb(&self.raw_data, n),
+            None => self.raw_data.clone(),
+        };
     }
 
     /// Set event markers
@@ -97,78 +266,462 @@ impl TimelineChart {
         Ok(())
     }
 
+    /// Set cumulative completion counts (`[{timestamp, completed}]`), drawn as a
+    /// second line on the cumulative axis with the gap to submissions shaded as backlog
+    pub fn set_completion_series(&mut self, data_js: JsValue) -> Result<(), JsValue> {
+        let mut completions: Vec<CompletionPoint> = serde_wasm_bindgen::from_value(data_js)?;
+        completions.sort_by(|a, b| a.timestamp.total_cmp(&b.timestamp));
+        self.max_completed = completions.iter().map(|c| c.completed).max().unwrap_or(0);
+        self.completions = completions;
+        self.render()
+    }
+
     /// Set time granularity
     pub fn set_granularity(&mut self, granularity: &str) {
         self.granularity = granularity.to_string();
     }
 
+    /// Snap bars to granularity buckets so each bucket gets one tiled bar
+    pub fn set_snap_to_granularity(&mut self, enabled: bool) {
+        self.snap_to_granularity = enabled;
+    }
+
+    /// Format y-axis count labels: `compact` abbreviates thousands ("1.5k"),
+    /// `decimals` sets the precision of uncompacted values
+    pub fn set_count_format(&mut self, compact: bool, decimals: usize) {
+        self.count_compact = compact;
+        self.count_decimals = decimals;
+        self.refresh();
+    }
+
+    /// Count labels above bars: "auto" (bars taller than `min_height`, default 20px),
+    /// "always" or "never" (default)
+    pub fn set_value_labels(&mut self, mode: &str, min_height: Option<f64>) -> Result<(), JsValue> {
+        self.value_labels = ValueLabelMode::parse(mode, min_height)?;
+        self.render()
+    }
+
+    /// Draw any nonzero bar at least `px` tall so tiny counts stay visible;
+    /// the axis scale is unchanged and empty bars stay empty
+    pub fn set_min_bar_height(&mut self, px: f64) {
+        self.min_bar_height = px.max(0.0);
+        self.refresh();
+    }
+
+    /// Scale the cumulative line on its own right axis (default) or, when
+    /// `independent` is false, share the left axis and hide the right one
+    pub fn set_dual_axis(&mut self, independent: bool) {
+        self.independent_axes = independent;
+        self.refresh();
+    }
+
+    /// Configure cumulative line markers: toggle them, set their radius, and skip
+    /// markers closer than `min_spacing_px` to the previous one. The hovered
+    /// point is always marked.
+    pub fn set_point_markers(&mut self, enabled: bool, size: f64, min_spacing_px: f64) {
+        self.point_markers = enabled;
+        self.marker_size = size.max(0.0);
+        self.marker_min_spacing = min_spacing_px.max(0.0);
+        self.refresh();
+    }
+
+    /// Shape of the cumulative line markers: "circle" (default), "square" or
+    /// "triangle". The legend swatch follows the marker shape.
+    pub fn set_marker_shape(&mut self, shape: &str) -> Result<(), JsValue> {
+        self.marker_shape = SwatchShape::parse_marker(shape)?;
+        self.render()
+    }
+
+    /// Legend swatch for the cumulative series: its marker when markers are drawn
+    fn cumulative_swatch(&self) -> SwatchShape {
+        if self.point_markers { self.marker_shape } else { SwatchShape::Line }
+    }
+
+    /// Largest value on either cumulative series
+    fn cumulative_max(&self) -> u32 {
+        self.max_cumulative.max(self.max_completed)
+    }
+
+    /// Value at the top of the left axis. On their own axis bars leave headroom
+    /// above the tallest one; a shared axis tops out at the cumulative maximum
+    /// so bars and line are read off the same scale.
+    fn count_scale(&self) -> f64 {
+        if self.independent_axes || !self.show_cumulative {
+            self.max_count as f64 / BAR_HEADROOM
+        } else {
+            self.max_count.max(self.cumulative_max()) as f64
+        }
+    }
+
+    /// Value at the top of the cumulative line's axis
+    fn cumulative_scale(&self) -> f64 {
+        if self.independent_axes {
+            self.cumulative_max() as f64
+        } else {
+            self.max_count.max(self.cumulative_max()) as f64
+        }
+    }
+
+    fn format_count(&self, value: f64) -> String {
+        let value = if self.count_decimals == 0 { value.round() } else { value };
+        if self.count_compact {
+            format_number(value, self.count_decimals)
+        } else {
+            format!("{:.*}", self.count_decimals, value)
+        }
+    }
+
+    /// Shade weekends and draw faint midnight lines (local time) behind the bars
+    pub fn set_calendar_shading(&mut self, enabled: bool) {
+        self.calendar_shading = enabled;
+        self.refresh();
+    }
+
+    /// Draw dashed guides from the hovered point to the axes, with the axis values labeled
+    pub fn set_show_crosshair(&mut self, enabled: bool) {
+        self.show_crosshair = enabled;
+        self.refresh();
+    }
+
+    /// Enable or disable the hover highlight on bars and line markers
+    pub fn set_hover_effect(&mut self, enabled: bool) {
+        self.hover_effect = enabled;
+        self.refresh();
+    }
+
+    /// Toggle sparkline mode: only the data shape, filling the full canvas
+    pub fn set_sparkline(&mut self, enabled: bool) {
+        self.apply_sparkline(enabled);
+        self.refresh();
+    }
+
+    fn apply_sparkline(&mut self, enabled: bool) {
+        if enabled {
+            if self.sparkline_base.is_none() {
+                self.sparkline_base = Some(self.config.enter_sparkline());
+            }
+        } else if let Some(base) = self.sparkline_base.take() {
+            self.config.exit_sparkline(base);
+            self.update_label_padding();
+        }
+    }
+
+    /// Rotate x-axis tick labels by `degrees` (0 = horizontal); bottom padding
+    /// grows to fit the rotated labels
+    pub fn set_x_label_rotation(&mut self, degrees: f64) {
+        self.x_label_rotation = degrees;
+        self.update_label_padding();
+        self.refresh();
+    }
+
+    /// Rotate event marker labels by `degrees` (default 45)
+    pub fn set_event_label_rotation(&mut self, degrees: f64) {
+        self.event_label_rotation = degrees;
+        self.refresh();
+    }
+
+    /// Grow bottom padding to fit rotated tick labels, never shrinking below the configured value.
+    /// Text is measured on the chart's canvas, or a scratch canvas before it is mounted.
+    fn update_label_padding(&mut self) {
+        if self.x_label_rotation == 0.0 {
+            // Horizontal labels fit the configured padding without measuring
+            if self.sparkline_base.is_none() {
+                self.config.padding.bottom = self.base_padding_bottom;
+            }
+            return;
+        }
+
+        let measured = get_canvas_context(&self.canvas_id).or_else(|_| create_offscreen_canvas(1, 1));
+        if let Ok((_, ctx)) = measured {
+            self.fit_label_padding(&ctx);
+        }
+    }
+
+    fn fit_label_padding<T: DrawTarget + ?Sized>(&mut self, ctx: &T) {
+        if self.sparkline_base.is_some() {
+            return;
+        }
+
+        let mut bottom = self.base_padding_bottom;
+        if self.x_label_rotation != 0.0 {
+            let font_size = self.config.axis_size();
+            ctx.set_font(&self.config.font("", font_size));
+            let labels: Vec<String> = self.x_tick_labels().into_iter().map(|(_, label)| label).collect();
+            // Tick gap above the labels, axis title below them
+            bottom = bottom.max(X_TICK_GAP + rotated_label_height(ctx, &labels, font_size, self.x_label_rotation) + 25.0);
+        }
+        self.config.padding.bottom = bottom;
+    }
+
+    /// Set the display format for a value kind used in tooltips and overlay text.
+    ///
+    /// Numbers accept specs like `"0.0"` or `"#,##0"`; timestamps accept date
+    /// patterns like `"YYYY-MM-DD HH:mm"`. An empty format restores the default.
+    pub fn set_value_formatter(&mut self, kind: &str, format: &str) {
+        self.formatters.set(kind, format);
+        self.update_label_padding();
+        self.refresh();
+    }
+
+    /// Replace the color theme (a `ColorTheme` object) and re-render
+    pub fn set_theme(&mut self, theme_js: JsValue) -> Result<(), JsValue> {
+        let theme: ColorTheme = serde_wasm_bindgen::from_value(theme_js)?;
+        self.config.theme = theme;
+        self.render()
+    }
+
+    /// Whether the last render failed because the 2D context was lost
+    pub fn is_context_lost(&self) -> bool {
+        self.context_lost.get()
+    }
+
+    /// Re-acquire the canvas context after a loss and re-render
+    pub fn restore(&mut self) -> Result<(), JsValue> {
+        self.context_lost.set(false);
+        self.render()
+    }
+
+    /// Re-render after a state change, warning once if the context was lost
+    fn refresh(&self) {
+        surface_context_loss(&self.context_lost, &self.canvas_id, || self.render());
+    }
+
     /// Render the timeline
     pub fn render(&self) -> Result<(), JsValue> {
+        if !self.config.has_drawable_size() {
+            return Ok(());
+        }
+
         let (canvas, ctx) = get_canvas_context(&self.canvas_id)?;
+        let start = now_ms();
+        let result = track_context_loss(&self.context_lost, &ctx, || self.draw_frame(&canvas, &ctx));
+        self.render_timing.record(now_ms() - start);
+        result
+    }
+
+    /// Duration of the last render in milliseconds
+    pub fn last_render_ms(&self) -> f64 {
+        self.render_timing.last()
+    }
 
+    /// Average render duration over recent renders in milliseconds
+    pub fn avg_render_ms(&self) -> f64 {
+        self.render_timing.average()
+    }
+
+    /// Render offscreen and return raw RGBA bytes for pixel-level regression tests
+    pub fn render_to_image_data(&self, width: u32, height: u32) -> Result<Vec<u8>, JsValue> {
+        render_image_data(&self.config, width, height, |canvas, ctx| self.draw_frame(canvas, ctx))
+    }
+
+    fn draw_frame(&self, canvas: &HtmlCanvasElement, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
         canvas.set_width(self.config.width as u32);
         canvas.set_height(self.config.height as u32);
 
-        clear_canvas(&ctx, self.config.width, self.config.height, &self.config.theme.background);
+        self.draw_to(ctx)
+    }
+
+    /// Draw the full chart onto any target; the canvas must already be sized
+    pub(crate) fn draw_to<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        clear_canvas(ctx, self.config.width, self.config.height, &self.config.theme.background);
+        // Refilled by draw_legend; stays empty when no legend is drawn
+        self.legend_boxes.borrow_mut().clear();
+
+        let sparkline = self.sparkline_base.is_some();
 
         if self.data.is_empty() {
-            self.draw_empty_state(&ctx)?;
+            if !sparkline {
+                self.draw_empty_state(ctx)?;
+            }
             return Ok(());
         }
 
+        // Draw weekend shading and day boundaries
+        if self.calendar_shading && !sparkline {
+            self.draw_calendar(ctx);
+        }
+
         // Draw grid
         if self.config.show_grid {
-            draw_grid(&ctx, &self.config, 10, 5);
+            draw_grid(ctx, &self.config, 10, 5);
         }
 
         // Draw event markers
-        self.draw_events(&ctx)?;
+        if !sparkline {
+            self.draw_events(ctx)?;
+        }
 
         // Draw bar chart for counts
-        self.draw_bars(&ctx)?;
+        if self.show_bars {
+            self.draw_bars(ctx)?;
+        }
 
-        // Draw cumulative line if enabled
+        // Draw cumulative line if enabled, over the completion series and backlog
         if self.show_cumulative {
-            self.draw_cumulative_line(&ctx)?;
+            if !self.completions.is_empty() {
+                self.draw_completion_series(ctx)?;
+            }
+            self.draw_cumulative_line(ctx)?;
         }
 
         // Draw axes
-        self.draw_axes(&ctx)?;
+        if !sparkline {
+            self.draw_axes(ctx)?;
+        }
+
+        if self.show_crosshair && !sparkline {
+            if let Some(idx) = self.hovered_point {
+                self.draw_crosshair(ctx, idx)?;
+            }
+        }
 
         // Draw title and labels
         if self.config.show_labels {
-            self.draw_labels(&ctx)?;
+            self.draw_labels(ctx)?;
         }
 
         // Draw legend
         if self.config.show_legend {
-            self.draw_legend(&ctx)?;
+            self.draw_legend(ctx)?;
         }
 
         Ok(())
     }
 
-    fn draw_bars(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
+    /// Bucket duration for the current granularity
+    fn bucket_ms(&self) -> f64 {
+        match self.granularity.as_str() {
+            "hour" => HOUR_MS,
+            "week" => WEEK_MS,
+            _ => DAY_MS,
+        }
+    }
+
+    /// Start of the granularity bucket containing a timestamp
+    fn bucket_start(&self, timestamp: f64) -> f64 {
+        let bucket = self.bucket_ms();
+        let offset = if bucket == WEEK_MS { WEEK_OFFSET_MS } else { 0.0 };
+        ((timestamp - offset) / bucket).floor() * bucket + offset
+    }
+
+    /// Time range mapped onto the x-axis, widened to whole buckets when snapping
+    fn x_domain(&self) -> (f64, f64) {
+        if self.snap_to_granularity {
+            (
+                self.bucket_start(self.time_range.0),
+                self.bucket_start(self.time_range.1) + self.bucket_ms(),
+            )
+        } else {
+            self.time_range
+        }
+    }
+
+    /// Time at which a data point is plotted (bucket center when snapping)
+    fn plot_time(&self, timestamp: f64) -> f64 {
+        if self.snap_to_granularity {
+            self.bucket_start(timestamp) + self.bucket_ms() / 2.0
+        } else {
+            timestamp
+        }
+    }
+
+    /// Local-time day spans `(midnight, next midnight, is_weekend)` covering the x domain
+    fn calendar_days(&self) -> Vec<(f64, f64, bool)> {
+        let (domain_start, domain_end) = self.x_domain();
+        let mut days = Vec::new();
+        if domain_end <= domain_start {
+            return days;
+        }
+
+        let date = js_sys::Date::new(&JsValue::from_f64(domain_start));
+        date.set_hours(0);
+        date.set_minutes(0);
+        date.set_seconds(0);
+        date.set_milliseconds(0);
+
+        while date.get_time() < domain_end {
+            let start = date.get_time();
+            let day_of_week = date.get_day();
+            // set_date rolls over months and keeps DST days at their true length
+            date.set_date(date.get_date() + 1);
+            days.push((start, date.get_time(), day_of_week == 0 || day_of_week == 6));
+        }
+
+        days
+    }
+
+    fn draw_calendar<T: DrawTarget + ?Sized>(&self, ctx: &T) {
+        self.draw_calendar_days(ctx, &self.calendar_days());
+    }
+
+    /// Weekend shading and midnight lines for the given day spans
+    fn draw_calendar_days<T: DrawTarget + ?Sized>(&self, ctx: &T, days: &[(f64, f64, bool)]) {
         let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
         let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
+        let (domain_start, domain_end) = self.x_domain();
+        let time_span = domain_end - domain_start;
+        let to_x = |t: f64| self.config.padding.left + ((t - domain_start) / time_span) * plot_width;
+
+        // Skip midnight lines when days are too narrow to read
+        let show_lines = plot_width / days.len().max(1) as f64 >= 4.0;
+
+        ctx.set_fill_style(&self.config.theme.grid);
+        ctx.set_stroke_style(&self.config.theme.grid);
+        ctx.set_line_width(0.5);
+
+        for &(start, end, weekend) in days {
+            let x0 = to_x(start.max(domain_start));
+            let x1 = to_x(end.min(domain_end));
+
+            if weekend {
+                ctx.set_global_alpha(0.4);
+                ctx.fill_rect(x0, self.config.padding.top, x1 - x0, plot_height);
+                ctx.set_global_alpha(1.0);
+            }
 
-        let time_span = self.time_range.1 - self.time_range.0;
+            if show_lines && start > domain_start {
+                ctx.begin_path();
+                ctx.move_to(x0, self.config.padding.top);
+                ctx.line_to(x0, self.config.padding.top + plot_height);
+                ctx.stroke();
+            }
+        }
+    }
+
+    fn draw_bars<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
+        let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
+
+        let (domain_start, domain_end) = self.x_domain();
+        let time_span = domain_end - domain_start;
         if time_span <= 0.0 || self.max_count == 0 {
             return Ok(());
         }
 
-        let bar_width = (plot_width / self.data.len() as f64).min(30.0);
+        // Width follows the bucket duration, so gaps in time stay visible as gaps
+        let bucket_px = (self.bucket_ms() / time_span) * plot_width;
+        let bar_width = if self.snap_to_granularity {
+            bucket_px
+        } else {
+            (bucket_px * BAR_FILL).clamp(MIN_BAR_WIDTH, MAX_BAR_WIDTH)
+        };
 
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.primary));
+        ctx.set_fill_style(&self.config.theme.primary);
+        ctx.set_font(&self.config.font("", self.config.axis_size()));
+        ctx.set_text_align("center");
+        ctx.set_text_baseline("bottom");
 
         for (i, point) in self.data.iter().enumerate() {
             let x = self.config.padding.left
-                + ((point.timestamp - self.time_range.0) / time_span) * plot_width
+                + ((self.plot_time(point.timestamp) - domain_start) / time_span) * plot_width
                 - bar_width / 2.0;
-            let height = (point.count as f64 / self.max_count as f64) * plot_height * 0.8;
+            let mut height = (point.count as f64 / self.count_scale()) * plot_height;
+            if point.count > 0 {
+                height = height.max(self.min_bar_height.min(plot_height));
+            }
             let y = self.config.height - self.config.padding.bottom - height;
 
-            let is_hovered = self.hovered_point == Some(i);
+            let is_hovered = self.hover_effect && self.hovered_point == Some(i);
             ctx.set_global_alpha(if is_hovered { 1.0 } else { 0.7 });
 
             // Draw bar with rounded top
@@ -181,32 +734,172 @@ impl TimelineChart {
             ctx.line_to(x + bar_width, y + height);
             ctx.close_path();
             ctx.fill();
+
+            if point.count > 0 && self.value_labels.shows(height) && self.sparkline_base.is_none() {
+                ctx.set_global_alpha(1.0);
+                ctx.set_fill_style(&self.config.theme.text);
+                let label_y = (y - 4.0).max(self.config.padding.top);
+                ctx.fill_text(&self.formatters.number("count", point.count as f64, "0"), x + bar_width / 2.0, label_y)?;
+                ctx.set_fill_style(&self.config.theme.primary);
+            }
+        }
+
+        ctx.set_global_alpha(1.0);
+        ctx.set_text_baseline("alphabetic");
+        Ok(())
+    }
+
+    /// Completion line plus the backlog area between it and the submissions line
+    fn draw_completion_series<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
+        let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
+
+        let (domain_start, domain_end) = self.x_domain();
+        let time_span = domain_end - domain_start;
+        let scale = self.cumulative_scale();
+        if time_span <= 0.0 || scale <= 0.0 {
+            return Ok(());
         }
 
+        let bottom = self.config.height - self.config.padding.bottom;
+        let to_screen = |timestamp: f64, value: u32| {
+            (
+                self.config.padding.left + ((self.plot_time(timestamp) - domain_start) / time_span) * plot_width,
+                bottom - (value as f64 / scale) * plot_height,
+            )
+        };
+        let submitted: Vec<(f64, f64)> = self.data.iter().map(|p| to_screen(p.timestamp, p.cumulative)).collect();
+        let completed: Vec<(f64, f64)> = self.completions.iter().map(|c| to_screen(c.timestamp, c.completed)).collect();
+
+        // Backlog: along the submissions line, then back along the completions line
+        ctx.set_fill_style(&self.config.theme.warning);
+        ctx.set_global_alpha(0.2);
+        ctx.begin_path();
+        for (i, &(x, y)) in submitted.iter().chain(completed.iter().rev()).enumerate() {
+            if i == 0 {
+                ctx.move_to(x, y);
+            } else {
+                ctx.line_to(x, y);
+            }
+        }
+        ctx.close_path();
+        ctx.fill();
         ctx.set_global_alpha(1.0);
+
+        ctx.set_stroke_style(&self.config.theme.warning);
+        ctx.set_line_width(2.5);
+        ctx.begin_path();
+        for (i, &(x, y)) in completed.iter().enumerate() {
+            if i == 0 {
+                ctx.move_to(x, y);
+            } else {
+                ctx.line_to(x, y);
+            }
+        }
+        ctx.stroke();
+
         Ok(())
     }
 
-    fn draw_cumulative_line(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
+    /// Guides from the hovered point: vertical from the axis up through the bar to
+    /// the cumulative line, horizontal to each value's axis, with value tags
+    fn draw_crosshair<T: DrawTarget + ?Sized>(&self, ctx: &T, idx: usize) -> Result<(), JsValue> {
+        let Some(point) = self.data.get(idx) else {
+            return Ok(());
+        };
         let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
         let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
+        let (domain_start, domain_end) = self.x_domain();
+        let time_span = domain_end - domain_start;
+        if time_span <= 0.0 {
+            return Ok(());
+        }
 
-        let time_span = self.time_range.1 - self.time_range.0;
+        let left = self.config.padding.left;
+        let right = self.config.width - self.config.padding.right;
+        let bottom = self.config.height - self.config.padding.bottom;
+        let x = left + ((self.plot_time(point.timestamp) - domain_start) / time_span) * plot_width;
+
+        let count_scale = self.count_scale().max(1.0);
+        let bar_y = self.show_bars
+            .then(|| bottom - (point.count as f64 / count_scale) * plot_height);
+        let cumulative_y = self.show_cumulative
+            .then(|| bottom - (point.cumulative as f64 / self.cumulative_scale().max(1.0)) * plot_height);
+
+        ctx.set_stroke_style(&self.config.theme.grid);
+        ctx.set_line_width(1.0);
+        ctx.set_line_dash(&[4.0, 4.0])?;
+        ctx.begin_path();
+        ctx.move_to(x, bottom);
+        ctx.line_to(x, bar_y.into_iter().chain(cumulative_y).fold(bottom, f64::min));
+        if let Some(by) = bar_y {
+            ctx.move_to(left, by);
+            ctx.line_to(x, by);
+        }
+        // The cumulative value reads off the right axis when it has its own scale
+        let cumulative_axis_x = if self.independent_axes { right } else { left };
+        if let Some(cy) = cumulative_y {
+            ctx.move_to(x, cy);
+            ctx.line_to(cumulative_axis_x, cy);
+        }
+        ctx.stroke();
+        ctx.set_line_dash(&[])?;
+
+        ctx.set_font(&self.config.font("", self.config.axis_size()));
+        ctx.set_text_baseline("middle");
+        if let Some(by) = bar_y {
+            self.draw_axis_tag(ctx, &self.format_count(point.count as f64), left, by, &self.config.theme.primary, true)?;
+        }
+        if let Some(cy) = cumulative_y {
+            self.draw_axis_tag(
+                ctx,
+                &self.format_count(point.cumulative as f64),
+                cumulative_axis_x,
+                cy,
+                &self.config.theme.success,
+                !self.independent_axes,
+            )?;
+        }
+        ctx.set_text_baseline("alphabetic");
+
+        Ok(())
+    }
+
+    /// Value tag on an axis line at `y`: outside the plot, to the left when `left_side`
+    fn draw_axis_tag<T: DrawTarget + ?Sized>(&self, ctx: &T, text: &str, axis_x: f64, y: f64, color: &str, left_side: bool) -> Result<(), JsValue> {
+        let width = ctx.measure_text_width(text).unwrap_or(0.0) + 8.0;
+        let height = self.config.axis_size() + 6.0;
+        let tag_x = if left_side { axis_x - width } else { axis_x };
+
+        ctx.set_fill_style(color);
+        ctx.fill_rect(tag_x, y - height / 2.0, width, height);
+        ctx.set_fill_style("#FFFFFF");
+        ctx.set_text_align("center");
+        ctx.fill_text(text, tag_x + width / 2.0, y)?;
+        Ok(())
+    }
+
+    fn draw_cumulative_line<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
+        let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
+
+        let (domain_start, domain_end) = self.x_domain();
+        let time_span = domain_end - domain_start;
         if time_span <= 0.0 || self.max_cumulative == 0 {
             return Ok(());
         }
 
-        ctx.set_stroke_style(&JsValue::from_str(&self.config.theme.success));
+        ctx.set_stroke_style(&self.config.theme.success);
         ctx.set_line_width(2.5);
         ctx.begin_path();
 
         let mut first = true;
         for point in &self.data {
             let x = self.config.padding.left
-                + ((point.timestamp - self.time_range.0) / time_span) * plot_width;
+                + ((self.plot_time(point.timestamp) - domain_start) / time_span) * plot_width;
             let y = self.config.height
                 - self.config.padding.bottom
-                - (point.cumulative as f64 / self.max_cumulative as f64) * plot_height;
+                - (point.cumulative as f64 / self.cumulative_scale()) * plot_height;
 
             if first {
                 ctx.move_to(x, y);
@@ -219,28 +912,33 @@ impl TimelineChart {
         ctx.stroke();
 
         // Draw points
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.success));
+        let mut last_marker_x = f64::NEG_INFINITY;
         for (i, point) in self.data.iter().enumerate() {
             let x = self.config.padding.left
-                + ((point.timestamp - self.time_range.0) / time_span) * plot_width;
+                + ((self.plot_time(point.timestamp) - domain_start) / time_span) * plot_width;
             let y = self.config.height
                 - self.config.padding.bottom
-                - (point.cumulative as f64 / self.max_cumulative as f64) * plot_height;
-
-            let is_hovered = self.hovered_point == Some(i);
-            let radius = if is_hovered { 6.0 } else { 4.0 };
+                - (point.cumulative as f64 / self.cumulative_scale()) * plot_height;
+
+            let is_hovered = self.hover_effect && self.hovered_point == Some(i);
+            if !is_hovered {
+                if !self.point_markers || x - last_marker_x < self.marker_min_spacing {
+                    continue;
+                }
+                last_marker_x = x;
+            }
+            let radius = if is_hovered { self.marker_size * 1.5 } else { self.marker_size };
 
-            ctx.begin_path();
-            ctx.arc(x, y, radius, 0.0, std::f64::consts::PI * 2.0)?;
-            ctx.fill();
+            draw_legend_swatch(ctx, self.marker_shape, &self.config.theme.success, x - radius, y - radius, radius * 2.0)?;
         }
 
         Ok(())
     }
 
-    fn draw_events(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
+    fn draw_events<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
         let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
-        let time_span = self.time_range.1 - self.time_range.0;
+        let (domain_start, domain_end) = self.x_domain();
+        let time_span = domain_end - domain_start;
 
         if time_span <= 0.0 {
             return Ok(());
@@ -248,7 +946,7 @@ impl TimelineChart {
 
         for event in &self.events {
             let x = self.config.padding.left
-                + ((event.timestamp - self.time_range.0) / time_span) * plot_width;
+                + ((event.timestamp - domain_start) / time_span) * plot_width;
 
             // Draw vertical line
             let color = match event.event_type.as_str() {
@@ -257,26 +955,26 @@ impl TimelineChart {
                 _ => &self.config.theme.warning,
             };
 
-            ctx.set_stroke_style(&JsValue::from_str(color));
+            ctx.set_stroke_style(color);
             ctx.set_line_width(2.0);
-            ctx.set_line_dash(&JsValue::from(js_sys::Array::of2(&JsValue::from(5), &JsValue::from(5))))?;
+            ctx.set_line_dash(&[5.0, 5.0])?;
 
             ctx.begin_path();
             ctx.move_to(x, self.config.padding.top);
             ctx.line_to(x, self.config.height - self.config.padding.bottom);
             ctx.stroke();
 
-            ctx.set_line_dash(&JsValue::from(js_sys::Array::new()))?;
+            ctx.set_line_dash(&[])?;
 
             // Draw label
-            ctx.set_fill_style(&JsValue::from_str(color));
-            ctx.set_font(&format!("{}px {}", self.config.font_size - 2.0, self.config.font_family));
+            ctx.set_fill_style(color);
+            ctx.set_font(&self.config.font("", self.config.axis_size()));
             ctx.set_text_align("center");
 
             // Rotate text for better readability
             ctx.save();
             ctx.translate(x, self.config.padding.top - 5.0)?;
-            ctx.rotate(-std::f64::consts::FRAC_PI_4)?;
+            ctx.rotate(-self.event_label_rotation.to_radians())?;
             ctx.fill_text(&event.label, 0.0, 0.0)?;
             ctx.restore();
         }
@@ -284,11 +982,10 @@ impl TimelineChart {
         Ok(())
     }
 
-    fn draw_axes(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
+    fn draw_axes<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
         let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
-        let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
 
-        ctx.set_stroke_style(&JsValue::from_str(&self.config.theme.text));
+        ctx.set_stroke_style(&self.config.theme.text);
         ctx.set_line_width(1.0);
 
         // X-axis
@@ -297,86 +994,113 @@ impl TimelineChart {
         ctx.line_to(self.config.width - self.config.padding.right, self.config.height - self.config.padding.bottom);
         ctx.stroke();
 
+        // X-axis time labels
+        ctx.set_fill_style(&self.config.theme.text);
+        ctx.set_font(&self.config.font("", self.config.axis_size()));
+        for (t, label) in self.x_tick_labels() {
+            let x = self.config.padding.left + t * plot_width;
+            draw_tick_label(
+                ctx,
+                &label,
+                x,
+                self.config.height - self.config.padding.bottom + X_TICK_GAP,
+                self.x_label_rotation,
+            )?;
+        }
+
+        self.draw_y_axes(ctx)
+    }
+
+    /// Left count axis, plus the right cumulative axis when it has its own scale
+    fn draw_y_axes<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
+
         // Y-axis (left - counts)
+        ctx.set_stroke_style(&self.config.theme.text);
+        ctx.set_line_width(1.0);
         ctx.begin_path();
         ctx.move_to(self.config.padding.left, self.config.padding.top);
         ctx.line_to(self.config.padding.left, self.config.height - self.config.padding.bottom);
         ctx.stroke();
 
         // Y-axis (right - cumulative)
-        if self.show_cumulative {
+        if self.show_cumulative && self.independent_axes {
             ctx.begin_path();
             ctx.move_to(self.config.width - self.config.padding.right, self.config.padding.top);
             ctx.line_to(self.config.width - self.config.padding.right, self.config.height - self.config.padding.bottom);
             ctx.stroke();
         }
 
-        // X-axis time labels
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
-        ctx.set_font(&format!("{}px {}", self.config.font_size - 2.0, self.config.font_family));
-        ctx.set_text_align("center");
-
-        let label_count = 6;
-        let time_span = self.time_range.1 - self.time_range.0;
-
-        for i in 0..=label_count {
-            let t = i as f64 / label_count as f64;
-            let timestamp = self.time_range.0 + t * time_span;
-            let x = self.config.padding.left + t * plot_width;
-
-            // Format timestamp (simplified)
-            let date = js_sys::Date::new(&JsValue::from_f64(timestamp));
-            let label = format!(
-                "{}/{} {}:{}",
-                date.get_date(),
-                date.get_month() + 1,
-                date.get_hours(),
-                format!("{:02}", date.get_minutes())
-            );
-
-            ctx.fill_text(&label, x, self.config.height - self.config.padding.bottom + 15.0)?;
-        }
+        ctx.set_fill_style(&self.config.theme.text);
+        ctx.set_font(&self.config.font("", self.config.axis_size()));
 
         // Left Y-axis labels (counts)
         ctx.set_text_align("right");
+        ctx.set_text_baseline("middle");
         for i in 0..=5 {
             let t = i as f64 / 5.0;
             let y = self.config.height - self.config.padding.bottom - t * plot_height;
-            let value = (t * self.max_count as f64).round() as u32;
+            let value = t * self.count_scale();
 
             ctx.fill_text(
-                &format_number(value as f64, 0),
+                &self.format_count(value),
                 self.config.padding.left - 10.0,
-                y + 4.0,
+                y,
             )?;
         }
 
         // Right Y-axis labels (cumulative)
-        if self.show_cumulative {
+        if self.show_cumulative && self.independent_axes {
             ctx.set_text_align("left");
-            ctx.set_fill_style(&JsValue::from_str(&self.config.theme.success));
+            ctx.set_fill_style(&self.config.theme.success);
 
             for i in 0..=5 {
                 let t = i as f64 / 5.0;
                 let y = self.config.height - self.config.padding.bottom - t * plot_height;
-                let value = (t * self.max_cumulative as f64).round() as u32;
+                let value = t * self.cumulative_scale();
 
                 ctx.fill_text(
-                    &format_number(value as f64, 0),
+                    &self.format_count(value),
                     self.config.width - self.config.padding.right + 10.0,
-                    y + 4.0,
+                    y,
                 )?;
             }
         }
 
+        ctx.set_text_baseline("alphabetic");
         Ok(())
     }
 
-    fn draw_labels(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
+    /// X tick labels as (fraction of plot width, text)
+    fn x_tick_labels(&self) -> Vec<(f64, String)> {
+        let label_count = 6;
+        let (domain_start, domain_end) = self.x_domain();
+        let time_span = domain_end - domain_start;
+
+        (0..=label_count)
+            .map(|i| {
+                let t = i as f64 / label_count as f64;
+
+                // Format timestamp (simplified)
+                let date = js_sys::Date::new(&JsValue::from_f64(domain_start + t * time_span));
+                let label = format!(
+                    "{}/{} {}:{}",
+                    date.get_date(),
+                    date.get_month() + 1,
+                    date.get_hours(),
+                    format!("{:02}", date.get_minutes())
+                );
+                (t, label)
+            })
+            .collect()
+    }
+
+    fn draw_labels<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        ctx.set_fill_style(&self.config.theme.text);
+        ctx.set_text_baseline("alphabetic");
 
         // Title
-        ctx.set_font(&format!("bold {}px {}", self.config.font_size + 2.0, self.config.font_family));
+        ctx.set_font(&self.config.title_font());
         ctx.set_text_align("center");
         ctx.fill_text(
             "Application Submission Timeline",
@@ -387,38 +1111,53 @@ impl TimelineChart {
         Ok(())
     }
 
-    fn draw_legend(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
+    fn draw_legend<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
         let legend_y = 20.0;
-        let legend_x = self.config.width - self.config.padding.right - 200.0;
+        let legend_width = if self.show_cumulative && !self.completions.is_empty() { 300.0 } else { 200.0 };
+        let legend_x = self.config.width - self.config.padding.right - legend_width;
 
-        ctx.set_font(&format!("{}px {}", self.config.font_size - 1.0, self.config.font_family));
+        ctx.set_font(&self.config.font("", self.config.legend_size()));
         ctx.set_text_align("left");
 
+        let mut boxes = self.legend_boxes.borrow_mut();
+        boxes.clear();
+
+        // Hidden series stay in the legend, dimmed, so they can be clicked back on
+        let mut entry = |series: &'static str, label: &str, shape: SwatchShape, color: &str, x: f64, visible: bool| -> Result<(), JsValue> {
+            ctx.set_global_alpha(if visible { 1.0 } else { 0.4 });
+            let (swatch_y, swatch_size) = match shape {
+                SwatchShape::Line => (legend_y - 10.0, 16.0),
+                _ => (legend_y - 8.0, 12.0),
+            };
+            draw_legend_swatch(ctx, shape, color, x, swatch_y, swatch_size)?;
+            ctx.set_fill_style(&self.config.theme.text);
+            ctx.fill_text(label, x + 22.0, legend_y)?;
+            ctx.set_global_alpha(1.0);
+
+            let text_width = ctx.measure_text_width(label).unwrap_or(0.0);
+            boxes.push(LegendHitBox { series, x, y: legend_y - 12.0, width: 22.0 + text_width, height: 16.0 });
+            Ok(())
+        };
+
         // Daily submissions
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.primary));
-        ctx.fill_rect(legend_x, legend_y - 8.0, 16.0, 12.0);
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
-        ctx.fill_text("Submissions", legend_x + 22.0, legend_y)?;
+        entry("submissions", "Submissions", SwatchShape::Square, &self.config.theme.primary, legend_x, self.show_bars)?;
 
         // Cumulative
-        if self.show_cumulative {
-            ctx.set_stroke_style(&JsValue::from_str(&self.config.theme.success));
-            ctx.set_line_width(2.0);
-            ctx.begin_path();
-            ctx.move_to(legend_x + 100.0, legend_y - 2.0);
-            ctx.line_to(legend_x + 116.0, legend_y - 2.0);
-            ctx.stroke();
+        entry("cumulative", "Cumulative", self.cumulative_swatch(), &self.config.theme.success, legend_x + 100.0, self.show_cumulative)?;
+
+        if self.show_cumulative && !self.completions.is_empty() {
+            draw_legend_swatch(ctx, SwatchShape::Line, &self.config.theme.warning, legend_x + 200.0, legend_y - 10.0, 16.0)?;
 
-            ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
-            ctx.fill_text("Cumulative", legend_x + 122.0, legend_y)?;
+            ctx.set_fill_style(&self.config.theme.text);
+            ctx.fill_text("Completed", legend_x + 222.0, legend_y)?;
         }
 
         Ok(())
     }
 
-    fn draw_empty_state(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.secondary));
-        ctx.set_font(&format!("{}px {}", self.config.font_size, self.config.font_family));
+    fn draw_empty_state<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        ctx.set_fill_style(readable_on(&self.config.theme.secondary, &self.config.theme.background));
+        ctx.set_font(&self.config.font("", self.config.label_size()));
         ctx.set_text_align("center");
         ctx.fill_text(
             "No timeline data available",
@@ -428,24 +1167,14 @@ impl TimelineChart {
         Ok(())
     }
 
-    /// Handle mouse move
-    pub fn on_mouse_move(&mut self, x: f64, y: f64) -> JsValue {
-        let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
-        let time_span = self.time_range.1 - self.time_range.0;
-
-        if time_span <= 0.0 {
-            return serde_wasm_bindgen::to_value(&HitTestResult::miss()).unwrap();
-        }
-
-        let old_hovered = self.hovered_point;
-
-        // Find closest point
+    /// Nearest data point within 30px of the cursor x
+    fn closest_point(&self, x: f64, plot_width: f64, domain_start: f64, time_span: f64) -> Option<usize> {
         let mut min_dist = f64::INFINITY;
         let mut closest_idx: Option<usize> = None;
 
         for (i, point) in self.data.iter().enumerate() {
             let px = self.config.padding.left
-                + ((point.timestamp - self.time_range.0) / time_span) * plot_width;
+                + ((self.plot_time(point.timestamp) - domain_start) / time_span) * plot_width;
 
             let dist = (px - x).abs();
             if dist < min_dist && dist < 30.0 {
@@ -454,56 +1183,482 @@ impl TimelineChart {
             }
         }
 
-        self.hovered_point = closest_idx;
+        closest_idx
+    }
+
+    /// Handle mouse move
+    pub fn on_mouse_move(&mut self, x: f64, y: f64) -> JsValue {
+        let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
+        let (domain_start, domain_end) = self.x_domain();
+        let time_span = domain_end - domain_start;
+
+        if time_span <= 0.0 {
+            return HitTestResult::miss_value();
+        }
+
+        let old_hovered = self.hovered_point;
+
+        if self.snap_to_granularity {
+            // Map the cursor back to the bucket that contains it
+            let cursor_time = self.time_at(x);
+            let bucket = self.bucket_start(cursor_time);
+            self.hovered_point = self.data.iter().position(|p| self.bucket_start(p.timestamp) == bucket);
+        } else {
+            self.hovered_point = self.closest_point(x, plot_width, domain_start, time_span);
+        }
 
         if self.hovered_point != old_hovered {
-            self.render().ok();
+            self.refresh();
         }
 
         if let Some(idx) = self.hovered_point {
             let point = &self.data[idx];
-            let date = js_sys::Date::new(&JsValue::from_f64(point.timestamp));
-
             let result = HitTestResult::hit(
                 &format!("point-{}", idx),
                 "timeline_point",
                 serde_json::json!({
                     "index": idx,
                     "timestamp": point.timestamp,
-                    "date": format!("{}-{:02}-{:02} {:02}:{:02}",
-                        date.get_full_year(),
-                        date.get_month() + 1,
-                        date.get_date(),
-                        date.get_hours(),
-                        date.get_minutes()
-                    ),
+                    "date": self.formatters.date("timestamp", point.timestamp, "YYYY-MM-DD HH:mm"),
                     "count": point.count,
                     "cumulative": point.cumulative,
-                    "label": point.label
+                    "label": point.label,
+                    "formatted": {
+                        "count": self.formatters.number("count", point.count as f64, "0"),
+                        "cumulative": self.formatters.number("count", point.cumulative as f64, "0")
+                    }
                 }),
             );
             return serde_wasm_bindgen::to_value(&result).unwrap();
         }
 
-        serde_wasm_bindgen::to_value(&HitTestResult::miss()).unwrap()
+        HitTestResult::miss_value()
+    }
+
+    /// Timestamp under a canvas x (inverse of the x-axis mapping)
+    fn time_at(&self, x: f64) -> f64 {
+        let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
+        let (domain_start, domain_end) = self.x_domain();
+        domain_start + ((x - self.config.padding.left) / plot_width) * (domain_end - domain_start)
+    }
+
+    /// Convert canvas coordinates to data space: `{timestamp, count, cumulative}`,
+    /// where `count` reads the bar axis and `cumulative` the line axis
+    pub fn screen_to_data(&self, x: f64, y: f64) -> JsValue {
+        let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
+        let from_bottom = self.config.height - self.config.padding.bottom - y;
+
+        let result = serde_json::json!({
+            "timestamp": self.time_at(x),
+            "count": from_bottom / plot_height * self.count_scale(),
+            "cumulative": from_bottom / plot_height * self.cumulative_scale()
+        });
+        serde_wasm_bindgen::to_value(&result).unwrap()
     }
 
     /// Get statistics
     pub fn get_stats(&self) -> JsValue {
-        let total_submissions: u32 = self.data.iter().map(|d| d.count).sum();
-        let peak_day = self.data.iter().max_by_key(|d| d.count);
+        let total_submissions: u32 = self.raw_data.iter().map(|d| d.count).sum();
+        let peak_day = self.raw_data.iter().max_by_key(|d| d.count);
+        let completed = self.completions.last().map(|c| c.completed);
 
         let stats = serde_json::json!({
             "totalSubmissions": total_submissions,
-            "dataPoints": self.data.len(),
+            "dataPoints": self.raw_data.len(),
+            "renderedPoints": self.data.len(),
             "peakCount": peak_day.map(|p| p.count).unwrap_or(0),
             "peakTimestamp": peak_day.map(|p| p.timestamp),
             "timeRange": {
                 "start": self.time_range.0,
                 "end": self.time_range.1
             },
-            "eventCount": self.events.len()
+            "eventCount": self.events.len(),
+            "completed": completed,
+            "backlog": completed.map(|c| total_submissions.saturating_sub(c))
         });
         serde_wasm_bindgen::to_value(&stats).unwrap()
     }
+
+    /// One-sentence summary for screen readers (e.g. a canvas `aria-label`)
+    pub fn describe(&self) -> String {
+        let total_submissions: u32 = self.raw_data.iter().map(|d| d.count).sum();
+        // First point wins ties so the wording is stable
+        let peak = self.raw_data.iter().fold(None::<&TimelineDataPoint>, |best, d| match best {
+            Some(best) if best.count >= d.count => Some(best),
+            _ => Some(d),
+        });
+        let Some(peak) = peak else {
+            return "Submission timeline with no data".to_string();
+        };
+
+        let days = ((self.time_range.1 - self.time_range.0) / DAY_MS).ceil().max(1.0);
+        let mut text = format!(
+            "Submission timeline of {} submissions over {} days; peak {} on {}",
+            total_submissions,
+            days,
+            peak.count,
+            self.formatters.date("timestamp", peak.timestamp, "YYYY-MM-DD")
+        );
+        if let Some(last) = self.completions.last() {
+            text.push_str(&format!(
+                "; {} completed, backlog {}",
+                last.completed,
+                total_submissions.saturating_sub(last.completed)
+            ));
+        }
+        text
+    }
+}
+
+/// Downsample a time-ordered series to `threshold` points using
+/// largest-triangle-three-buckets on the count values.
+///
+/// The first, last and highest-count points are always kept.
+fn downsample_lttb(data: &[TimelineDataPoint], threshold: usize) -> Vec<TimelineDataPoint> {
+    let len = data.len();
+    if threshold >= len || threshold < 3 {
+        return data.to_vec();
+    }
+
+    let peak = data
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, d)| d.count)
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+
+    let every = (len - 2) as f64 / (threshold - 2) as f64;
+    let mut sampled = Vec::with_capacity(threshold);
+    sampled.push(data[0].clone());
+    let mut a = 0;
+
+    for i in 0..threshold - 2 {
+        // Average of the next bucket is the third triangle vertex
+        let avg_start = ((i + 1) as f64 * every) as usize + 1;
+        let avg_end = (((i + 2) as f64 * every) as usize + 1).min(len);
+        let avg_len = (avg_end - avg_start) as f64;
+        let avg_x = data[avg_start..avg_end].iter().map(|d| d.timestamp).sum::<f64>() / avg_len;
+        let avg_y = data[avg_start..avg_end].iter().map(|d| d.count as f64).sum::<f64>() / avg_len;
+
+        let range_start = (i as f64 * every) as usize + 1;
+        let range_end = ((i + 1) as f64 * every) as usize + 1;
+
+        let next = if (range_start..range_end).contains(&peak) {
+            peak
+        } else {
+            let (ax, ay) = (data[a].timestamp, data[a].count as f64);
+            (range_start..range_end)
+                .max_by(|&j, &k| {
+                    let area = |idx: usize| {
+                        ((ax - avg_x) * (data[idx].count as f64 - ay)
+                            - (ax - data[idx].timestamp) * (avg_y - ay))
+                            .abs()
+                    };
+                    area(j).total_cmp(&area(k))
+                })
+                .unwrap_or(range_start)
+        };
+
+        sampled.push(data[next].clone());
+        a = next;
+    }
+
+    sampled.push(data[len - 1].clone());
+    sampled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::charts::RecordingTarget;
+
+    fn series(len: usize) -> Vec<TimelineDataPoint> {
+        let mut cumulative = 0;
+        (0..len)
+            .map(|i| {
+                // Deterministic noise with one spike well above it
+                let count = if i == 7321 { 500 } else { ((i * 37) % 50) as u32 };
+                cumulative += count;
+                TimelineDataPoint {
+                    timestamp: i as f64 * 60_000.0,
+                    count,
+                    cumulative,
+                    label: None,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lttb_keeps_endpoints_peak_and_threshold_length() {
+        let data = series(10_000);
+        let sampled = downsample_lttb(&data, 200);
+
+        assert_eq!(sampled.len(), 200);
+        assert_eq!(sampled[0].timestamp, data[0].timestamp);
+        assert_eq!(sampled[199].timestamp, data[9_999].timestamp);
+        assert!(sampled.iter().any(|d| d.count == 500), "global max dropped");
+        assert!(sampled.windows(2).all(|w| w[0].timestamp < w[1].timestamp));
+    }
+
+    #[test]
+    fn lttb_leaves_short_series_untouched() {
+        let data = series(50);
+        assert_eq!(downsample_lttb(&data, 200).len(), 50);
+        assert_eq!(downsample_lttb(&data, 2).len(), 50);
+    }
+
+    #[test]
+    fn describe_without_points_says_so() {
+        let chart = TimelineChart::with_config("test", ChartConfig::default());
+        assert_eq!(chart.describe(), "Submission timeline with no data");
+    }
+
+    fn chart_with(points: &[(u32, u32)]) -> TimelineChart {
+        let mut chart = TimelineChart::with_config("test", ChartConfig::default());
+        chart.load_points(
+            points
+                .iter()
+                .enumerate()
+                .map(|(i, &(count, cumulative))| TimelineDataPoint {
+                    timestamp: i as f64 * DAY_MS,
+                    count,
+                    cumulative,
+                    label: None,
+                })
+                .collect(),
+        );
+        chart
+    }
+
+    #[test]
+    fn shared_axis_scales_bars_and_line_to_the_cumulative_max() {
+        let mut chart = chart_with(&[(5, 5), (10, 15), (25, 40)]);
+        chart.independent_axes = false;
+        assert_eq!(chart.count_scale(), 40.0);
+        assert_eq!(chart.cumulative_scale(), 40.0);
+
+        let target = RecordingTarget::new();
+        chart.draw_y_axes(&target).unwrap();
+        // Only the left axis line and its six labels; the right axis is omitted
+        assert_eq!(target.count("stroke"), 1);
+        assert_eq!(target.texts(), vec!["0", "8", "16", "24", "32", "40"]);
+    }
+
+    #[test]
+    fn independent_axes_leave_bar_headroom_and_draw_the_right_axis() {
+        let chart = chart_with(&[(5, 5), (10, 15), (20, 35), (0, 35)]);
+        assert_eq!(chart.count_scale(), 25.0);
+        assert_eq!(chart.cumulative_scale(), 35.0);
+
+        let target = RecordingTarget::new();
+        chart.draw_y_axes(&target).unwrap();
+        assert_eq!(target.count("stroke"), 2);
+        assert_eq!(target.texts().len(), 12);
+        assert_eq!(target.texts()[11], "35");
+    }
+
+    #[test]
+    fn sparkline_zeroes_padding_and_restores_only_what_it_overrode() {
+        let mut chart = chart_with(&[(5, 5), (10, 15)]);
+        let padding = chart.config.padding;
+
+        chart.apply_sparkline(true);
+        let p = chart.config.padding;
+        assert_eq!((p.top, p.right, p.bottom, p.left), (0.0, 0.0, 0.0, 0.0));
+        assert!(!chart.config.show_grid && !chart.config.show_labels && !chart.config.show_legend);
+
+        // Changes made while the sparkline is shown survive leaving it
+        chart.config.font_size = 16.0;
+        chart.config.theme.primary = "#123456".to_string();
+        chart.apply_sparkline(false);
+        let p = chart.config.padding;
+        assert_eq!((p.top, p.right, p.bottom, p.left), (padding.top, padding.right, padding.bottom, padding.left));
+        assert!(chart.config.show_grid && chart.config.show_labels && chart.config.show_legend);
+        assert_eq!(chart.config.font_size, 16.0);
+        assert_eq!(chart.config.theme.primary, "#123456");
+    }
+
+    #[test]
+    fn triangle_markers_draw_a_triangle_legend_swatch() {
+        let mut chart = chart_with(&[(5, 5), (10, 15)]);
+        chart.marker_shape = SwatchShape::Triangle;
+        let target = RecordingTarget::new();
+        chart.draw_legend(&target).unwrap();
+
+        let ops: Vec<&str> = target.calls().iter().map(|c| c.op).collect();
+        let path = ["move_to", "line_to", "line_to", "close_path", "fill"];
+        assert!(ops.windows(path.len()).any(|w| w == path), "{:?}", ops);
+
+        // Without markers the series is only a line again
+        chart.point_markers = false;
+        let target = RecordingTarget::new();
+        chart.draw_legend(&target).unwrap();
+        assert_eq!(target.count("close_path"), 0);
+        assert_eq!(target.count("stroke"), 1);
+    }
+
+    #[test]
+    fn full_height_bar_label_stays_inside_the_plot() {
+        let mut chart = chart_with(&[(1, 1), (10, 10)]);
+        chart.independent_axes = false;
+        chart.show_cumulative = true;
+        chart.value_labels = ValueLabelMode::Always;
+
+        let target = RecordingTarget::new();
+        chart.draw_bars(&target).unwrap();
+        let label_ys: Vec<f64> = target.calls().iter().filter(|c| c.op == "fill_text").map(|c| c.args[1]).collect();
+        assert_eq!(label_ys.len(), 2);
+        assert!(label_ys[0] > chart.config.padding.top);
+        assert_eq!(label_ys[1], chart.config.padding.top);
+    }
+
+    #[test]
+    fn min_bar_height_is_held_to_the_plot() {
+        let mut chart = chart_with(&[(1, 1), (0, 1), (400, 401)]);
+        chart.min_bar_height = 1000.0;
+        let plot_height = chart.config.height - chart.config.padding.top - chart.config.padding.bottom;
+
+        let target = RecordingTarget::new();
+        chart.draw_bars(&target).unwrap();
+        // Each bar's first corner curve ends on its top edge
+        let bottom = chart.config.height - chart.config.padding.bottom;
+        let heights: Vec<f64> = target
+            .calls()
+            .iter()
+            .filter(|c| c.op == "quadratic_curve_to")
+            .step_by(2)
+            .map(|c| bottom - c.args[3])
+            .collect();
+        assert_eq!(heights, vec![plot_height, 0.0, plot_height]);
+    }
+
+    #[test]
+    fn snapped_same_day_points_share_their_bucket_x() {
+        let mut chart = TimelineChart::with_config("test", ChartConfig::default());
+        chart.snap_to_granularity = true;
+        chart.load_points(
+            [(2.0 * HOUR_MS, 3), (20.0 * HOUR_MS, 4), (DAY_MS + 9.0 * HOUR_MS, 5)]
+                .iter()
+                .map(|&(timestamp, count)| TimelineDataPoint { timestamp, count, cumulative: count, label: None })
+                .collect(),
+        );
+        assert_eq!(chart.plot_time(2.0 * HOUR_MS), chart.plot_time(20.0 * HOUR_MS));
+
+        let target = RecordingTarget::new();
+        chart.draw_bars(&target).unwrap();
+        let xs: Vec<f64> = target.calls().iter().filter(|c| c.op == "move_to").map(|c| c.args[0]).collect();
+        assert_eq!(xs.len(), 3);
+        assert_eq!(xs[0], xs[1]);
+        assert!(xs[2] > xs[1]);
+    }
+
+    #[test]
+    fn weekend_shading_spans_the_weekend_days() {
+        let chart = chart_with(&[(1, 1); 8]);
+        // A week from Thursday 1970-01-01 UTC; Saturday and Sunday are days 2 and 3
+        let days: Vec<(f64, f64, bool)> = (0..7)
+            .map(|d| (d as f64 * DAY_MS, (d + 1) as f64 * DAY_MS, d == 2 || d == 3))
+            .collect();
+
+        let target = RecordingTarget::new();
+        chart.draw_calendar_days(&target, &days);
+
+        let plot_width = chart.config.width - chart.config.padding.left - chart.config.padding.right;
+        let day_px = plot_width / 7.0;
+        let shaded: Vec<(f64, f64)> = target
+            .calls()
+            .iter()
+            .filter(|c| c.op == "fill_rect")
+            .map(|c| (c.args[0], c.args[0] + c.args[2]))
+            .collect();
+        assert_eq!(shaded.len(), 2);
+        let left = chart.config.padding.left;
+        assert!((shaded[0].0 - (left + 2.0 * day_px)).abs() < 1e-9);
+        assert!((shaded[0].1 - shaded[1].0).abs() < 1e-9);
+        assert!((shaded[1].1 - (left + 4.0 * day_px)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn count_format_compacts_thousands_only_when_asked() {
+        let mut chart = TimelineChart::with_config("test", ChartConfig::default());
+        chart.count_decimals = 0;
+
+        chart.count_compact = false;
+        assert_eq!(chart.format_count(1500.0), "1500");
+        chart.count_compact = true;
+        assert_eq!(chart.format_count(1500.0), "1.5k");
+        assert_eq!(chart.format_count(42.0), "42");
+    }
+
+    #[test]
+    fn dense_markers_are_thinned_to_the_minimum_spacing() {
+        let points: Vec<(u32, u32)> = (1..=100).map(|i| (1, i)).collect();
+        let mut chart = chart_with(&points);
+        let marker_xs = |chart: &TimelineChart| {
+            let target = RecordingTarget::new();
+            chart.draw_cumulative_line(&target).unwrap();
+            target.calls().iter().filter(|c| c.op == "arc").map(|c| c.args[0]).collect::<Vec<f64>>()
+        };
+
+        // ~7px apart, so every point gets a marker by default
+        assert_eq!(marker_xs(&chart).len(), 100);
+
+        chart.marker_min_spacing = 20.0;
+        let thinned = marker_xs(&chart);
+        assert!(thinned.len() < 40 && thinned.len() > 25, "{} markers", thinned.len());
+        assert!(thinned.windows(2).all(|w| w[1] - w[0] >= 20.0));
+
+        // The hovered point shows even where thinning would drop it
+        chart.hover_effect = true;
+        chart.hovered_point = Some(1);
+        assert_eq!(marker_xs(&chart).len(), thinned.len() + 1);
+    }
+
+    #[test]
+    fn bars_are_spaced_by_time_not_index() {
+        let mut chart = TimelineChart::with_config("test", ChartConfig::default());
+        chart.load_points(
+            [0.0, 1.0, 30.0]
+                .iter()
+                .map(|&day| TimelineDataPoint { timestamp: day * DAY_MS, count: 5, cumulative: 5, label: None })
+                .collect(),
+        );
+
+        let target = RecordingTarget::new();
+        chart.draw_bars(&target).unwrap();
+        let xs: Vec<f64> = target.calls().iter().filter(|c| c.op == "move_to").map(|c| c.args[0]).collect();
+        assert_eq!(xs.len(), 3);
+        // A 29-day gap is 29 times the gap between neighboring days
+        let day_px = xs[1] - xs[0];
+        assert!(day_px > 0.0);
+        assert!(((xs[2] - xs[1]) / day_px - 29.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn backlog_fills_the_gap_between_submitted_and_completed() {
+        let mut chart = chart_with(&[(10, 10), (10, 20), (10, 30), (10, 40)]);
+        chart.completions = [2, 8, 14, 20]
+            .iter()
+            .enumerate()
+            .map(|(i, &completed)| CompletionPoint { timestamp: i as f64 * DAY_MS, completed })
+            .collect();
+
+        let target = RecordingTarget::new();
+        chart.draw_completion_series(&target).unwrap();
+        let calls = target.calls();
+        let close = calls.iter().position(|c| c.op == "close_path").unwrap();
+        let fill_start = calls[..close].iter().rposition(|c| c.op == "move_to").unwrap();
+        let outline: Vec<(f64, f64)> = calls[fill_start..close].iter().map(|c| (c.args[0], c.args[1])).collect();
+        assert_eq!(outline.len(), 8);
+
+        // At day 2 the shape spans from the submissions line down to the completions line
+        let plot_height = chart.config.height - chart.config.padding.top - chart.config.padding.bottom;
+        let bottom = chart.config.height - chart.config.padding.bottom;
+        let scale = chart.cumulative_scale();
+        let sample_x = outline[2].0;
+        let mut ys: Vec<f64> = outline.iter().filter(|p| p.0 == sample_x).map(|p| p.1).collect();
+        ys.sort_by(f64::total_cmp);
+        assert_eq!(ys, vec![bottom - 30.0 / scale * plot_height, bottom - 14.0 / scale * plot_height]);
+    }
 }
@@ -5,9 +5,12 @@
 
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
-use web_sys::CanvasRenderingContext2d;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+use std::cell::Cell;
+use std::collections::HashMap;
 
-use super::common::{get_canvas_context, clear_canvas, ChartConfig, HitTestResult, interpolate_color};
+use super::common::{get_canvas_context, create_offscreen_canvas, track_context_loss, surface_context_loss, clear_canvas, canvas_color, readable_on, payload_hash, is_same_payload, render_image_data, now_ms, RenderTiming, ChartConfig, HitTestResult, ValueFormatters, interpolate_color, truncate_to_width};
+use super::draw_target::DrawTarget;
 
 /// Variance data for a single application
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -16,11 +19,36 @@ pub struct VarianceDataPoint {
     pub reference: String,
     pub scores: Vec<f64>,
     pub assessor_names: Vec<String>,
+    /// Stable assessor ids parallel to `scores`, given for every row or none;
+    /// without them scores are placed by position
+    #[serde(default)]
+    pub assessor_ids: Vec<String>,
     pub variance: f64,
     pub mean: f64,
     pub flagged: bool,
 }
 
+/// How application references are shortened in row labels
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ReferenceDisplay {
+    Full,
+    Suffix,
+    Prefix,
+}
+
+/// Variance distribution inset size and bin count
+const INSET_WIDTH: f64 = 160.0;
+const INSET_HEIGHT: f64 = 70.0;
+const INSET_BINS: usize = 20;
+
+/// Maximum characters shown in a row label before truncation
+const MAX_REFERENCE_CHARS: usize = 12;
+/// Room (px) for row labels, right-aligned inside the reserved 100px label area
+const ROW_LABEL_MAX_WIDTH: f64 = 85.0;
+
+/// Duration of the color tween on changed cells
+const CELL_TRANSITION_MS: f64 = 300.0;
+
 /// Cell position in the heatmap
 #[derive(Clone, Debug)]
 struct CellPosition {
@@ -32,6 +60,15 @@ struct CellPosition {
     height: f64,
 }
 
+/// In-flight color tween for a cell whose score changed
+#[derive(Clone, Debug)]
+struct CellTransition {
+    application_id: String,
+    column: String,
+    from_color: String,
+    elapsed_ms: f64,
+}
+
 /// Variance heatmap chart
 #[wasm_bindgen]
 pub struct VarianceHeatmapChart {
@@ -39,11 +76,37 @@ pub struct VarianceHeatmapChart {
     config: ChartConfig,
     data: Vec<VarianceDataPoint>,
     max_assessors: usize,
+    // Global assessor column order (first-seen across rows)
+    column_ids: Vec<String>,
+    // Per row, per global column: index into that row's `scores`
+    cell_index: Vec<Vec<Option<usize>>>,
     variance_threshold: f64,
+    show_variance_distribution: bool,
+    flag_row_highlight: bool,
     cell_positions: Vec<CellPosition>,
     hovered_cell: Option<(usize, usize)>,
+    hover_effect: bool,
+    // Score text in grid cells; off leaves cells color-only (hover for values)
+    show_cell_values: bool,
+    show_variance_values: bool,
+    // Remap the hovered cell by application id on reload instead of clearing it
+    preserve_selection: bool,
     scroll_offset: f64,
-    visible_rows: usize,
+    // Target row height; the visible row count follows the canvas height
+    row_height: f64,
+    // Minimum assessor column width; 0 fits every column without horizontal scrolling
+    min_column_width: f64,
+    scroll_x_offset: f64,
+    reference_display: ReferenceDisplay,
+    reference_separator: char,
+    formatters: ValueFormatters,
+    cell_change_animation: bool,
+    reduced_motion: bool,
+    cell_transitions: Vec<CellTransition>,
+    // Fingerprint of the last loaded payload
+    data_hash: Option<u64>,
+    render_timing: RenderTiming,
+    context_lost: Cell<bool>,
 }
 
 #[wasm_bindgen]
@@ -53,18 +116,41 @@ impl VarianceHeatmapChart {
     pub fn new(canvas_id: &str, config_js: JsValue) -> Result<VarianceHeatmapChart, JsValue> {
         let config: ChartConfig = serde_wasm_bindgen::from_value(config_js)
             .unwrap_or_else(|_| ChartConfig::default());
+        Ok(Self::with_config(canvas_id, config))
+    }
 
-        Ok(Self {
+    /// Create a chart from an already-parsed config
+    pub(crate) fn with_config(canvas_id: &str, config: ChartConfig) -> Self {
+Self {
             canvas_id: canvas_id.to_string(),
             config,
             data: Vec::new(),
             max_assessors: 0,
+            column_ids: Vec::new(),
+            cell_index: Vec::new(),
             variance_threshold: 10.0,
+            show_variance_distribution: false,
+            flag_row_highlight: false,
             cell_positions: Vec::new(),
             hovered_cell: None,
+            hover_effect: true,
+            show_cell_values: true,
+            show_variance_values: true,
+            preserve_selection: true,
             scroll_offset: 0.0,
-            visible_rows: 20,
-        })
+            row_height: 15.0,
+            min_column_width: 0.0,
+            scroll_x_offset: 0.0,
+            reference_display: ReferenceDisplay::Full,
+            reference_separator: '-',
+            formatters: ValueFormatters::default(),
+            cell_change_animation: false,
+            reduced_motion: false,
+            cell_transitions: Vec::new(),
+            data_hash: None,
+            render_timing: RenderTiming::default(),
+            context_lost: Cell::new(false),
+        }
     }
 
     /// Set the variance threshold for flagging
@@ -72,37 +158,227 @@ impl VarianceHeatmapChart {
         self.variance_threshold = threshold;
     }
 
+    /// Tint flagged rows across the whole grid so they stand out while scrolling
+    pub fn set_flag_row_highlight(&mut self, enabled: bool) {
+        self.flag_row_highlight = enabled;
+        self.refresh();
+    }
+
+    /// Draw the score inside each grid cell; turn off for dense data where the
+    /// numbers are unreadable and hover gives exact values
+    pub fn set_show_cell_values(&mut self, enabled: bool) {
+        self.show_cell_values = enabled;
+        self.refresh();
+    }
+
+    /// Draw the variance number in the variance column
+    pub fn set_show_variance_values(&mut self, enabled: bool) {
+        self.show_variance_values = enabled;
+        self.refresh();
+    }
+
+    /// Show an inset histogram of row variances with a draggable threshold handle
+    pub fn set_show_variance_distribution(&mut self, show: bool) {
+        self.show_variance_distribution = show;
+        self.refresh();
+    }
+
+    /// Drag the inset threshold handle to canvas x; updates the threshold and re-flags rows
+    pub fn on_threshold_drag(&mut self, x: f64) -> JsValue {
+        if !self.drag_threshold_to(x) {
+            return HitTestResult::miss_value();
+        }
+        self.refresh();
+
+        serde_wasm_bindgen::to_value(&serde_json::json!({
+            "threshold": self.variance_threshold,
+            "flaggedCount": self.flagged_count()
+        })).unwrap()
+    }
+
+    /// Move the threshold to the inset position under canvas x; false when
+    /// there is no inset to drag
+    fn drag_threshold_to(&mut self, x: f64) -> bool {
+        if !self.show_variance_distribution || self.data.is_empty() {
+            return false;
+        }
+
+        let (inset_x, _, inset_width, _) = self.inset_rect();
+        let t = ((x - inset_x) / inset_width).clamp(0.0, 1.0);
+        self.variance_threshold = t * self.max_variance();
+        true
+    }
+
+    /// Whether a row is flagged at the current threshold. The payload's own
+    /// `flagged` value is kept as sent and not used for display.
+    fn is_flagged(&self, data: &VarianceDataPoint) -> bool {
+        data.variance > self.variance_threshold
+    }
+
+    fn flagged_count(&self) -> usize {
+        self.data.iter().filter(|d| self.is_flagged(d)).count()
+    }
+
+    /// Change one score in place and recompute only that row's mean and variance.
+    /// `column` is the heatmap column, as reported by hover, so it names the same
+    /// assessor in every row. Returns `{variance, mean, flagged}`, flagged at the
+    /// current threshold.
+    pub fn update_score(&mut self, application_id: &str, column: usize, new_score: f64) -> Result<JsValue, JsValue> {
+        let before = self.snapshot_cell_colors();
+        let row = self.set_score(application_id, column, new_score).map_err(|e| JsValue::from_str(&e))?;
+        let data = &self.data[row];
+        let result = serde_json::json!({
+            "variance": data.variance,
+            "mean": data.mean,
+            "flagged": self.is_flagged(data)
+        });
+
+        // The loaded payload no longer matches what was sent
+        self.data_hash = None;
+        self.start_cell_transitions(before);
+        self.refresh();
+
+        Ok(serde_wasm_bindgen::to_value(&result)?)
+    }
+
+    /// Write a score into the cell at `column` and refresh that row's mean and
+    /// variance; returns the row index
+    fn set_score(&mut self, application_id: &str, column: usize, new_score: f64) -> Result<usize, String> {
+        let row = self.data.iter()
+            .position(|d| d.application_id == application_id)
+            .ok_or_else(|| format!("Unknown application: {}", application_id))?;
+        let index = self.score_index(row, column)
+            .ok_or_else(|| format!("No score in column {} for application {}", column, application_id))?;
+
+        let data = &mut self.data[row];
+        data.scores[index] = new_score;
+        let n = data.scores.len() as f64;
+        data.mean = data.scores.iter().sum::<f64>() / n;
+        data.variance = data.scores.iter().map(|s| (s - data.mean).powi(2)).sum::<f64>() / n;
+        Ok(row)
+    }
+
+    /// Set how row labels shorten references: "full", "suffix" or "prefix".
+    /// The separator (default "-") splits structured references like "APP-2024-0423".
+    pub fn set_reference_display(&mut self, mode: &str, separator: Option<String>) {
+        self.reference_display = match mode {
+            "suffix" => ReferenceDisplay::Suffix,
+            "prefix" => ReferenceDisplay::Prefix,
+            _ => ReferenceDisplay::Full,
+        };
+        self.reference_separator = separator
+            .and_then(|s| s.chars().next())
+            .unwrap_or('-');
+    }
+
     /// Set data and compute layout
     pub fn set_data(&mut self, data_js: JsValue) -> Result<(), JsValue> {
+        self.set_data_with_options(data_js, false)
+    }
+
+    /// Set data and compute layout; identical payloads are skipped unless `force` is set
+    pub fn set_data_with_options(&mut self, data_js: JsValue, force: bool) -> Result<(), JsValue> {
+        let hash = payload_hash(&[&data_js]);
+        if is_same_payload(hash, self.data_hash, force) {
+            return Ok(());
+        }
+
+        self.load_data(data_js)?;
+        self.data_hash = hash;
+        Ok(())
+    }
+
+    fn load_data(&mut self, data_js: JsValue) -> Result<(), JsValue> {
         let data: Vec<VarianceDataPoint> = serde_wasm_bindgen::from_value(data_js)?;
+        check_assessor_ids(&data).map_err(|e| JsValue::from_str(&e))?;
+        self.load_rows(data);
+        Ok(())
+    }
+
+    /// Replace the rows with parsed data and rebuild the layout
+    fn load_rows(&mut self, data: Vec<VarianceDataPoint>) {
+        let before = self.snapshot_cell_colors();
+        let hovered = self.hovered_cell
+            .and_then(|(row, col)| self.data.get(row).map(|d| (d.application_id.clone(), self.column_key(col))));
 
-        self.max_assessors = data.iter().map(|d| d.scores.len()).max().unwrap_or(0);
         self.data = data;
+        self.build_columns();
         self.scroll_offset = 0.0;
+        self.scroll_x_offset = 0.0;
+        self.start_cell_transitions(before);
+
+        self.hovered_cell = match hovered {
+            Some((application_id, column)) if self.preserve_selection => {
+                let row = self.data.iter().position(|d| d.application_id == application_id);
+                let col = (0..self.max_assessors).find(|&c| self.column_key(c) == column);
+                row.zip(col)
+            }
+            _ => None,
+        };
 
         self.compute_cell_positions();
-        Ok(())
+    }
+
+    /// Align assessors to global columns so the same assessor always shares a column
+    fn build_columns(&mut self) {
+        let mut column_of: HashMap<String, usize> = HashMap::new();
+        self.column_ids.clear();
+
+        for data in &self.data {
+            for id in &data.assessor_ids {
+                if !column_of.contains_key(id) {
+                    column_of.insert(id.clone(), self.column_ids.len());
+                    self.column_ids.push(id.clone());
+                }
+            }
+        }
+
+        let positional = self.data.iter()
+            .filter(|d| d.assessor_ids.is_empty())
+            .map(|d| d.scores.len())
+            .max()
+            .unwrap_or(0);
+        self.max_assessors = self.column_ids.len().max(positional);
+
+        self.cell_index = self.data.iter()
+            .map(|data| {
+                let mut row = vec![None; self.max_assessors];
+                if data.assessor_ids.is_empty() {
+                    for (i, slot) in row.iter_mut().enumerate().take(data.scores.len()) {
+                        *slot = Some(i);
+                    }
+                } else {
+                    // A repeated id keeps its first score
+                    for (i, id) in data.assessor_ids.iter().enumerate().take(data.scores.len()) {
+                        row[column_of[id]].get_or_insert(i);
+                    }
+                }
+                row
+            })
+            .collect();
+    }
+
+    /// Index into a row's `scores` for a global column
+    fn score_index(&self, row: usize, col: usize) -> Option<usize> {
+        self.cell_index.get(row).and_then(|r| r.get(col)).copied().flatten()
     }
 
     fn compute_cell_positions(&mut self) {
         self.cell_positions.clear();
 
-        let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
         let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
 
         // Calculate cell dimensions
-        let row_count = self.visible_rows.min(self.data.len());
-        let col_count = self.max_assessors.max(1);
-
-        let cell_width = (plot_width - 100.0) / col_count as f64; // Reserve 100px for labels
+        let row_count = self.visible_rows().min(self.data.len());
+        let (start_col, col_count, cell_width) = self.column_layout();
         let cell_height = plot_height / row_count as f64;
 
         let start_row = (self.scroll_offset / cell_height) as usize;
         let end_row = (start_row + row_count + 1).min(self.data.len());
 
         for row in start_row..end_row {
-            for col in 0..col_count {
-                let x = self.config.padding.left + 100.0 + col as f64 * cell_width;
+            for col in start_col..start_col + col_count {
+                let x = self.config.padding.left + 100.0 + (col - start_col) as f64 * cell_width;
                 let y = self.config.padding.top + (row - start_row) as f64 * cell_height;
 
                 self.cell_positions.push(CellPosition {
@@ -117,46 +393,335 @@ impl VarianceHeatmapChart {
         }
     }
 
+    /// Tween the background of cells whose score changed (via `update_score` or
+    /// new data) from the old color to the new one; drive it with `animate`
+    pub fn set_cell_change_animation(&mut self, enabled: bool) {
+        self.cell_change_animation = enabled;
+        if !enabled {
+            self.cell_transitions.clear();
+        }
+    }
+
+    /// Skip cell change tweens (prefers-reduced-motion)
+    pub fn set_reduced_motion(&mut self, enabled: bool) {
+        self.reduced_motion = enabled;
+        if enabled {
+            self.cell_transitions.clear();
+        }
+    }
+
+    /// Advance cell change tweens; returns true while any are still running
+    pub fn animate(&mut self, delta_ms: f64) -> bool {
+        if self.cell_transitions.is_empty() {
+            return false;
+        }
+
+        for transition in &mut self.cell_transitions {
+            transition.elapsed_ms += delta_ms;
+        }
+        self.cell_transitions.retain(|t| t.elapsed_ms < CELL_TRANSITION_MS);
+
+        self.refresh();
+        !self.cell_transitions.is_empty()
+    }
+
+    /// Stable key for a global column: assessor id, or position for id-less rows
+    fn column_key(&self, col: usize) -> String {
+        self.column_ids.get(col).cloned().unwrap_or_else(|| col.to_string())
+    }
+
+    /// Resting background color of a cell
+    fn cell_color(&self, row: usize, col: usize) -> String {
+        match self.score_index(row, col).map(|i| self.data[row].scores[i]) {
+            // Color based on score value (normalized to 0-100)
+            Some(s) => interpolate_color(&self.config.theme.danger, &self.config.theme.success, (s / 100.0).clamp(0.0, 1.0)),
+            None => self.config.theme.grid.clone(),
+        }
+    }
+
+    /// Colors of every cell keyed by (application id, column key); empty when animation is off
+    fn snapshot_cell_colors(&self) -> HashMap<(String, String), String> {
+        if !self.cell_change_animation || self.reduced_motion {
+            return HashMap::new();
+        }
+
+        let mut colors = HashMap::new();
+        for (row, data) in self.data.iter().enumerate() {
+            for col in 0..self.max_assessors {
+                colors.insert((data.application_id.clone(), self.column_key(col)), self.cell_color(row, col));
+            }
+        }
+        colors
+    }
+
+    /// Start tweens for cells whose color differs from the snapshot; new cells don't animate
+    fn start_cell_transitions(&mut self, before: HashMap<(String, String), String>) {
+        if before.is_empty() {
+            return;
+        }
+
+        for row in 0..self.data.len() {
+            for col in 0..self.max_assessors {
+                let key = (self.data[row].application_id.clone(), self.column_key(col));
+                let Some(from_color) = before.get(&key) else {
+                    continue;
+                };
+                if *from_color == self.cell_color(row, col) {
+                    continue;
+                }
+
+                self.cell_transitions.retain(|t| t.application_id != key.0 || t.column != key.1);
+                self.cell_transitions.push(CellTransition {
+                    application_id: key.0,
+                    column: key.1,
+                    from_color: from_color.clone(),
+                    elapsed_ms: 0.0,
+                });
+            }
+        }
+    }
+
+    /// Background color to draw now, mid-tween if the cell recently changed
+    fn current_cell_color(&self, row: usize, col: usize) -> String {
+        let color = self.cell_color(row, col);
+        if self.cell_transitions.is_empty() {
+            return color;
+        }
+
+        let application_id = &self.data[row].application_id;
+        let column = self.column_key(col);
+        match self.cell_transitions.iter().find(|t| &t.application_id == application_id && t.column == column) {
+            Some(t) => interpolate_color(&t.from_color, &color, t.elapsed_ms / CELL_TRANSITION_MS),
+            None => color,
+        }
+    }
+
+    /// Keep the hovered cell across `set_data` when its application and assessor
+    /// still exist (default); when off, a reload clears it
+    pub fn set_preserve_selection(&mut self, enabled: bool) {
+        self.preserve_selection = enabled;
+    }
+
+    /// Set the target row height in px; the number of visible rows is derived from the plot height
+    pub fn set_row_height(&mut self, px: f64) {
+        self.row_height = px.max(1.0);
+        self.on_scroll(0.0);
+    }
+
+    /// Rows that fit in the plot area at the target row height
+    fn visible_rows(&self) -> usize {
+        let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
+        ((plot_height / self.row_height).floor() as usize).max(1)
+    }
+
+    /// Set a minimum assessor column width in px; columns that don't fit scroll
+    /// horizontally with `on_horizontal_scroll` (0 = fit all columns)
+    pub fn set_min_column_width(&mut self, px: f64) {
+        self.min_column_width = px.max(0.0);
+        self.on_horizontal_scroll(0.0);
+    }
+
+    /// First visible column, visible column count and column width
+    fn column_layout(&self) -> (usize, usize, f64) {
+        let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
+        let grid_width = plot_width - 100.0; // Reserve 100px for labels
+        let col_count = self.max_assessors.max(1);
+
+        let visible = if self.min_column_width > 0.0 {
+            ((grid_width / self.min_column_width).floor() as usize).clamp(1, col_count)
+        } else {
+            col_count
+        };
+        let cell_width = grid_width / visible as f64;
+        let start_col = ((self.scroll_x_offset / cell_width) as usize).min(col_count - visible);
+        (start_col, visible, cell_width)
+    }
+
+    /// Enable or disable the hover highlight on cells (the hover outline is kept)
+    pub fn set_hover_effect(&mut self, enabled: bool) {
+        self.hover_effect = enabled;
+        self.refresh();
+    }
+
+    /// Set the display format for a value kind used in tooltips and overlay text.
+    ///
+    /// Numbers accept specs like `"0.0"` or `"#,##0"`; timestamps accept date
+    /// patterns like `"YYYY-MM-DD HH:mm"`. An empty format restores the default.
+    pub fn set_value_formatter(&mut self, kind: &str, format: &str) {
+        self.formatters.set(kind, format);
+        self.refresh();
+    }
+
+    /// Replace the color theme (a `ColorTheme` object) and re-render
+    pub fn set_theme(&mut self, theme_js: JsValue) -> Result<(), JsValue> {
+        self.config.theme = serde_wasm_bindgen::from_value(theme_js)?;
+        // In-flight transitions interpolate between old theme colors
+        self.cell_transitions.clear();
+        self.render()
+    }
+
+    /// Whether the last render failed because the 2D context was lost
+    pub fn is_context_lost(&self) -> bool {
+        self.context_lost.get()
+    }
+
+    /// Re-acquire the canvas context after a loss and re-render
+    pub fn restore(&mut self) -> Result<(), JsValue> {
+        self.context_lost.set(false);
+        self.render()
+    }
+
+    /// Re-render after a state change, warning once if the context was lost
+    fn refresh(&self) {
+        surface_context_loss(&self.context_lost, &self.canvas_id, || self.render());
+    }
+
     /// Render the heatmap
     pub fn render(&self) -> Result<(), JsValue> {
+        if !self.config.has_drawable_size() {
+            return Ok(());
+        }
+
         let (canvas, ctx) = get_canvas_context(&self.canvas_id)?;
+        let start = now_ms();
+        let result = track_context_loss(&self.context_lost, &ctx, || self.draw_frame(&canvas, &ctx));
+        self.render_timing.record(now_ms() - start);
+        result
+    }
 
+    /// Duration of the last render in milliseconds
+    pub fn last_render_ms(&self) -> f64 {
+        self.render_timing.last()
+    }
+
+    /// Average render duration over recent renders in milliseconds
+    pub fn avg_render_ms(&self) -> f64 {
+        self.render_timing.average()
+    }
+
+    /// Render offscreen and return raw RGBA bytes for pixel-level regression tests
+    pub fn render_to_image_data(&self, width: u32, height: u32) -> Result<Vec<u8>, JsValue> {
+        render_image_data(&self.config, width, height, |canvas, ctx| self.draw_frame(canvas, ctx))
+    }
+
+    fn draw_frame(&self, canvas: &HtmlCanvasElement, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
         canvas.set_width(self.config.width as u32);
         canvas.set_height(self.config.height as u32);
 
-        clear_canvas(&ctx, self.config.width, self.config.height, &self.config.theme.background);
+        self.draw_to(ctx)
+    }
+
+    /// Draw the full chart onto any target; the canvas must already be sized
+    pub(crate) fn draw_to<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        clear_canvas(ctx, self.config.width, self.config.height, &self.config.theme.background);
 
         if self.data.is_empty() {
-            self.draw_empty_state(&ctx)?;
+            self.draw_empty_state(ctx)?;
             return Ok(());
         }
 
         // Draw header
-        self.draw_header(&ctx)?;
+        self.draw_header(ctx)?;
 
         // Draw row labels
-        self.draw_row_labels(&ctx)?;
+        self.draw_row_labels(ctx)?;
 
         // Draw column headers
-        self.draw_column_headers(&ctx)?;
+        self.draw_column_headers(ctx)?;
+
+        // Draw flagged row highlight beneath the cells
+        if self.flag_row_highlight {
+            self.draw_flag_rows(ctx);
+        }
 
         // Draw cells
-        self.draw_cells(&ctx)?;
+        self.draw_cells(ctx)?;
 
         // Draw variance column
-        self.draw_variance_column(&ctx)?;
+        self.draw_variance_column(ctx)?;
 
         // Draw legend
         if self.config.show_legend {
-            self.draw_legend(&ctx)?;
+            self.draw_legend(ctx)?;
+        }
+
+        if self.show_variance_distribution {
+            self.draw_variance_distribution(ctx)?;
+        }
+
+        Ok(())
+    }
+
+    /// Inset plot area (x, y, width, height) in the top-right corner of the cells
+    fn inset_rect(&self) -> (f64, f64, f64, f64) {
+        let x = self.config.width - self.config.padding.right - 60.0 - INSET_WIDTH;
+        let y = self.config.padding.top + 10.0;
+        (x, y, INSET_WIDTH, INSET_HEIGHT)
+    }
+
+    /// Upper bound of the inset's variance axis
+    fn max_variance(&self) -> f64 {
+        let max = self.data.iter().map(|d| d.variance).fold(0.0, f64::max);
+        max.max(self.variance_threshold).max(1.0)
+    }
+
+    fn draw_variance_distribution<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        let (x, y, width, height) = self.inset_rect();
+        let max_variance = self.max_variance();
+
+        let mut bins = [0u32; INSET_BINS];
+        for data in &self.data {
+            let idx = ((data.variance / max_variance) * INSET_BINS as f64) as usize;
+            bins[idx.min(INSET_BINS - 1)] += 1;
         }
+        let max_count = bins.iter().copied().max().unwrap_or(0).max(1);
+
+        // Panel
+        ctx.set_fill_style(canvas_color(&self.config.theme.background));
+        ctx.set_global_alpha(0.9);
+        ctx.fill_rect(x - 6.0, y - 6.0, width + 12.0, height + 24.0);
+        ctx.set_global_alpha(1.0);
+        ctx.set_stroke_style(&self.config.theme.grid);
+        ctx.set_line_width(1.0);
+        ctx.stroke_rect(x - 6.0, y - 6.0, width + 12.0, height + 24.0);
+
+        // Bars, colored by which side of the threshold they fall on
+        let bar_width = width / INSET_BINS as f64;
+        for (i, count) in bins.iter().enumerate() {
+            let bar_height = *count as f64 / max_count as f64 * height;
+            let bin_start = i as f64 / INSET_BINS as f64 * max_variance;
+            let color = if bin_start >= self.variance_threshold {
+                &self.config.theme.danger
+            } else {
+                &self.config.theme.success
+            };
+            ctx.set_fill_style(color);
+            ctx.fill_rect(x + i as f64 * bar_width + 0.5, y + height - bar_height, bar_width - 1.0, bar_height);
+        }
+
+        // Threshold line and handle
+        let tx = x + (self.variance_threshold / max_variance).min(1.0) * width;
+        ctx.set_stroke_style(&self.config.theme.text);
+        ctx.set_line_width(1.5);
+        ctx.begin_path();
+        ctx.move_to(tx, y);
+        ctx.line_to(tx, y + height);
+        ctx.stroke();
+        ctx.set_fill_style(&self.config.theme.text);
+        ctx.fill_rect(tx - 4.0, y + height, 8.0, 6.0);
+
+        ctx.set_font(&self.config.font("", self.config.axis_size()));
+        ctx.set_text_align("left");
+        ctx.set_text_baseline("top");
+        ctx.fill_text(&format!("Variance (threshold {:.1})", self.variance_threshold), x, y + height + 8.0)?;
+        ctx.set_text_baseline("alphabetic");
 
         Ok(())
     }
 
-    fn draw_header(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
-        ctx.set_font(&format!("bold {}px {}", self.config.font_size + 2.0, self.config.font_family));
+    fn draw_header<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        ctx.set_fill_style(&self.config.theme.text);
+        ctx.set_font(&self.config.title_font());
         ctx.set_text_align("center");
         ctx.fill_text(
             "Score Variance by Assessor",
@@ -166,43 +731,73 @@ impl VarianceHeatmapChart {
         Ok(())
     }
 
-    fn draw_row_labels(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
+    fn draw_row_labels<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
         let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
-        let row_count = self.visible_rows.min(self.data.len());
+        let row_count = self.visible_rows().min(self.data.len());
         let cell_height = plot_height / row_count as f64;
 
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
-        ctx.set_font(&format!("{}px {}", self.config.font_size - 2.0, self.config.font_family));
+        ctx.set_fill_style(&self.config.theme.text);
+        ctx.set_font(&self.config.font("", self.config.axis_size()));
         ctx.set_text_align("right");
+        ctx.set_text_baseline("middle");
 
         let start_row = (self.scroll_offset / cell_height) as usize;
 
         for (i, data) in self.data.iter().enumerate().skip(start_row).take(row_count + 1) {
             let y = self.config.padding.top + (i - start_row) as f64 * cell_height + cell_height / 2.0;
 
-            // Truncate reference if too long
-            let ref_text = if data.reference.len() > 12 {
-                format!("{}...", &data.reference[..9])
-            } else {
-                data.reference.clone()
-            };
+            // Character-based display mode first, then a hard fit to the label area
+            let ref_text = self.format_reference(&data.reference);
+            let ref_text = truncate_to_width(ctx, &ref_text, ROW_LABEL_MAX_WIDTH);
 
-            ctx.fill_text(&ref_text, self.config.padding.left + 90.0, y + 4.0)?;
+            ctx.fill_text(&ref_text, self.config.padding.left + 90.0, y)?;
         }
 
+        ctx.set_text_baseline("alphabetic");
         Ok(())
     }
 
-    fn draw_column_headers(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
-        let plot_width = self.config.width - self.config.padding.left - self.config.padding.right;
-        let cell_width = (plot_width - 100.0) / self.max_assessors.max(1) as f64;
+    /// Shorten a reference for the row label, keeping its distinguishing part
+    fn format_reference(&self, reference: &str) -> String {
+        let char_count = reference.chars().count();
+        if char_count <= MAX_REFERENCE_CHARS {
+            return reference.to_string();
+        }
 
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
-        ctx.set_font(&format!("{}px {}", self.config.font_size - 2.0, self.config.font_family));
+        let keep = MAX_REFERENCE_CHARS - 3;
+        match self.reference_display {
+            ReferenceDisplay::Full => {
+                // Truncate reference if too long, on char boundaries so accented
+                // references can't split a multibyte character
+                format!("{}...", reference.chars().take(keep).collect::<String>())
+            }
+            ReferenceDisplay::Suffix => {
+                let token = reference
+                    .rsplit(self.reference_separator)
+                    .find(|t| !t.is_empty())
+                    .unwrap_or(reference);
+                let skip = token.chars().count().saturating_sub(keep);
+                format!("...{}", token.chars().skip(skip).collect::<String>())
+            }
+            ReferenceDisplay::Prefix => {
+                let token = reference
+                    .split(self.reference_separator)
+                    .find(|t| !t.is_empty())
+                    .unwrap_or(reference);
+                format!("{}...", token.chars().take(keep).collect::<String>())
+            }
+        }
+    }
+
+    fn draw_column_headers<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        let (start_col, col_count, cell_width) = self.column_layout();
+
+        ctx.set_fill_style(&self.config.theme.text);
+        ctx.set_font(&self.config.font("", self.config.axis_size()));
         ctx.set_text_align("center");
 
-        for col in 0..self.max_assessors {
-            let x = self.config.padding.left + 100.0 + col as f64 * cell_width + cell_width / 2.0;
+        for col in start_col..(start_col + col_count).min(self.max_assessors) {
+            let x = self.config.padding.left + 100.0 + (col - start_col) as f64 * cell_width + cell_width / 2.0;
             ctx.fill_text(&format!("A{}", col + 1), x, self.config.padding.top - 10.0)?;
         }
 
@@ -216,7 +811,31 @@ impl VarianceHeatmapChart {
         Ok(())
     }
 
-    fn draw_cells(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
+    fn draw_flag_rows<T: DrawTarget + ?Sized>(&self, ctx: &T) {
+        let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
+        let row_count = self.visible_rows().min(self.data.len());
+        let cell_height = plot_height / row_count as f64;
+        let start_row = (self.scroll_offset / cell_height) as usize;
+
+        let x = self.config.padding.left;
+        let width = self.config.width - self.config.padding.right - x;
+
+        ctx.set_fill_style(&self.config.theme.danger);
+        for (i, data) in self.data.iter().enumerate().skip(start_row).take(row_count + 1) {
+            if !self.is_flagged(data) {
+                continue;
+            }
+
+            let y = self.config.padding.top + (i - start_row) as f64 * cell_height;
+            ctx.set_global_alpha(0.12);
+            ctx.fill_rect(x, y, width, cell_height);
+            ctx.set_global_alpha(1.0);
+            // Left-edge marker
+            ctx.fill_rect(x, y + 1.0, 3.0, cell_height - 2.0);
+        }
+    }
+
+    fn draw_cells<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
         for cell in &self.cell_positions {
             if cell.row >= self.data.len() {
                 continue;
@@ -225,38 +844,39 @@ impl VarianceHeatmapChart {
             let data = &self.data[cell.row];
 
             // Get score for this cell if available
-            let score = data.scores.get(cell.col).copied();
+            let score = self.score_index(cell.row, cell.col).map(|i| data.scores[i]);
             let is_hovered = self.hovered_cell == Some((cell.row, cell.col));
 
             // Draw cell background
-            let bg_color = if let Some(s) = score {
-                // Color based on score value (normalized to 0-100)
-                let normalized = (s / 100.0).min(1.0).max(0.0);
-                interpolate_color(&self.config.theme.danger, &self.config.theme.success, normalized)
-            } else {
-                self.config.theme.grid.clone()
-            };
+            let bg_color = self.current_cell_color(cell.row, cell.col);
 
-            ctx.set_fill_style(&JsValue::from_str(&bg_color));
-            ctx.set_global_alpha(if is_hovered { 1.0 } else { 0.85 });
+            ctx.set_fill_style(&bg_color);
+            ctx.set_global_alpha(if is_hovered && self.hover_effect { 1.0 } else { 0.85 });
             ctx.fill_rect(cell.x + 1.0, cell.y + 1.0, cell.width - 2.0, cell.height - 2.0);
             ctx.set_global_alpha(1.0);
 
+            // Hatch cells that are not yet assessed so they can't be mistaken for a low score
+            if score.is_none() {
+                self.draw_hatch(ctx, cell.x + 1.0, cell.y + 1.0, cell.width - 2.0, cell.height - 2.0);
+            }
+
             // Draw score value if available
-            if let Some(s) = score {
-                ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
-                ctx.set_font(&format!("{}px {}", self.config.font_size - 2.0, self.config.font_family));
+            if let Some(s) = score.filter(|_| self.show_cell_values) {
+                ctx.set_fill_style("#FFFFFF");
+                ctx.set_font(&self.config.font("", self.config.axis_size()));
                 ctx.set_text_align("center");
+                ctx.set_text_baseline("middle");
                 ctx.fill_text(
-                    &format!("{:.0}", s),
+                    &self.formatters.number("score", s, "0"),
                     cell.x + cell.width / 2.0,
-                    cell.y + cell.height / 2.0 + 4.0,
+                    cell.y + cell.height / 2.0,
                 )?;
+                ctx.set_text_baseline("alphabetic");
             }
 
             // Draw border for hovered cell
             if is_hovered {
-                ctx.set_stroke_style(&JsValue::from_str(&self.config.theme.primary));
+                ctx.set_stroke_style(&self.config.theme.primary);
                 ctx.set_line_width(2.0);
                 ctx.stroke_rect(cell.x, cell.y, cell.width, cell.height);
             }
@@ -265,70 +885,90 @@ impl VarianceHeatmapChart {
         Ok(())
     }
 
-    fn draw_variance_column(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
+    fn draw_hatch<T: DrawTarget + ?Sized>(&self, ctx: &T, x: f64, y: f64, width: f64, height: f64) {
+        let spacing = 6.0;
+
+        ctx.save();
+        ctx.begin_path();
+        ctx.rect(x, y, width, height);
+        ctx.clip();
+
+        ctx.set_stroke_style(&self.config.theme.secondary);
+        ctx.set_line_width(1.0);
+        ctx.begin_path();
+        let mut offset = -height;
+        while offset < width {
+            ctx.move_to(x + offset, y + height);
+            ctx.line_to(x + offset + height, y);
+            offset += spacing;
+        }
+        ctx.stroke();
+        ctx.restore();
+    }
+
+    fn draw_variance_column<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
         let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
-        let row_count = self.visible_rows.min(self.data.len());
+        let row_count = self.visible_rows().min(self.data.len());
         let cell_height = plot_height / row_count as f64;
 
         let var_x = self.config.width - self.config.padding.right - 50.0;
         let start_row = (self.scroll_offset / cell_height) as usize;
 
-        ctx.set_font(&format!("bold {}px {}", self.config.font_size - 2.0, self.config.font_family));
+        ctx.set_font(&self.config.font("bold", self.config.axis_size()));
         ctx.set_text_align("center");
+        ctx.set_text_baseline("middle");
 
         for (i, data) in self.data.iter().enumerate().skip(start_row).take(row_count + 1) {
             let y = self.config.padding.top + (i - start_row) as f64 * cell_height;
 
             // Color based on variance (red if above threshold)
-            let is_flagged = data.variance > self.variance_threshold;
+            let is_flagged = self.is_flagged(data);
             let color = if is_flagged {
                 &self.config.theme.danger
             } else {
                 &self.config.theme.success
             };
 
-            ctx.set_fill_style(&JsValue::from_str(color));
+            ctx.set_fill_style(color);
             ctx.fill_rect(var_x, y + 1.0, 50.0, cell_height - 2.0);
 
             // Draw variance value
-            ctx.set_fill_style(&JsValue::from_str("#FFFFFF"));
-            ctx.fill_text(
-                &format!("{:.1}", data.variance),
-                var_x + 25.0,
-                y + cell_height / 2.0 + 4.0,
-            )?;
+            ctx.set_fill_style("#FFFFFF");
+            if self.show_variance_values {
+                ctx.fill_text(
+                    &self.formatters.number("variance", data.variance, "0.0"),
+                    var_x + 25.0,
+                    y + cell_height / 2.0,
+                )?;
+            }
 
             // Draw flag indicator
             if is_flagged {
-                ctx.fill_text("!", var_x + 45.0, y + 12.0)?;
+                ctx.fill_text("!", var_x + 45.0, y + cell_height / 2.0)?;
             }
         }
 
+        ctx.set_text_baseline("alphabetic");
         Ok(())
     }
 
-    fn draw_legend(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
+    fn draw_legend<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
         let legend_y = self.config.height - 25.0;
 
-        ctx.set_font(&format!("{}px {}", self.config.font_size - 2.0, self.config.font_family));
+        ctx.set_font(&self.config.font("", self.config.legend_size()));
         ctx.set_text_align("left");
 
         // Score gradient legend
         let gradient_width = 150.0;
         let gradient_x = self.config.padding.left;
 
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
+        ctx.set_fill_style(&self.config.theme.text);
         ctx.fill_text("Score:", gradient_x, legend_y)?;
 
         // Draw gradient
-        for i in 0..50 {
-            let x = gradient_x + 50.0 + i as f64 * 3.0;
-            let color = interpolate_color(&self.config.theme.danger, &self.config.theme.success, i as f64 / 49.0);
-            ctx.set_fill_style(&JsValue::from_str(&color));
-            ctx.fill_rect(x, legend_y - 10.0, 3.0, 12.0);
-        }
+        self.fill_score_gradient(ctx, gradient_x + 50.0, legend_y - 10.0, gradient_width, 12.0, 50);
 
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
+        ctx.set_fill_style(&self.config.theme.text);
         ctx.fill_text("0", gradient_x + 50.0, legend_y)?;
         ctx.fill_text("100", gradient_x + 155.0, legend_y)?;
 
@@ -336,22 +976,82 @@ impl VarianceHeatmapChart {
         let var_legend_x = self.config.width / 2.0;
         ctx.fill_text("Variance:", var_legend_x, legend_y)?;
 
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.success));
+        ctx.set_fill_style(&self.config.theme.success);
         ctx.fill_rect(var_legend_x + 60.0, legend_y - 10.0, 20.0, 12.0);
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
+        ctx.set_fill_style(&self.config.theme.text);
         ctx.fill_text(&format!("< {}", self.variance_threshold), var_legend_x + 85.0, legend_y)?;
 
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.danger));
+        ctx.set_fill_style(&self.config.theme.danger);
         ctx.fill_rect(var_legend_x + 130.0, legend_y - 10.0, 20.0, 12.0);
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.text));
+        ctx.set_fill_style(&self.config.theme.text);
         ctx.fill_text(&format!(">= {} (flagged)", self.variance_threshold), var_legend_x + 155.0, legend_y)?;
 
         Ok(())
     }
 
-    fn draw_empty_state(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
-        ctx.set_fill_style(&JsValue::from_str(&self.config.theme.secondary));
-        ctx.set_font(&format!("{}px {}", self.config.font_size, self.config.font_family));
+    /// Fill the score color ramp into a rectangle as `steps` vertical strips
+    fn fill_score_gradient<T: DrawTarget + ?Sized>(&self, ctx: &T, x: f64, y: f64, width: f64, height: f64, steps: u32) {
+        let steps = steps.max(2);
+        let strip = width / steps as f64;
+
+        for i in 0..steps {
+            let color = interpolate_color(
+                &self.config.theme.danger,
+                &self.config.theme.success,
+                i as f64 / (steps - 1) as f64,
+            );
+            ctx.set_fill_style(&color);
+            ctx.fill_rect(x + i as f64 * strip, y, strip.ceil(), height);
+        }
+    }
+
+    /// Render the score gradient and variance threshold swatches to a PNG data URL
+    pub fn export_gradient_legend(&self, width: u32, height: u32) -> Result<String, JsValue> {
+        let (canvas, ctx) = create_offscreen_canvas(width, height)?;
+        self.draw_gradient_legend(&ctx, width as f64, height as f64)?;
+        canvas.to_data_url()
+    }
+
+    fn draw_gradient_legend<T: DrawTarget + ?Sized>(&self, ctx: &T, w: f64, h: f64) -> Result<(), JsValue> {
+        clear_canvas(ctx, w, h, &self.config.theme.background);
+
+        // Swatches fill the top of the image with labels underneath
+        let swatch_height = (h - self.config.legend_size() - 4.0).max(h * 0.5);
+        let label_y = h - 2.0;
+
+        // Score gradient across the left half, one strip per pixel
+        let gradient_width = (w * 0.5 - 10.0).max(1.0);
+        self.fill_score_gradient(ctx, 0.0, 0.0, gradient_width, swatch_height, gradient_width as u32);
+
+        ctx.set_fill_style(&self.config.theme.text);
+        ctx.set_font(&self.config.font("", self.config.legend_size()));
+        ctx.set_text_align("left");
+        ctx.fill_text("0", 0.0, label_y)?;
+        ctx.set_text_align("right");
+        ctx.fill_text("100", gradient_width, label_y)?;
+
+        // Variance threshold swatches across the right half
+        let swatch_width = w * 0.25 - 5.0;
+        let swatches = [
+            (&self.config.theme.success, format!("< {}", self.variance_threshold)),
+            (&self.config.theme.danger, format!(">= {}", self.variance_threshold)),
+        ];
+
+        ctx.set_text_align("left");
+        for (i, (color, label)) in swatches.iter().enumerate() {
+            let x = w * 0.5 + i as f64 * w * 0.25;
+            ctx.set_fill_style(color);
+            ctx.fill_rect(x, 0.0, swatch_width, swatch_height);
+            ctx.set_fill_style(&self.config.theme.text);
+            ctx.fill_text(label, x, label_y)?;
+        }
+
+        Ok(())
+    }
+
+    fn draw_empty_state<T: DrawTarget + ?Sized>(&self, ctx: &T) -> Result<(), JsValue> {
+        ctx.set_fill_style(readable_on(&self.config.theme.secondary, &self.config.theme.background));
+        ctx.set_font(&self.config.font("", self.config.label_size()));
         ctx.set_text_align("center");
         ctx.fill_text(
             "No variance data available",
@@ -361,17 +1061,31 @@ impl VarianceHeatmapChart {
         Ok(())
     }
 
+    /// Scroll assessor columns horizontally; the reference and variance columns stay fixed
+    pub fn on_horizontal_scroll(&mut self, delta_x: f64) {
+        self.scroll_columns(delta_x);
+        self.refresh();
+    }
+
+    fn scroll_columns(&mut self, delta_x: f64) {
+        let (_, visible, cell_width) = self.column_layout();
+        let max_scroll = (self.max_assessors.max(1) - visible) as f64 * cell_width;
+
+        self.scroll_x_offset = (self.scroll_x_offset + delta_x).max(0.0).min(max_scroll);
+        self.compute_cell_positions();
+    }
+
     /// Handle scroll
     pub fn on_scroll(&mut self, delta_y: f64) {
         let plot_height = self.config.height - self.config.padding.top - self.config.padding.bottom;
-        let row_count = self.visible_rows.min(self.data.len());
+        let row_count = self.visible_rows().min(self.data.len());
         let cell_height = plot_height / row_count as f64;
 
         let max_scroll = (self.data.len() as f64 - row_count as f64) * cell_height;
 
         self.scroll_offset = (self.scroll_offset + delta_y).max(0.0).min(max_scroll.max(0.0));
         self.compute_cell_positions();
-        self.render().ok();
+        self.refresh();
     }
 
     /// Handle mouse move
@@ -386,28 +1100,15 @@ impl VarianceHeatmapChart {
                 self.hovered_cell = Some((cell.row, cell.col));
 
                 if old_hovered != self.hovered_cell {
-                    self.render().ok();
+                    self.refresh();
                 }
 
                 if cell.row < self.data.len() {
                     let data = &self.data[cell.row];
-                    let score = data.scores.get(cell.col).copied();
-                    let assessor = data.assessor_names.get(cell.col)
-                        .cloned()
-                        .unwrap_or_else(|| format!("Assessor {}", cell.col + 1));
-
                     let result = HitTestResult::hit(
                         &format!("{}-{}", data.application_id, cell.col),
                         "heatmap_cell",
-                        serde_json::json!({
-                            "applicationId": data.application_id,
-                            "reference": data.reference,
-                            "assessor": assessor,
-                            "score": score,
-                            "variance": data.variance,
-                            "mean": data.mean,
-                            "flagged": data.flagged
-                        }),
+                        self.cell_data(cell.row, cell.col),
                     );
                     return serde_wasm_bindgen::to_value(&result).unwrap();
                 }
@@ -416,15 +1117,80 @@ impl VarianceHeatmapChart {
 
         self.hovered_cell = None;
         if old_hovered.is_some() {
-            self.render().ok();
+            self.refresh();
         }
-        serde_wasm_bindgen::to_value(&HitTestResult::miss()).unwrap()
+        HitTestResult::miss_value()
+    }
+
+    /// Hover payload for one cell: its score (or awaiting state) and the row's variance
+    fn cell_data(&self, row: usize, col: usize) -> serde_json::Value {
+        let data = &self.data[row];
+        let index = self.score_index(row, col);
+        let score = index.map(|i| data.scores[i]);
+        let assessor = index
+            .and_then(|i| data.assessor_names.get(i))
+            .cloned()
+            .unwrap_or_else(|| format!("Assessor {}", col + 1));
+        let assessed = self.cell_index[row].iter().filter(|i| i.is_some()).count();
+
+        serde_json::json!({
+            "applicationId": data.application_id,
+            "reference": data.reference,
+            "assessor": assessor,
+            "assessorId": self.column_ids.get(col),
+            "column": col,
+            "score": score,
+            "variance": data.variance,
+            "mean": data.mean,
+            "flagged": self.is_flagged(data),
+            "awaiting": score.is_none(),
+            "awaitingCount": self.max_assessors.saturating_sub(assessed),
+            "formatted": {
+                "score": score.map(|s| self.formatters.number("score", s, "0")),
+                "variance": self.formatters.number("variance", data.variance, "0.0"),
+                "mean": self.formatters.number("score", data.mean, "0.0")
+            }
+        })
+    }
+
+    /// Per-assessor leniency: average deviation of their scores from each row's
+    /// mean (positive = lenient) and how many rows they scored, by aligned column
+    pub fn get_assessor_bias(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.assessor_bias()).unwrap()
+    }
+
+    /// One bias entry per aligned column, behind `get_assessor_bias`
+    fn assessor_bias(&self) -> Vec<serde_json::Value> {
+        (0..self.max_assessors)
+            .map(|col| {
+                let mut name = None;
+                let mut deviation_sum = 0.0;
+                let mut scored = 0u32;
+
+                for (row, data) in self.data.iter().enumerate() {
+                    if let Some(i) = self.score_index(row, col) {
+                        deviation_sum += data.scores[i] - data.mean;
+                        scored += 1;
+                        if name.is_none() {
+                            name = data.assessor_names.get(i).cloned();
+                        }
+                    }
+                }
+
+                serde_json::json!({
+                    "assessorId": self.column_key(col),
+                    "name": name,
+                    "bias": if scored > 0 { deviation_sum / scored as f64 } else { 0.0 },
+                    "scoredCount": scored
+                })
+            })
+            .collect()
     }
 
     /// Get flagged applications
     pub fn get_flagged(&self) -> JsValue {
         let flagged: Vec<_> = self.data.iter()
-            .filter(|d| d.variance > self.variance_threshold)
+            .filter(|d| self.is_flagged(d))
             .map(|d| serde_json::json!({
                 "applicationId": d.application_id,
                 "reference": d.reference,
@@ -440,7 +1206,7 @@ impl VarianceHeatmapChart {
     /// Get statistics
     pub fn get_stats(&self) -> JsValue {
         let total_count = self.data.len();
-        let flagged_count = self.data.iter().filter(|d| d.flagged).count();
+        let flagged_count = self.flagged_count();
         let avg_variance = if total_count > 0 {
             self.data.iter().map(|d| d.variance).sum::<f64>() / total_count as f64
         } else {
@@ -457,4 +1223,355 @@ impl VarianceHeatmapChart {
         });
         serde_wasm_bindgen::to_value(&stats).unwrap()
     }
+
+    /// One-sentence summary for screen readers (e.g. a canvas `aria-label`)
+    pub fn describe(&self) -> String {
+        let total_count = self.data.len();
+        if total_count == 0 {
+            return "Score variance heatmap with no applications".to_string();
+        }
+        let flagged_count = self.flagged_count();
+        let avg_variance = self.data.iter().map(|d| d.variance).sum::<f64>() / total_count as f64;
+
+        format!(
+            "Score variance heatmap of {} applications; {} flagged above variance {} ({:.0}%); average variance {:.1}",
+            total_count,
+            flagged_count,
+            self.variance_threshold,
+            flagged_count as f64 / total_count as f64 * 100.0,
+            avg_variance
+        )
+    }
+}
+
+/// Rows are placed either all by assessor id or all by position, and no row may
+/// list the same assessor twice
+fn check_assessor_ids(rows: &[VarianceDataPoint]) -> Result<(), String> {
+    let with_ids = rows.iter().filter(|d| !d.assessor_ids.is_empty()).count();
+    if with_ids > 0 && with_ids < rows.len() {
+        return Err(format!(
+            "Assessor ids given for {} of {} applications; provide them for all or none",
+            with_ids,
+            rows.len()
+        ));
+    }
+
+    for row in rows {
+        let mut seen = std::collections::HashSet::new();
+        if let Some(id) = row.assessor_ids.iter().find(|id| !seen.insert(id.as_str())) {
+            return Err(format!("Duplicate assessor {} in application {}", id, row.application_id));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::charts::RecordingTarget;
+
+    fn row(id: &str, variance: f64, flagged: bool) -> VarianceDataPoint {
+        VarianceDataPoint {
+            application_id: id.to_string(),
+            reference: format!("APP-{}", id),
+            scores: vec![60.0, 70.0],
+            assessor_names: vec!["Ada".to_string(), "Ben".to_string()],
+            assessor_ids: vec!["ada".to_string(), "ben".to_string()],
+            variance,
+            mean: 65.0,
+            flagged,
+        }
+    }
+
+    #[test]
+    fn describe_reports_flagged_share_and_average() {
+        let mut chart = VarianceHeatmapChart::with_config("test", ChartConfig::default());
+        chart.load_rows(vec![row("1", 4.0, false), row("2", 12.0, true), row("3", 20.0, true)]);
+        assert_eq!(
+            chart.describe(),
+            "Score variance heatmap of 3 applications; 2 flagged above variance 10 (67%); average variance 12.0"
+        );
+
+        let empty = VarianceHeatmapChart::with_config("test", ChartConfig::default());
+        assert_eq!(empty.describe(), "Score variance heatmap with no applications");
+    }
+
+    #[test]
+    fn full_reference_truncates_accented_text_on_char_boundaries() {
+        let chart = VarianceHeatmapChart::with_config("test", ChartConfig::default());
+        assert_eq!(chart.format_reference("Café-Étude"), "Café-Étude");
+        assert_eq!(chart.format_reference("Énergie-Côtière-2024"), "Énergie-C...");
+    }
+
+    #[test]
+    fn suffix_and_prefix_keep_the_distinguishing_token() {
+        let mut chart = VarianceHeatmapChart::with_config("test", ChartConfig::default());
+        chart.reference_display = ReferenceDisplay::Suffix;
+        assert_eq!(chart.format_reference("FUND-2024-ÉCOLE-0042"), "...0042");
+        assert_eq!(chart.format_reference("FUND-2024-RÉSEAU-ÉLÈVES-ÉTÉ"), "...ÉTÉ");
+
+        chart.reference_display = ReferenceDisplay::Prefix;
+        assert_eq!(chart.format_reference("ÉCOLE-2024-0000042"), "ÉCOLE...");
+    }
+
+    #[test]
+    fn threshold_drag_updates_threshold_and_flagged_count() {
+        let mut chart = VarianceHeatmapChart::with_config("test", ChartConfig::default());
+        chart.load_rows(vec![row("1", 4.0, false), row("2", 12.0, true), row("3", 20.0, true)]);
+        assert!(!chart.drag_threshold_to(0.0), "no inset to drag while it is hidden");
+
+        chart.show_variance_distribution = true;
+        let (inset_x, _, inset_width, _) = chart.inset_rect();
+        assert!(chart.drag_threshold_to(inset_x + inset_width * 0.9));
+        assert_eq!(chart.variance_threshold, 18.0);
+        assert_eq!(chart.flagged_count(), 1);
+
+        assert!(chart.drag_threshold_to(inset_x + inset_width * 0.1));
+        assert_eq!(chart.variance_threshold, 2.0);
+        assert_eq!(chart.flagged_count(), 3);
+
+        // The payload's own flags are left as sent
+        let sent: Vec<bool> = chart.data.iter().map(|d| d.flagged).collect();
+        assert_eq!(sent, vec![false, true, true]);
+    }
+
+    #[test]
+    fn assessors_listed_in_different_orders_share_columns() {
+        let mut first = row("1", 25.0, false);
+        first.scores = vec![60.0, 70.0];
+        let mut second = row("2", 25.0, false);
+        second.assessor_ids = vec!["ben".to_string(), "ada".to_string()];
+        second.scores = vec![80.0, 90.0];
+
+        let mut chart = VarianceHeatmapChart::with_config("test", ChartConfig::default());
+        chart.load_rows(vec![first, second]);
+        assert_eq!(chart.column_ids, vec!["ada", "ben"]);
+        // Ada's column holds Ada's score in both rows
+        assert_eq!(chart.score_index(0, 0).map(|i| chart.data[0].scores[i]), Some(60.0));
+        assert_eq!(chart.score_index(1, 0).map(|i| chart.data[1].scores[i]), Some(90.0));
+        assert_eq!(chart.score_index(1, 1).map(|i| chart.data[1].scores[i]), Some(80.0));
+    }
+
+    #[test]
+    fn duplicate_or_partial_assessor_ids_are_rejected() {
+        assert!(check_assessor_ids(&[row("1", 4.0, false), row("2", 4.0, false)]).is_ok());
+
+        let mut duplicate = row("1", 4.0, false);
+        duplicate.assessor_ids = vec!["ada".to_string(), "ada".to_string()];
+        assert_eq!(check_assessor_ids(&[duplicate]), Err("Duplicate assessor ada in application 1".to_string()));
+
+        let mut positional = row("2", 4.0, false);
+        positional.assessor_ids.clear();
+        assert!(check_assessor_ids(&[row("1", 4.0, false), positional.clone()]).is_err());
+        assert!(check_assessor_ids(&[positional]).is_ok());
+    }
+
+    #[test]
+    fn updating_a_score_changes_only_that_rows_variance() {
+        let mut swapped = row("2", 25.0, false);
+        swapped.assessor_ids = vec!["ben".to_string(), "ada".to_string()];
+        let mut chart = VarianceHeatmapChart::with_config("test", ChartConfig::default());
+        chart.load_rows(vec![row("1", 25.0, false), swapped]);
+
+        // Column 0 is Ada, which is the second score in row 2
+        assert_eq!(chart.set_score("2", 0, 90.0), Ok(1));
+        assert_eq!(chart.data[1].scores, vec![60.0, 90.0]);
+        assert_eq!(chart.data[1].mean, 75.0);
+        assert_eq!(chart.data[1].variance, 225.0);
+        assert_eq!(chart.data[0].scores, vec![60.0, 70.0]);
+        assert_eq!(chart.data[0].variance, 25.0);
+
+        assert!(chart.set_score("3", 0, 50.0).is_err());
+        assert!(chart.set_score("1", 5, 50.0).is_err());
+    }
+
+    #[test]
+    fn taller_canvas_shows_more_rows_at_a_fixed_row_height() {
+        let rows: Vec<VarianceDataPoint> = (0..60).map(|i| row(&i.to_string(), 4.0, false)).collect();
+        let rows_shown = |height: f64| {
+            let mut chart = VarianceHeatmapChart::with_config("test", ChartConfig { height, ..ChartConfig::default() });
+            chart.load_rows(rows.clone());
+            let mut shown: Vec<usize> = chart.cell_positions.iter().map(|c| c.row).collect();
+            shown.dedup();
+            (chart.visible_rows(), shown.len())
+        };
+
+        let (short_rows, short_shown) = rows_shown(400.0);
+        let (tall_rows, tall_shown) = rows_shown(800.0);
+        assert!(tall_rows > short_rows, "{} vs {}", tall_rows, short_rows);
+        assert!(tall_shown > short_shown);
+    }
+
+    #[test]
+    fn horizontal_scroll_shifts_the_materialized_columns() {
+        let mut wide = row("1", 4.0, false);
+        wide.assessor_ids = (0..12).map(|i| format!("as{}", i)).collect();
+        wide.scores = vec![50.0; 12];
+        let mut chart = VarianceHeatmapChart::with_config("test", ChartConfig::default());
+        chart.load_rows(vec![wide]);
+        chart.min_column_width = 100.0;
+        chart.scroll_columns(0.0);
+
+        let columns = |chart: &VarianceHeatmapChart| chart.cell_positions.iter().map(|c| c.col).collect::<Vec<_>>();
+        let (_, visible, cell_width) = chart.column_layout();
+        assert!(visible < 12);
+        assert_eq!(columns(&chart), (0..visible).collect::<Vec<_>>());
+
+        chart.scroll_columns(cell_width * 2.0);
+        assert_eq!(columns(&chart), (2..visible + 2).collect::<Vec<_>>());
+
+        // Scrolling past the end stops at the last column
+        chart.scroll_columns(1e6);
+        assert_eq!(columns(&chart).last(), Some(&11));
+    }
+
+    #[test]
+    fn gradient_legend_draws_score_labels_and_threshold_swatches() {
+        let chart = VarianceHeatmapChart::with_config("test", ChartConfig::default());
+        let target = RecordingTarget::new();
+        chart.draw_gradient_legend(&target, 200.0, 40.0).unwrap();
+
+        assert_eq!(target.texts(), vec!["0", "100", "< 10", ">= 10"]);
+
+        // Threshold swatches split the right half
+        let rects: Vec<Vec<f64>> = target
+            .calls()
+            .iter()
+            .filter(|c| c.op == "fill_rect")
+            .map(|c| c.args.clone())
+            .collect();
+        let swatch_height = rects[rects.len() - 1][3];
+        assert_eq!(rects[rects.len() - 2], vec![100.0, 0.0, 45.0, swatch_height]);
+        assert_eq!(rects[rects.len() - 1], vec![150.0, 0.0, 45.0, swatch_height]);
+        assert!((20.0..40.0).contains(&swatch_height));
+    }
+
+    #[test]
+    fn missing_cell_is_hatched_and_reported_as_awaiting() {
+        let mut partial = row("2", 0.0, false);
+        partial.scores.truncate(1);
+        partial.assessor_names.truncate(1);
+        partial.assessor_ids.truncate(1);
+        let mut chart = VarianceHeatmapChart::with_config("test", ChartConfig::default());
+        chart.load_rows(vec![row("1", 50.0, false), partial]);
+
+        let target = RecordingTarget::new();
+        chart.draw_cells(&target).unwrap();
+        // Only Ben's cell on the second row is clipped for hatching
+        let missing = chart.cell_positions.iter().find(|c| (c.row, c.col) == (1, 1)).unwrap();
+        let clipped: Vec<Vec<f64>> = target
+            .calls()
+            .iter()
+            .filter(|c| c.op == "rect")
+            .map(|c| c.args.clone())
+            .collect();
+        assert_eq!(clipped, vec![vec![missing.x + 1.0, missing.y + 1.0, missing.width - 2.0, missing.height - 2.0]]);
+
+        let data = chart.cell_data(1, 1);
+        assert_eq!(data["awaiting"], true);
+        assert_eq!(data["awaitingCount"], 1);
+        assert_eq!(data["score"], serde_json::Value::Null);
+        assert_eq!(chart.cell_data(1, 0)["awaiting"], false);
+    }
+
+    #[test]
+    fn flagged_rows_take_the_highlight_path() {
+        let mut chart = VarianceHeatmapChart::with_config("test", ChartConfig::default());
+        chart.load_rows(vec![row("1", 4.0, false), row("2", 20.0, true), row("3", 5.0, false)]);
+        let row_band = |chart: &VarianceHeatmapChart| {
+            let target = RecordingTarget::new();
+            chart.draw_to(&target).unwrap();
+            // Each highlighted row gets a 3px left-edge marker
+            target
+                .calls()
+                .iter()
+                .filter(|c| c.op == "fill_rect" && c.args[0] == chart.config.padding.left && c.args[2] == 3.0)
+                .map(|c| c.args[1])
+                .collect::<Vec<f64>>()
+        };
+
+        assert!(row_band(&chart).is_empty());
+
+        chart.flag_row_highlight = true;
+        let markers = row_band(&chart);
+        let second_row = chart.cell_positions.iter().find(|c| c.row == 1).unwrap();
+        assert_eq!(markers, vec![second_row.y + 1.0]);
+    }
+
+    #[test]
+    fn changed_cell_is_between_colors_mid_animation() {
+        let mut chart = VarianceHeatmapChart::with_config("test", ChartConfig::default());
+        chart.cell_change_animation = true;
+        chart.load_rows(vec![row("1", 50.0, false)]);
+        let old_color = chart.cell_color(0, 0);
+
+        let mut rescored = row("1", 50.0, false);
+        rescored.scores[0] = 95.0;
+        chart.load_rows(vec![rescored]);
+        // Halfway through the tween; animate would also redraw
+        for transition in &mut chart.cell_transitions {
+            transition.elapsed_ms = CELL_TRANSITION_MS / 2.0;
+        }
+
+        let final_color = chart.cell_color(0, 0);
+        let mid_color = chart.current_cell_color(0, 0);
+        assert_ne!(old_color, final_color);
+        assert_ne!(mid_color, final_color);
+        assert_ne!(mid_color, old_color);
+        // The untouched cell is drawn at its final color
+        assert_eq!(chart.current_cell_color(0, 1), chart.cell_color(0, 1));
+    }
+
+    #[test]
+    fn lenient_assessor_shows_a_positive_bias() {
+        let panel = |id: &str, scores: [f64; 3]| VarianceDataPoint {
+            application_id: id.to_string(),
+            reference: format!("APP-{}", id),
+            scores: scores.to_vec(),
+            assessor_names: vec!["Ada".to_string(), "Ben".to_string(), "Cy".to_string()],
+            assessor_ids: vec!["ada".to_string(), "ben".to_string(), "cy".to_string()],
+            variance: 0.0,
+            mean: scores.iter().sum::<f64>() / 3.0,
+            flagged: false,
+        };
+        let mut chart = VarianceHeatmapChart::with_config("test", ChartConfig::default());
+        // Ada scores 10 above each row's mean, Cy 10 below
+        chart.load_rows(vec![panel("1", [80.0, 70.0, 60.0]), panel("2", [55.0, 45.0, 35.0])]);
+
+        let bias = chart.assessor_bias();
+        assert_eq!(bias[0]["assessorId"], "ada");
+        assert!((bias[0]["bias"].as_f64().unwrap() - 10.0).abs() < 1e-9);
+        assert_eq!(bias[0]["scoredCount"], 2);
+        assert!(bias[1]["bias"].as_f64().unwrap().abs() < 1e-9);
+        assert!((bias[2]["bias"].as_f64().unwrap() + 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reload_keeps_the_hovered_cell_while_its_row_exists() {
+        let mut chart = VarianceHeatmapChart::with_config("test", ChartConfig::default());
+        chart.load_rows(vec![row("1", 4.0, false), row("2", 12.0, true)]);
+        chart.hovered_cell = Some((1, 1));
+
+        // Row "2" moves to the top
+        chart.load_rows(vec![row("2", 12.0, true), row("3", 6.0, false), row("1", 4.0, false)]);
+        assert_eq!(chart.hovered_cell, Some((0, 1)));
+
+        chart.load_rows(vec![row("1", 4.0, false), row("3", 6.0, false)]);
+        assert_eq!(chart.hovered_cell, None);
+    }
+
+    #[test]
+    fn cell_values_can_be_hidden() {
+        let mut chart = VarianceHeatmapChart::with_config("test", ChartConfig::default());
+        chart.load_rows(vec![row("1", 50.0, false), row("2", 50.0, false)]);
+        let cell_texts = |chart: &VarianceHeatmapChart| {
+            let target = RecordingTarget::new();
+            chart.draw_cells(&target).unwrap();
+            target.texts()
+        };
+
+        assert_eq!(cell_texts(&chart), vec!["60", "70", "60", "70"]);
+        chart.show_cell_values = false;
+        assert!(cell_texts(&chart).is_empty());
+    }
 }
@@ -25,6 +25,65 @@ pub fn version() -> String {
 /// Performance check - returns approximate render capability
 #[wasm_bindgen]
 pub fn benchmark_canvas(canvas_id: &str, iterations: u32) -> Result<f64, JsValue> {
+    let (performance, ctx) = benchmark_context(canvas_id)?;
+    let start = performance.now();
+
+    for i in 0..iterations {
+        let x = (i % 100) as f64 * 5.0;
+        let y = (i / 100) as f64 * 5.0;
+        ctx.fill_rect(x, y, 4.0, 4.0);
+    }
+
+    let end = performance.now();
+    Ok(end - start)
+}
+
+/// Time individual canvas primitives. `op` is "rect", "arc" (arc + fill),
+/// "curve" (quadratic_curve_to + stroke), "text" (fill_text) or "all".
+/// Returns `{op: milliseconds}` for each op run.
+#[wasm_bindgen]
+pub fn benchmark_canvas_ops(canvas_id: &str, iterations: u32, op: &str) -> Result<JsValue, JsValue> {
+    const OPS: [&str; 4] = ["rect", "arc", "curve", "text"];
+
+    let ops: Vec<&str> = match op {
+        "all" => OPS.to_vec(),
+        _ if OPS.contains(&op) => vec![op],
+        _ => return Err(JsValue::from_str(&format!("Unknown benchmark op: {}", op))),
+    };
+
+    let (performance, ctx) = benchmark_context(canvas_id)?;
+    ctx.set_font("10px sans-serif");
+    let mut results = serde_json::Map::new();
+
+    for op in ops {
+        let start = performance.now();
+        for i in 0..iterations {
+            let x = (i % 100) as f64 * 5.0;
+            let y = (i / 100) as f64 * 5.0;
+            match op {
+                "rect" => ctx.fill_rect(x, y, 4.0, 4.0),
+                "arc" => {
+                    ctx.begin_path();
+                    ctx.arc(x + 2.0, y + 2.0, 2.0, 0.0, std::f64::consts::PI * 2.0)?;
+                    ctx.fill();
+                }
+                "curve" => {
+                    ctx.begin_path();
+                    ctx.move_to(x, y);
+                    ctx.quadratic_curve_to(x + 2.0, y + 4.0, x + 4.0, y);
+                    ctx.stroke();
+                }
+                _ => ctx.fill_text("0", x, y)?,
+            }
+        }
+        results.insert(op.to_string(), serde_json::json!(performance.now() - start));
+    }
+
+    Ok(serde_wasm_bindgen::to_value(&results)?)
+}
+
+/// Canvas context and performance API for the benchmarks
+fn benchmark_context(canvas_id: &str) -> Result<(web_sys::Performance, web_sys::CanvasRenderingContext2d), JsValue> {
     use web_sys::window;
 
     let window = window().ok_or("No window")?;
@@ -39,14 +98,5 @@ pub fn benchmark_canvas(canvas_id: &str, iterations: u32) -> Result<f64, JsValue
         .dyn_into::<web_sys::CanvasRenderingContext2d>()?;
 
     let performance = window.performance().ok_or("No performance API")?;
-    let start = performance.now();
-
-    for i in 0..iterations {
-        let x = (i % 100) as f64 * 5.0;
-        let y = (i / 100) as f64 * 5.0;
-        ctx.fill_rect(x, y, 4.0, 4.0);
-    }
-
-    let end = performance.now();
-    Ok(end - start)
+    Ok((performance, ctx))
 }
@@ -0,0 +1,52 @@
+//! Allocation counting for hit-test misses. Lives in its own test binary so
+//! the counting global allocator doesn't wrap the library's unit tests.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use funding_viz::HitTestResult;
+
+/// Counts allocations made on the current thread, so parallel tests don't interfere
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations_during(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+fn miss_sweep_does_not_allocate() {
+    // What each miss used to build: a fresh JSON object per pointer move
+    let per_move_json = allocations_during(|| {
+        for _ in 0..1000 {
+            std::hint::black_box(serde_json::json!({ "hit": false, "elementType": "none" }));
+        }
+    });
+    let shared_miss = allocations_during(|| {
+        for _ in 0..1000 {
+            std::hint::black_box(HitTestResult::miss());
+        }
+    });
+
+    assert!(per_move_json >= 1000, "expected the JSON path to allocate, got {}", per_move_json);
+    assert_eq!(shared_miss, 0);
+}
@@ -0,0 +1,17 @@
+//! The shared miss object needs a JS host: run with `wasm-pack test --node`.
+#![cfg(target_arch = "wasm32")]
+
+use funding_viz::HitTestResult;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn miss_value_matches_miss_and_is_shared_frozen() {
+    let value = HitTestResult::miss_value();
+    let decoded: serde_json::Value = serde_wasm_bindgen::from_value(value.clone()).unwrap();
+    assert_eq!(decoded, serde_json::to_value(HitTestResult::miss()).unwrap());
+
+    // Every miss is the same object, and writes to it are ignored
+    assert_eq!(HitTestResult::miss_value(), value);
+    assert!(js_sys::Object::is_frozen(value.unchecked_ref()));
+}